@@ -3,7 +3,8 @@ use acir::{
         opcodes::{BlackBoxFuncCall, FunctionInput},
         Circuit, Opcode,
     },
-    native_types::Witness,
+    native_types::{Expression, Witness},
+    FieldElement,
 };
 use std::collections::{BTreeMap, HashSet};
 
@@ -48,54 +49,191 @@ impl RangeOptimizer {
     /// be 16 bits.
     fn collect_ranges(circuit: &Circuit) -> BTreeMap<Witness, u32> {
         let mut witness_to_bit_sizes: BTreeMap<Witness, u32> = BTreeMap::new();
+        let mut constant_bit_sizes: Vec<(Witness, u32)> = Vec::new();
 
         for opcode in &circuit.opcodes {
-            let Some((witness, num_bits)) = (match opcode {
-                Opcode::AssertZero(expr) => {
-                    // If the opcode is constraining a witness to be equal to a value then it can be considered
-                    // as a range opcode for the number of bits required to hold that value.
-                    if expr.is_degree_one_univariate() {
-                        let (k, witness) = expr.linear_combinations[0];
-                        let constant = expr.q_c;
-                        let witness_value = -constant / k;
-
-                        if witness_value.is_zero() {
-                            Some((witness, 0))
-                        } else {
-                            // We subtract off 1 bit from the implied witness value to give the weakest range constraint
-                            // which would be stricter than the constraint imposed by this opcode.
-                            let implied_range_constraint_bits = witness_value.num_bits() - 1;
-                            Some((witness, implied_range_constraint_bits))
-                        }
+            match opcode {
+                // If the opcode is constraining a witness to be equal to a value then it can be considered
+                // as a range opcode for the number of bits required to hold that value.
+                Opcode::AssertZero(expr) if expr.is_degree_one_univariate() => {
+                    let (k, witness) = expr.linear_combinations[0];
+                    let constant = expr.q_c;
+                    let witness_value = -constant / k;
+
+                    if witness_value.is_zero() {
+                        constant_bit_sizes.push((witness, 0));
                     } else {
-                        None
+                        // We subtract off 1 bit from the implied witness value to give the weakest range constraint
+                        // which would be stricter than the constraint imposed by this opcode.
+                        let implied_range_constraint_bits = witness_value.num_bits() - 1;
+                        constant_bit_sizes.push((witness, implied_range_constraint_bits));
                     }
                 }
 
-
                 Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
                     input: FunctionInput { witness, num_bits },
                 }) => {
-                    Some((*witness, *num_bits))
+                    insert_bit_size(&mut witness_to_bit_sizes, *witness, *num_bits);
                 }
 
-                _ => None,
-            }) else {
-                continue;
-            };
+                _ => (),
+            }
+        }
 
-            // Check if the witness has already been recorded and if the witness
-            // size is more than the current one, we replace it
-            witness_to_bit_sizes
-                .entry(witness)
-                .and_modify(|old_range_bits| {
-                    *old_range_bits = std::cmp::min(*old_range_bits, num_bits);
-                })
-                .or_insert(num_bits);
+        // Inference relies on each witness being strictly less than `2^num_bits`, which doesn't hold for the
+        // ranges implied by constants, so these are only merged in afterwards.
+        // Each kind of inference may enable the others, e.g. a witness equal to a boolean may be negated by a `NOT`,
+        // so they are repeated until none of them finds a new range.
+        loop {
+            let found_equal = Self::collect_equalities(circuit, &mut witness_to_bit_sizes);
+            let found_complement =
+                Self::collect_boolean_complements(circuit, &mut witness_to_bit_sizes);
+            let found_sum = Self::collect_bounded_sums(circuit, &mut witness_to_bit_sizes);
+            if !(found_equal || found_complement || found_sum) {
+                break;
+            }
         }
+
+        for (witness, num_bits) in constant_bit_sizes {
+            insert_bit_size(&mut witness_to_bit_sizes, witness, num_bits);
+        }
+
         witness_to_bit_sizes
     }
 
+    /// Shares the ranges of witnesses which are constrained to be equal to each other.
+    ///
+    /// An equality is encoded as the opcode `x - y = 0` (or a multiple of it), so the lower of the bit sizes known
+    /// for `x` and `y` applies to both of them, including when one of them is known to be boolean.
+    /// We repeat this until no new ranges are found so that chains of equalities are resolved.
+    ///
+    /// Returns whether any new range was found.
+    fn collect_equalities(
+        circuit: &Circuit,
+        witness_to_bit_sizes: &mut BTreeMap<Witness, u32>,
+    ) -> bool {
+        let equalities: Vec<(Witness, Witness)> = circuit
+            .opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::AssertZero(expr) => equality(expr),
+                _ => None,
+            })
+            .collect();
+
+        let mut found_any = false;
+        let mut found_new_range = true;
+        while found_new_range {
+            found_new_range = false;
+            for (lhs, rhs) in &equalities {
+                for (known, other) in [(lhs, rhs), (rhs, lhs)] {
+                    let Some(num_bits) = witness_to_bit_sizes.get(known).copied() else {
+                        continue;
+                    };
+                    // Only strictly smaller ranges are recorded, so the range opcode of the witness which the range
+                    // was taken from is never removed.
+                    if witness_to_bit_sizes.get(other).map_or(true, |old_bits| num_bits < *old_bits)
+                    {
+                        witness_to_bit_sizes.insert(*other, num_bits);
+                        found_new_range = true;
+                        found_any = true;
+                    }
+                }
+            }
+        }
+        found_any
+    }
+
+    /// Marks witnesses which are constrained to be the complement (`1 - x`) of a boolean witness
+    /// as being boolean themselves.
+    ///
+    /// A `NOT` is encoded as the opcode `1 - x - y = 0` (or a multiple of it), so whenever one of
+    /// `x` or `y` is known to fit inside of a single bit then so must the other.
+    /// We repeat this until no new booleans are found so that chains of `NOT`s are resolved.
+    ///
+    /// Returns whether any new boolean was found.
+    fn collect_boolean_complements(
+        circuit: &Circuit,
+        witness_to_bit_sizes: &mut BTreeMap<Witness, u32>,
+    ) -> bool {
+        let complements: Vec<(Witness, Witness)> = circuit
+            .opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::AssertZero(expr) => boolean_complement(expr),
+                _ => None,
+            })
+            .collect();
+
+        let is_boolean = |bit_sizes: &BTreeMap<Witness, u32>, witness: &Witness| {
+            bit_sizes.get(witness).is_some_and(|num_bits| *num_bits <= 1)
+        };
+
+        let mut found_any = false;
+        let mut found_new_boolean = true;
+        while found_new_boolean {
+            found_new_boolean = false;
+            for (lhs, rhs) in &complements {
+                for (known, other) in [(lhs, rhs), (rhs, lhs)] {
+                    if is_boolean(witness_to_bit_sizes, known)
+                        && !is_boolean(witness_to_bit_sizes, other)
+                    {
+                        // Only witnesses which are not yet known to be boolean are updated, so the range
+                        // opcodes which the inference relies upon are never removed.
+                        witness_to_bit_sizes.insert(*other, 1);
+                        found_new_boolean = true;
+                        found_any = true;
+                    }
+                }
+            }
+        }
+        found_any
+    }
+
+    /// Infers ranges for witnesses which are constrained to be equal to a sum of products of range constrained
+    /// witnesses.
+    ///
+    /// If `c = k * a * b + l * x` for small positive coefficients `k` and `l`, and `a`, `b` and `x` all have known
+    /// ranges, then `c` is at most `k * max(a) * max(b) + l * max(x)`. As long as this bound is below the field
+    /// modulus then the sum cannot wrap around, so `c` must fit inside of the number of bits needed to hold it.
+    /// We repeat this until no new ranges are found so that chains of such opcodes are resolved.
+    ///
+    /// Returns whether any new range was found.
+    fn collect_bounded_sums(
+        circuit: &Circuit,
+        witness_to_bit_sizes: &mut BTreeMap<Witness, u32>,
+    ) -> bool {
+        let candidates: Vec<(&Expression, Witness)> = circuit
+            .opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::AssertZero(expr) => Some(expr),
+                _ => None,
+            })
+            .flat_map(|expr| sum_outputs(expr).into_iter().map(move |output| (expr, output)))
+            .collect();
+
+        let mut found_any = false;
+        let mut found_new_range = true;
+        while found_new_range {
+            found_new_range = false;
+            for (expr, output) in &candidates {
+                let Some(num_bits) = bounded_sum_bit_size(expr, *output, witness_to_bit_sizes)
+                else {
+                    continue;
+                };
+                // Any inferred range is strictly larger than the ranges of the witnesses it was inferred from,
+                // so the range opcodes which the inference relies upon are never removed.
+                if witness_to_bit_sizes.get(output).map_or(true, |old_bits| num_bits < *old_bits) {
+                    witness_to_bit_sizes.insert(*output, num_bits);
+                    found_new_range = true;
+                    found_any = true;
+                }
+            }
+        }
+        found_any
+    }
+
     /// Returns a `Circuit` where each Witness is only range constrained
     /// once to the lowest number `bit size` possible.
     pub(crate) fn replace_redundant_ranges(self, order_list: Vec<usize>) -> (Circuit, Vec<usize>) {
@@ -140,6 +278,106 @@ impl RangeOptimizer {
     }
 }
 
+/// Records that `witness` fits in `num_bits` bits, keeping the lowest bit size seen for each witness.
+fn insert_bit_size(
+    witness_to_bit_sizes: &mut BTreeMap<Witness, u32>,
+    witness: Witness,
+    num_bits: u32,
+) {
+    // Check if the witness has already been recorded and if the witness
+    // size is more than the current one, we replace it
+    witness_to_bit_sizes
+        .entry(witness)
+        .and_modify(|old_range_bits| {
+            *old_range_bits = std::cmp::min(*old_range_bits, num_bits);
+        })
+        .or_insert(num_bits);
+}
+
+/// Returns the witnesses which `expr` can be rearranged to define as a sum of its other terms,
+/// i.e. those which appear exactly once in `expr` as a linear term.
+fn sum_outputs(expr: &Expression) -> Vec<Witness> {
+    let occurrences = |witness: &Witness| {
+        let mul_occurrences =
+            expr.mul_terms.iter().filter(|(_, lhs, rhs)| lhs == witness || rhs == witness).count();
+        let linear_occurrences =
+            expr.linear_combinations.iter().filter(|(_, other)| other == witness).count();
+        mul_occurrences + linear_occurrences
+    };
+    expr.linear_combinations
+        .iter()
+        .filter(|(k, witness)| !k.is_zero() && occurrences(witness) == 1)
+        .map(|(_, witness)| *witness)
+        .collect()
+}
+
+/// Returns the number of bits needed to hold `output` if `expr` constrains it to be equal to a sum of terms
+/// which are each known to be small and positive, such that the sum cannot wrap around the field modulus.
+fn bounded_sum_bit_size(
+    expr: &Expression,
+    output: Witness,
+    witness_to_bit_sizes: &BTreeMap<Witness, u32>,
+) -> Option<u32> {
+    let (output_coefficient, _) =
+        expr.linear_combinations.iter().find(|(_, witness)| *witness == output)?;
+    // `output = scale * (expr - output_coefficient * output)`
+    let scale = -output_coefficient.inverse();
+    let known_bits = |witness: &Witness| witness_to_bit_sizes.get(witness).copied();
+
+    // A term `k * a * b` is less than `2^bits(k) * 2^bits(a) * 2^bits(b)`.
+    // Negative coefficients are represented by large field elements, so they make the sum unbounded.
+    let mut term_bits = Vec::new();
+    for (k, lhs, rhs) in &expr.mul_terms {
+        let coefficient = *k * scale;
+        if !coefficient.is_zero() {
+            term_bits.push(coefficient.num_bits() + known_bits(lhs)? + known_bits(rhs)?);
+        }
+    }
+    for (k, witness) in &expr.linear_combinations {
+        let coefficient = *k * scale;
+        if *witness != output && !coefficient.is_zero() {
+            term_bits.push(coefficient.num_bits() + known_bits(witness)?);
+        }
+    }
+    let constant = expr.q_c * scale;
+    if !constant.is_zero() {
+        term_bits.push(constant.num_bits());
+    }
+
+    // Adding up `n` terms needs at most `ceil(log2(n))` more bits than the largest term.
+    let carry_bits = usize::BITS - term_bits.len().saturating_sub(1).leading_zeros();
+    let num_bits = term_bits.into_iter().max().unwrap_or(0) + carry_bits;
+    (num_bits < FieldElement::max_num_bits()).then_some(num_bits)
+}
+
+/// Returns the pair of witnesses `(x, y)` if `expr` is of the form `k * (x - y)`,
+/// i.e. if it constrains `x` to be equal to `y`.
+fn equality(expr: &Expression) -> Option<(Witness, Witness)> {
+    if !expr.mul_terms.is_empty() || expr.linear_combinations.len() != 2 || !expr.q_c.is_zero() {
+        return None;
+    }
+    let (k_x, x) = expr.linear_combinations[0];
+    let (k_y, y) = expr.linear_combinations[1];
+    if x == y || k_x.is_zero() || k_x != -k_y {
+        return None;
+    }
+    Some((x, y))
+}
+
+/// Returns the pair of witnesses `(x, y)` if `expr` is of the form `k * (1 - x - y)`,
+/// i.e. if it constrains `y` to be equal to `1 - x`.
+fn boolean_complement(expr: &Expression) -> Option<(Witness, Witness)> {
+    if !expr.mul_terms.is_empty() || expr.linear_combinations.len() != 2 {
+        return None;
+    }
+    let (k_x, x) = expr.linear_combinations[0];
+    let (k_y, y) = expr.linear_combinations[1];
+    if x == y || k_x != k_y || k_x.is_zero() || expr.q_c != -k_x {
+        return None;
+    }
+    Some((x, y))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
@@ -151,6 +389,7 @@ mod tests {
             Circuit, ExpressionWidth, Opcode, PublicInputs,
         },
         native_types::{Expression, Witness},
+        FieldElement,
     };
 
     fn test_circuit(ranges: Vec<(Witness, u32)>) -> Circuit {
@@ -261,4 +500,220 @@ mod tests {
         assert_eq!(optimized_circuit.opcodes.len(), 1);
         assert_eq!(optimized_circuit.opcodes[0], Opcode::AssertZero(Witness(1).into()));
     }
+
+    #[test]
+    fn boolean_complement_implied_ranges() {
+        // `Witness(2) = 1 - Witness(1)` and `Witness(3) = 1 - Witness(2)` where `Witness(1)` is boolean.
+        // The optimizer should infer that both `Witness(2)` and `Witness(3)` are boolean.
+        let mut circuit =
+            test_circuit(vec![(Witness(1), 1), (Witness(2), 32), (Witness(3), 8), (Witness(4), 8)]);
+
+        let one = FieldElement::one();
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(one, Witness(1)), (one, Witness(2))],
+            q_c: -one,
+        }));
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(-one, Witness(3)), (-one, Witness(2))],
+            q_c: one,
+        }));
+        // `Witness(4) = 2 - Witness(1)` is not a complement so should be left alone.
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(one, Witness(1)), (one, Witness(4))],
+            q_c: -(one + one),
+        }));
+
+        let optimizer = RangeOptimizer::new(circuit);
+        assert_eq!(optimizer.lists.get(&Witness(1)), Some(&1));
+        assert_eq!(optimizer.lists.get(&Witness(2)), Some(&1));
+        assert_eq!(optimizer.lists.get(&Witness(3)), Some(&1));
+        assert_eq!(optimizer.lists.get(&Witness(4)), Some(&8));
+    }
+
+    #[test]
+    fn constants_are_not_used_to_infer_booleans() {
+        // `Witness(1) = 3`, which is recorded as making any range opcode of more than one bit redundant.
+        // `Witness(2) = 1 - Witness(1)` is then not boolean so its range opcode must be kept.
+        let mut circuit = test_circuit(vec![(Witness(2), 8)]);
+
+        let one = FieldElement::one();
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(one, Witness(1))],
+            q_c: -FieldElement::from(3_u128),
+        }));
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(one, Witness(1)), (one, Witness(2))],
+            q_c: -one,
+        }));
+
+        let optimizer = RangeOptimizer::new(circuit);
+        assert_eq!(optimizer.lists.get(&Witness(1)), Some(&1));
+        assert_eq!(optimizer.lists.get(&Witness(2)), Some(&8));
+    }
+
+    #[test]
+    fn equality_implied_ranges() {
+        // `Witness(2) = Witness(1)` and `Witness(3) = Witness(2)` where `Witness(1)` is boolean.
+        // The optimizer should infer that both `Witness(2)` and `Witness(3)` are boolean.
+        let mut circuit =
+            test_circuit(vec![(Witness(1), 1), (Witness(2), 32), (Witness(3), 8), (Witness(4), 8)]);
+
+        let one = FieldElement::one();
+        let two = one + one;
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(one, Witness(1)), (-one, Witness(2))],
+            q_c: FieldElement::zero(),
+        }));
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(-two, Witness(3)), (two, Witness(2))],
+            q_c: FieldElement::zero(),
+        }));
+        // `Witness(4) = Witness(1) - 1` is not an equality so should be left alone.
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(one, Witness(1)), (-one, Witness(4))],
+            q_c: -one,
+        }));
+        let acir_opcode_positions = circuit.opcodes.iter().enumerate().map(|(i, _)| i).collect();
+
+        let optimizer = RangeOptimizer::new(circuit);
+        assert_eq!(optimizer.lists.get(&Witness(1)), Some(&1));
+        assert_eq!(optimizer.lists.get(&Witness(2)), Some(&1));
+        assert_eq!(optimizer.lists.get(&Witness(3)), Some(&1));
+        assert_eq!(optimizer.lists.get(&Witness(4)), Some(&8));
+
+        let (optimized_circuit, _) = optimizer.replace_redundant_ranges(acir_opcode_positions);
+        let remaining_ranges: Vec<_> = optimized_circuit
+            .opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) => Some(input.witness),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(remaining_ranges, vec![Witness(1), Witness(4)]);
+    }
+
+    #[test]
+    fn equalities_combine_with_other_inferred_ranges() {
+        // `Witness(2) = Witness(1)` where `Witness(1)` is boolean, and `Witness(3) = 1 - Witness(2)`.
+        // Only once `Witness(2)` is known to be boolean can `Witness(3)` be inferred to be boolean.
+        // `Witness(5) = Witness(4)` where `Witness(4) = Witness(3) * Witness(6)` is inferred to fit in 10 bits.
+        let mut circuit =
+            test_circuit(vec![(Witness(1), 1), (Witness(3), 8), (Witness(5), 16), (Witness(6), 8)]);
+
+        let one = FieldElement::one();
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(one, Witness(1)), (-one, Witness(2))],
+            q_c: FieldElement::zero(),
+        }));
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(one, Witness(2)), (one, Witness(3))],
+            q_c: -one,
+        }));
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: vec![(one, Witness(3), Witness(6))],
+            linear_combinations: vec![(-one, Witness(4))],
+            q_c: FieldElement::zero(),
+        }));
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(one, Witness(4)), (-one, Witness(5))],
+            q_c: FieldElement::zero(),
+        }));
+
+        let optimizer = RangeOptimizer::new(circuit);
+        assert_eq!(optimizer.lists.get(&Witness(2)), Some(&1));
+        assert_eq!(optimizer.lists.get(&Witness(3)), Some(&1));
+        assert_eq!(optimizer.lists.get(&Witness(4)), Some(&10));
+        assert_eq!(optimizer.lists.get(&Witness(5)), Some(&10));
+    }
+
+    #[test]
+    fn bounded_product_implied_ranges() {
+        // `Witness(1)` and `Witness(2)` are both 8 bits so `Witness(3) = Witness(1) * Witness(2)` fits in 17 bits,
+        // making its 32 bit range opcode redundant.
+        // `Witness(4) = 2 * Witness(3) * Witness(1) + Witness(2)` then fits in 28 bits.
+        let mut circuit = test_circuit(vec![
+            (Witness(1), 8),
+            (Witness(2), 8),
+            (Witness(3), 32),
+            (Witness(4), 32),
+        ]);
+
+        let one = FieldElement::one();
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: vec![(one, Witness(1), Witness(2))],
+            linear_combinations: vec![(-one, Witness(3))],
+            q_c: FieldElement::zero(),
+        }));
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: vec![(one + one, Witness(3), Witness(1))],
+            linear_combinations: vec![(one, Witness(2)), (-one, Witness(4))],
+            q_c: FieldElement::zero(),
+        }));
+        let acir_opcode_positions = circuit.opcodes.iter().enumerate().map(|(i, _)| i).collect();
+
+        let optimizer = RangeOptimizer::new(circuit);
+        assert_eq!(optimizer.lists.get(&Witness(3)), Some(&17));
+        assert_eq!(optimizer.lists.get(&Witness(4)), Some(&28));
+
+        let (optimized_circuit, _) = optimizer.replace_redundant_ranges(acir_opcode_positions);
+        let remaining_ranges: Vec<_> = optimized_circuit
+            .opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) => Some(input.witness),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(remaining_ranges, vec![Witness(1), Witness(2)]);
+    }
+
+    #[test]
+    fn unbounded_products_do_not_imply_ranges() {
+        let mut circuit = test_circuit(vec![
+            (Witness(1), 8),
+            (Witness(2), 8),
+            (Witness(3), 32),
+            (Witness(4), 32),
+            (Witness(5), 200),
+            (Witness(6), 32),
+        ]);
+
+        let one = FieldElement::one();
+        // `Witness(3) = Witness(1) * Witness(2) - Witness(1)` may be negative and so wrap around the field.
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: vec![(one, Witness(1), Witness(2))],
+            linear_combinations: vec![(-one, Witness(1)), (-one, Witness(3))],
+            q_c: FieldElement::zero(),
+        }));
+        // `Witness(4) = Witness(1) * Witness(7)` where `Witness(7)` has no known range.
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: vec![(one, Witness(1), Witness(7))],
+            linear_combinations: vec![(-one, Witness(4))],
+            q_c: FieldElement::zero(),
+        }));
+        // `Witness(6) = Witness(5) * Witness(5)` may exceed the field modulus.
+        circuit.opcodes.push(Opcode::AssertZero(Expression {
+            mul_terms: vec![(one, Witness(5), Witness(5))],
+            linear_combinations: vec![(-one, Witness(6))],
+            q_c: FieldElement::zero(),
+        }));
+
+        let optimizer = RangeOptimizer::new(circuit);
+        assert_eq!(optimizer.lists.get(&Witness(3)), Some(&32));
+        assert_eq!(optimizer.lists.get(&Witness(4)), Some(&32));
+        assert_eq!(optimizer.lists.get(&Witness(6)), Some(&32));
+        assert_eq!(optimizer.lists.get(&Witness(7)), None);
+    }
 }
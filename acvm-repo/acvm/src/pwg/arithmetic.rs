@@ -7,6 +7,9 @@ use super::{insert_value, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionErro
 
 /// An Expression solver will take a Circuit's assert-zero opcodes with witness assignments
 /// and create the other witness variables
+///
+/// Any opcode with a single unknown witness is solved, this includes opcodes which constrain a witness
+/// to equal a constant (e.g. `x - c = 0`). The witness is then known to all subsequent opcodes.
 pub(super) struct ExpressionSolver;
 
 #[allow(clippy::enum_variant_names)]
@@ -279,3 +282,38 @@ fn expression_solver_smoke_test() {
 
     assert_eq!(values.get(&a).unwrap(), &FieldElement::from(4_i128));
 }
+
+#[test]
+fn expression_solver_resolves_constant_witnesses() {
+    let x = Witness(0);
+    let y = Witness(1);
+    let z = Witness(2);
+
+    // x = 0
+    let x_is_zero = Expression {
+        mul_terms: vec![],
+        linear_combinations: vec![(FieldElement::one(), x)],
+        q_c: FieldElement::zero(),
+    };
+    // y = 5
+    let y_is_constant = Expression {
+        mul_terms: vec![],
+        linear_combinations: vec![(FieldElement::one(), y)],
+        q_c: -FieldElement::from(5_i128),
+    };
+    // z = x * y + y
+    let z_from_constants = Expression {
+        mul_terms: vec![(FieldElement::one(), x, y)],
+        linear_combinations: vec![(FieldElement::one(), y), (-FieldElement::one(), z)],
+        q_c: FieldElement::zero(),
+    };
+
+    let mut values = WitnessMap::new();
+    assert_eq!(ExpressionSolver::solve(&mut values, &x_is_zero), Ok(()));
+    assert_eq!(ExpressionSolver::solve(&mut values, &y_is_constant), Ok(()));
+    assert_eq!(ExpressionSolver::solve(&mut values, &z_from_constants), Ok(()));
+
+    assert_eq!(values.get(&x).unwrap(), &FieldElement::zero());
+    assert_eq!(values.get(&y).unwrap(), &FieldElement::from(5_i128));
+    assert_eq!(values.get(&z).unwrap(), &FieldElement::from(5_i128));
+}
@@ -1,10 +1,12 @@
 // Re-usable methods that backends can use to implement their PWG
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use acir::{
     brillig::ForeignCallResult,
-    circuit::{opcodes::BlockId, Opcode, OpcodeLocation},
+    circuit::{
+        brillig::BrilligInputs, directives::Directive, opcodes::BlockId, Opcode, OpcodeLocation,
+    },
     native_types::{Expression, Witness, WitnessMap},
     BlackBoxFunc, FieldElement,
 };
@@ -142,10 +144,20 @@ pub struct ACVM<'a, B: BlackBoxFunctionSolver> {
     /// A list of opcodes which are to be executed by the ACVM.
     opcodes: &'a [Opcode],
     /// Index of the next opcode to be executed.
+    ///
+    /// This only ever increments, so opcodes are solved in a stable order across executions.
     instruction_pointer: usize,
 
     witness_map: WitnessMap,
 
+    /// The witnesses which were assigned values in the initial witness map.
+    ///
+    /// This is only populated if [`ACVM::track_unused_initial_witnesses`] has been called.
+    initial_witnesses: BTreeSet<Witness>,
+    /// The witnesses which have been referenced by the opcodes which the ACVM has attempted to solve,
+    /// if [`ACVM::track_unused_initial_witnesses`] has been called.
+    read_witnesses: Option<BTreeSet<Witness>>,
+
     brillig_solver: Option<BrilligSolver<'a, B>>,
 }
 
@@ -160,10 +172,23 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
             opcodes,
             instruction_pointer: 0,
             witness_map: initial_witness,
+            initial_witnesses: BTreeSet::new(),
+            read_witnesses: None,
             brillig_solver: None,
         }
     }
 
+    /// Records the witnesses referenced by each opcode which the ACVM attempts to solve, so that the initial
+    /// witnesses which are never used can be found with [`ACVM::unused_initial_witnesses`].
+    ///
+    /// This must be called before solving any opcodes.
+    pub fn track_unused_initial_witnesses(mut self) -> Self {
+        self.initial_witnesses =
+            self.witness_map.clone().into_iter().map(|(witness, _)| witness).collect();
+        self.read_witnesses = Some(BTreeSet::new());
+        self
+    }
+
     /// Returns a reference to the current state of the ACVM's [`WitnessMap`].
     ///
     /// Once execution has completed, the witness map can be extracted using [`ACVM::finalize`]
@@ -189,6 +214,37 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
         self.instruction_pointer
     }
 
+    /// Returns the witnesses from the initial witness map which have not been referenced by any
+    /// of the opcodes which the ACVM has attempted to solve so far.
+    ///
+    /// Once execution has completed, these are the inputs which had no effect on solving the circuit.
+    ///
+    /// Returns `None` if the ACVM was not created with [`ACVM::track_unused_initial_witnesses`].
+    pub fn unused_initial_witnesses(&self) -> Option<BTreeSet<Witness>> {
+        let read_witnesses = self.read_witnesses.as_ref()?;
+        Some(self.initial_witnesses.difference(read_witnesses).copied().collect())
+    }
+
+    /// Returns the opcodes which have not yet been solved, starting with the current opcode.
+    ///
+    /// If solving has failed, the first of these is the opcode which could not be solved.
+    pub fn unsolved_opcodes(&self) -> &[Opcode] {
+        &self.opcodes[self.instruction_pointer..]
+    }
+
+    /// Returns the witnesses read by the current opcode which have not been assigned a value.
+    ///
+    /// If solving has failed because the current opcode is not solvable, these are the witnesses which are missing.
+    pub fn unknown_witnesses(&self) -> BTreeSet<Witness> {
+        let Some(opcode) = self.opcodes.get(self.instruction_pointer) else {
+            return BTreeSet::new();
+        };
+        let mut witnesses = BTreeSet::new();
+        collect_opcode_witnesses(opcode, &mut witnesses);
+        witnesses.retain(|witness| !self.witness_map.contains_key(witness));
+        witnesses
+    }
+
     /// Finalize the ACVM execution, returning the resulting [`WitnessMap`].
     pub fn finalize(self) -> WitnessMap {
         if self.status != ACVMStatus::Solved {
@@ -246,6 +302,9 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
 
     /// Executes the ACVM's circuit until execution halts.
     ///
+    /// Opcodes are always solved one at a time in the order in which they appear in the circuit,
+    /// so solving the same circuit with the same initial witness always produces the same [`WitnessMap`].
+    ///
     /// Execution can halt due to three reasons:
     /// 1. All opcodes have been executed successfully.
     /// 2. The circuit has been found to be unsatisfiable.
@@ -259,6 +318,9 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
 
     pub fn solve_opcode(&mut self) -> ACVMStatus {
         let opcode = &self.opcodes[self.instruction_pointer];
+        if let Some(read_witnesses) = &mut self.read_witnesses {
+            collect_opcode_witnesses(opcode, read_witnesses);
+        }
 
         let resolution = match opcode {
             Opcode::AssertZero(expr) => ExpressionSolver::solve(&mut self.witness_map, expr),
@@ -401,6 +463,53 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
     }
 }
 
+/// Inserts all of the witnesses which are referenced by `expr` into `witnesses`.
+pub fn collect_expression_witnesses(expr: &Expression, witnesses: &mut BTreeSet<Witness>) {
+    for (_, lhs, rhs) in &expr.mul_terms {
+        witnesses.insert(*lhs);
+        witnesses.insert(*rhs);
+    }
+    witnesses.extend(expr.linear_combinations.iter().map(|(_, witness)| *witness));
+}
+
+/// Inserts all of the witnesses which are referenced by `opcode` into `witnesses`.
+pub fn collect_opcode_witnesses(opcode: &Opcode, witnesses: &mut BTreeSet<Witness>) {
+    match opcode {
+        Opcode::AssertZero(expr) => collect_expression_witnesses(expr, witnesses),
+        Opcode::BlackBoxFuncCall(bb_func) => {
+            witnesses.extend(bb_func.get_inputs_vec().iter().map(|input| input.witness));
+        }
+        Opcode::Directive(Directive::ToLeRadix { a, .. }) => {
+            collect_expression_witnesses(a, witnesses);
+        }
+        Opcode::Brillig(brillig) => {
+            for input in &brillig.inputs {
+                match input {
+                    BrilligInputs::Single(expr) => collect_expression_witnesses(expr, witnesses),
+                    BrilligInputs::Array(exprs) => {
+                        for expr in exprs {
+                            collect_expression_witnesses(expr, witnesses);
+                        }
+                    }
+                    BrilligInputs::MemoryArray(_) => (),
+                }
+            }
+            if let Some(predicate) = &brillig.predicate {
+                collect_expression_witnesses(predicate, witnesses);
+            }
+        }
+        Opcode::MemoryOp { op, predicate, .. } => {
+            collect_expression_witnesses(&op.operation, witnesses);
+            collect_expression_witnesses(&op.index, witnesses);
+            collect_expression_witnesses(&op.value, witnesses);
+            if let Some(predicate) = predicate {
+                collect_expression_witnesses(predicate, witnesses);
+            }
+        }
+        Opcode::MemoryInit { init, .. } => witnesses.extend(init.iter().copied()),
+    }
+}
+
 // Returns the concrete value for a particular witness
 // If the witness has no assignment, then
 // an error is returned
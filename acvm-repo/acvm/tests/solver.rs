@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use acir::{
     brillig::{BinaryFieldOp, MemoryAddress, Opcode as BrilligOpcode, Value, ValueOrArray},
@@ -525,6 +525,39 @@ fn unsatisfied_opcode_resolved() {
     );
 }
 
+#[test]
+fn unused_initial_witnesses() {
+    let a = Witness(0);
+    let b = Witness(1);
+    let c = Witness(2);
+    let unused = Witness(3);
+
+    // c = a * b
+    let opcode = Expression {
+        mul_terms: vec![(FieldElement::one(), a, b)],
+        linear_combinations: vec![(-FieldElement::one(), c)],
+        q_c: FieldElement::zero(),
+    };
+
+    let mut values = WitnessMap::new();
+    values.insert(a, FieldElement::from(2_i128));
+    values.insert(b, FieldElement::from(3_i128));
+    values.insert(unused, FieldElement::from(4_i128));
+
+    let opcodes = vec![Opcode::AssertZero(opcode)];
+    let mut acvm =
+        ACVM::new(&StubbedBlackBoxSolver, &opcodes, values).track_unused_initial_witnesses();
+    assert_eq!(
+        acvm.unused_initial_witnesses(),
+        Some(BTreeSet::from([a, b, unused])),
+        "no opcodes have been solved so no initial witnesses should have been used"
+    );
+
+    let solver_status = acvm.solve();
+    assert_eq!(solver_status, ACVMStatus::Solved, "should be fully solved");
+    assert_eq!(acvm.unused_initial_witnesses(), Some(BTreeSet::from([unused])));
+}
+
 #[test]
 fn unsatisfied_opcode_resolved_brillig() {
     let a = Witness(0);
@@ -648,3 +681,176 @@ fn memory_operations() {
 
     assert_eq!(witness_map[&Witness(8)], FieldElement::from(6u128));
 }
+
+#[test]
+fn solving_is_deterministic() {
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+        (Witness(3), FieldElement::from(3u128)),
+        (Witness(4), FieldElement::from(2u128)),
+    ]));
+
+    let block_id = BlockId(0);
+    let init = Opcode::MemoryInit { block_id, init: (1..4).map(Witness).collect() };
+    let read_op = Opcode::MemoryOp {
+        block_id,
+        op: MemOp::read_at_mem_index(Witness(4).into(), Witness(5)),
+        predicate: None,
+    };
+
+    // _6 = _5 * _2
+    let product = Opcode::AssertZero(Expression {
+        mul_terms: vec![(FieldElement::one(), Witness(5), Witness(2))],
+        linear_combinations: vec![(-FieldElement::one(), Witness(6))],
+        q_c: FieldElement::zero(),
+    });
+
+    // _7 = 1 / _6, calculated in Brillig
+    let inverse = Opcode::Brillig(Brillig {
+        inputs: vec![BrilligInputs::Single(Witness(6).into())],
+        outputs: vec![BrilligOutputs::Simple(Witness(7))],
+        bytecode: vec![
+            BrilligOpcode::CalldataCopy {
+                destination_address: MemoryAddress(0),
+                size: 1,
+                offset: 0,
+            },
+            BrilligOpcode::Const {
+                destination: MemoryAddress(1),
+                bit_size: 254,
+                value: Value::from(1_usize),
+            },
+            BrilligOpcode::BinaryFieldOp {
+                op: BinaryFieldOp::Div,
+                lhs: MemoryAddress(1),
+                rhs: MemoryAddress(0),
+                destination: MemoryAddress(0),
+            },
+            BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 },
+        ],
+        predicate: None,
+    });
+
+    let opcodes = vec![init, read_op, product, inverse];
+
+    let solve = || {
+        let mut acvm = ACVM::new(&StubbedBlackBoxSolver, &opcodes, initial_witness.clone());
+        assert_eq!(acvm.solve(), ACVMStatus::Solved);
+        acvm.finalize()
+    };
+
+    let first_witness = solve();
+    let second_witness = solve();
+    assert_eq!(first_witness, second_witness);
+    assert_eq!(first_witness[&Witness(6)], FieldElement::from(6u128));
+}
+
+#[test]
+fn brillig_inversion_of_known_witness() {
+    let x = Witness(1);
+    let x_inverse = Witness(2);
+
+    // _2 = 1 / _1, calculated in Brillig
+    let inverse = Opcode::Brillig(Brillig {
+        inputs: vec![BrilligInputs::Single(x.into())],
+        outputs: vec![BrilligOutputs::Simple(x_inverse)],
+        bytecode: vec![
+            BrilligOpcode::CalldataCopy {
+                destination_address: MemoryAddress(0),
+                size: 1,
+                offset: 0,
+            },
+            BrilligOpcode::Const {
+                destination: MemoryAddress(1),
+                bit_size: 254,
+                value: Value::from(1_usize),
+            },
+            BrilligOpcode::BinaryFieldOp {
+                op: BinaryFieldOp::Div,
+                lhs: MemoryAddress(1),
+                rhs: MemoryAddress(0),
+                destination: MemoryAddress(0),
+            },
+            BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 },
+        ],
+        predicate: None,
+    });
+    // _1 * _2 - 1 = 0
+    let constrain_inverse = Opcode::AssertZero(Expression {
+        mul_terms: vec![(FieldElement::one(), x, x_inverse)],
+        linear_combinations: vec![],
+        q_c: -FieldElement::one(),
+    });
+    let opcodes = vec![inverse, constrain_inverse];
+
+    // A known nonzero witness has its inverse filled in.
+    let x_value = FieldElement::from(5u128);
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &opcodes,
+        WitnessMap::from(BTreeMap::from([(x, x_value)])),
+    );
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    assert_eq!(acvm.finalize()[&x_inverse], x_value.inverse());
+
+    // Zero has no inverse so the constraint on the result cannot be satisfied.
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &opcodes,
+        WitnessMap::from(BTreeMap::from([(x, FieldElement::zero())])),
+    );
+    assert_eq!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(1)),
+        })
+    );
+
+    // Without a value for the witness being inverted, solving cannot start.
+    let mut acvm = ACVM::new(&StubbedBlackBoxSolver, &opcodes, WitnessMap::new());
+    assert!(matches!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::OpcodeNotSolvable(_))
+    ));
+}
+
+#[test]
+fn reports_unsolved_opcodes_and_unknown_witnesses() {
+    let a = Witness(0);
+    let b = Witness(1);
+    let c = Witness(2);
+    let d = Witness(3);
+
+    // c = a + 1
+    let first = Expression {
+        mul_terms: vec![],
+        linear_combinations: vec![(FieldElement::one(), a), (-FieldElement::one(), c)],
+        q_c: FieldElement::one(),
+    };
+    // d = b * c
+    let second = Expression {
+        mul_terms: vec![(FieldElement::one(), b, c)],
+        linear_combinations: vec![(-FieldElement::one(), d)],
+        q_c: FieldElement::zero(),
+    };
+    // b = d
+    let third = Expression {
+        mul_terms: vec![],
+        linear_combinations: vec![(FieldElement::one(), b), (-FieldElement::one(), d)],
+        q_c: FieldElement::zero(),
+    };
+
+    let opcodes =
+        vec![Opcode::AssertZero(first), Opcode::AssertZero(second), Opcode::AssertZero(third)];
+    let values = WitnessMap::from(BTreeMap::from([(a, FieldElement::from(2_i128))]));
+    let mut acvm = ACVM::new(&StubbedBlackBoxSolver, &opcodes, values);
+
+    assert!(matches!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::OpcodeNotSolvable(_))
+    ));
+    assert_eq!(acvm.instruction_pointer(), 1);
+    assert_eq!(acvm.unsolved_opcodes(), &opcodes[1..]);
+    assert_eq!(acvm.unknown_witnesses(), BTreeSet::from([b, d]));
+}
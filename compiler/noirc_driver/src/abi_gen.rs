@@ -2,7 +2,8 @@ use std::collections::BTreeMap;
 
 use acvm::acir::native_types::Witness;
 use iter_extended::{btree_map, vecmap};
-use noirc_abi::{Abi, AbiParameter, AbiReturnType, AbiType};
+use noirc_abi::{visibility::check_visibility, Abi, AbiParameter, AbiReturnType, AbiType};
+use noirc_errors::{CustomDiagnostic, FileDiagnostic};
 use noirc_frontend::{
     hir::Context,
     hir_def::{function::Param, stmt::HirPattern},
@@ -27,6 +28,29 @@ pub(super) fn gen_abi(
     Abi { parameters, return_type, param_witnesses, return_witnesses }
 }
 
+/// Warns about likely mistakes in the visibility of the parameters and return value of the entrypoint `func_id`,
+/// pointing at the offending parameter where there is one.
+pub(super) fn visibility_warnings(
+    context: &Context,
+    func_id: &FuncId,
+    abi: &Abi,
+) -> Vec<FileDiagnostic> {
+    let func_meta = context.def_interner.function_meta(func_id);
+    vecmap(check_visibility(abi), |warning| {
+        // The ABI's parameters are listed in the same order as the function's parameters.
+        let name = warning.parameter_name();
+        let parameter_span = abi
+            .parameters
+            .iter()
+            .position(|parameter| parameter.name == name)
+            .and_then(|index| func_meta.parameters.0.get(index))
+            .map(|(pattern, _, _)| pattern.span());
+        let span = parameter_span.unwrap_or(func_meta.name.location.span);
+        CustomDiagnostic::simple_warning(warning.to_string(), String::new(), span)
+            .in_file(func_meta.location.file)
+    })
+}
+
 pub(super) fn compute_function_abi(
     context: &Context,
     func_id: &FuncId,
@@ -8,6 +8,7 @@ use noirc_errors::debug_info::DebugInfo;
 use noirc_evaluator::errors::SsaReport;
 
 use super::debug::DebugFile;
+use super::timings::CompilationTimings;
 
 /// Describes the types of smart contract functions that are allowed.
 /// Unlike the similar enum in noirc_frontend, 'open' and 'unconstrained'
@@ -43,6 +44,8 @@ pub struct CompiledContract {
 
     pub file_map: BTreeMap<FileId, DebugFile>,
     pub warnings: Vec<SsaReport>,
+    #[serde(skip)]
+    pub timings: CompilationTimings,
 }
 
 /// Each function in the contract will be compiled
@@ -4,7 +4,7 @@
 #![warn(clippy::semicolon_if_nothing_returned)]
 
 use acvm::acir::circuit::ExpressionWidth;
-use clap::Args;
+use clap::{builder::PossibleValuesParser, Args};
 use fm::{FileId, FileManager};
 use iter_extended::vecmap;
 use noirc_abi::{AbiParameter, AbiType, ContractEvent};
@@ -18,7 +18,9 @@ use noirc_frontend::hir::Context;
 use noirc_frontend::macros_api::MacroProcessor;
 use noirc_frontend::monomorphization::{monomorphize, monomorphize_debug};
 use noirc_frontend::node_interner::FuncId;
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::time::Instant;
 use tracing::info;
 
 mod abi_gen;
@@ -26,15 +28,24 @@ mod contract;
 mod debug;
 mod program;
 mod stdlib;
+mod timings;
 
 use debug::filter_relevant_files;
 
 pub use contract::{CompiledContract, ContractFunction, ContractFunctionType};
 pub use debug::DebugFile;
 pub use program::CompiledProgram;
+pub use timings::CompilationTimings;
 
 const STD_CRATE_NAME: &str = "std";
 const DEBUG_CRATE_NAME: &str = "__debug";
+/// The directory of the file manager which holds the minimal stdlib.
+const MINIMAL_STD_DIR: &str = "std-min";
+
+/// Links the minimal stdlib, which only contains the builtin and operator definitions, in place of the full stdlib.
+pub const MINIMAL_STD_FEATURE: &str = "minimal-std";
+/// The features which may be enabled when compiling a package.
+pub const KNOWN_FEATURES: &[&str] = &[MINIMAL_STD_FEATURE];
 
 pub const GIT_COMMIT: &str = env!("GIT_COMMIT");
 pub const GIT_DIRTY: &str = env!("GIT_DIRTY");
@@ -93,6 +104,10 @@ pub struct CompileOptions {
     /// Force Brillig output (for step debugging)
     #[arg(long, hide = true)]
     pub force_brillig: bool,
+
+    /// Comma-separated list of features to enable, e.g. `minimal-std`
+    #[arg(long, value_delimiter = ',', value_parser = PossibleValuesParser::new(KNOWN_FEATURES))]
+    pub features: Vec<String>,
 }
 
 fn parse_expression_width(input: &str) -> Result<ExpressionWidth, std::io::Error> {
@@ -125,6 +140,7 @@ pub fn file_manager_with_stdlib(root: &Path) -> FileManager {
     let mut file_manager = FileManager::new(root);
 
     add_stdlib_source_to_file_manager(&mut file_manager);
+    add_minimal_stdlib_source_to_file_manager(&mut file_manager);
     add_debug_source_to_file_manager(&mut file_manager);
 
     file_manager
@@ -142,6 +158,14 @@ fn add_stdlib_source_to_file_manager(file_manager: &mut FileManager) {
     }
 }
 
+/// Adds the source code for the minimal stdlib into the file manager, so that it can be linked in place
+/// of the full stdlib if the [MINIMAL_STD_FEATURE] is enabled.
+fn add_minimal_stdlib_source_to_file_manager(file_manager: &mut FileManager) {
+    for (path, source) in stdlib::minimal_stdlib_paths_with_source() {
+        file_manager.add_file_with_source_canonical_path(Path::new(&path), source);
+    }
+}
+
 /// Adds the source code of the debug crate needed to support instrumentation to
 /// track variables values
 fn add_debug_source_to_file_manager(file_manager: &mut FileManager) {
@@ -156,7 +180,21 @@ fn add_debug_source_to_file_manager(file_manager: &mut FileManager) {
 /// Note: This methods adds the stdlib as a dependency to the crate.
 /// This assumes that the stdlib has already been added to the file manager.
 pub fn prepare_crate(context: &mut Context, file_name: &Path) -> CrateId {
-    let path_to_std_lib_file = Path::new(STD_CRATE_NAME).join("lib.nr");
+    prepare_crate_with_features(context, file_name, &[])
+}
+
+/// Like [prepare_crate], but links the stdlib selected by the enabled `features`.
+pub fn prepare_crate_with_features(
+    context: &mut Context,
+    file_name: &Path,
+    features: &[String],
+) -> CrateId {
+    let std_dir = if features.iter().any(|feature| feature == MINIMAL_STD_FEATURE) {
+        MINIMAL_STD_DIR
+    } else {
+        STD_CRATE_NAME
+    };
+    let path_to_std_lib_file = Path::new(std_dir).join("lib.nr");
     let std_file_id = context
         .file_manager
         .name_to_id(path_to_std_lib_file)
@@ -260,8 +298,10 @@ pub fn compile_main(
     options: &CompileOptions,
     cached_program: Option<CompiledProgram>,
 ) -> CompilationResult<CompiledProgram> {
+    let frontend_start = Instant::now();
     let (_, mut warnings) =
         check_crate(context, crate_id, options.deny_warnings, options.disable_macros)?;
+    let frontend_time = frontend_start.elapsed();
 
     let main = context.get_main_function(&crate_id).ok_or_else(|| {
         // TODO(#2155): This error might be a better to exist in Nargo
@@ -272,11 +312,17 @@ pub fn compile_main(
         vec![err]
     })?;
 
-    let compiled_program =
+    let mut compiled_program =
         compile_no_check(context, options, main, cached_program, options.force_compile)
             .map_err(FileDiagnostic::from)?;
-
-    let compilation_warnings = vecmap(compiled_program.warnings.clone(), FileDiagnostic::from);
+    compiled_program.timings.frontend = frontend_time;
+
+    let mut compilation_warnings = vecmap(compiled_program.warnings.clone(), FileDiagnostic::from);
+    compilation_warnings.extend(abi_gen::visibility_warnings(
+        context,
+        &main,
+        &compiled_program.abi,
+    ));
     if options.deny_warnings && !compilation_warnings.is_empty() {
         return Err(compilation_warnings);
     }
@@ -290,14 +336,65 @@ pub fn compile_main(
     Ok((compiled_program, warnings))
 }
 
+/// Run the frontend to check the crate for errors then compile each entrypoint function into its own program
+///
+/// Entrypoints are the `main` function along with any public functions in the crate's root module.
+/// On success this returns the compiled programs keyed by function name alongside any warnings that were found.
+pub fn compile_entrypoints(
+    context: &mut Context,
+    crate_id: CrateId,
+    options: &CompileOptions,
+) -> CompilationResult<BTreeMap<String, CompiledProgram>> {
+    let frontend_start = Instant::now();
+    let (_, warnings) =
+        check_crate(context, crate_id, options.deny_warnings, options.disable_macros)?;
+    let frontend_time = frontend_start.elapsed();
+
+    let entrypoints = context.get_all_entrypoint_functions_in_crate(&crate_id);
+    if entrypoints.is_empty() {
+        let err = CustomDiagnostic::from_message(
+            "cannot compile crate into programs as it does not contain any public functions",
+        )
+        .in_file(FileId::default());
+        return Err(vec![err]);
+    }
+
+    let mut compiled_programs = BTreeMap::new();
+    let mut errors = warnings;
+    for (function_name, function_id) in entrypoints {
+        match compile_no_check(context, options, function_id, None, options.force_compile) {
+            Ok(mut compiled_program) => {
+                compiled_program.timings.frontend = frontend_time;
+                errors.extend(vecmap(compiled_program.warnings.clone(), FileDiagnostic::from));
+
+                if options.print_acir {
+                    println!("Compiled ACIR for {function_name} (unoptimized):");
+                    println!("{}", compiled_program.circuit);
+                }
+                compiled_programs.insert(function_name, compiled_program);
+            }
+            Err(error) => errors.push(FileDiagnostic::from(error)),
+        }
+    }
+
+    if has_errors(&errors, options.deny_warnings) {
+        Err(errors)
+    } else {
+        // errors here is either empty or contains only warnings
+        Ok((compiled_programs, errors))
+    }
+}
+
 /// Run the frontend to check the crate for errors then compile all contracts if there were none
 pub fn compile_contract(
     context: &mut Context,
     crate_id: CrateId,
     options: &CompileOptions,
 ) -> CompilationResult<CompiledContract> {
+    let frontend_start = Instant::now();
     let (_, warnings) =
         check_crate(context, crate_id, options.deny_warnings, options.disable_macros)?;
+    let frontend_time = frontend_start.elapsed();
 
     // TODO: We probably want to error if contracts is empty
     let contracts = context.get_all_contracts(&crate_id);
@@ -328,7 +425,8 @@ pub fn compile_contract(
         Err(errors)
     } else {
         assert_eq!(compiled_contracts.len(), 1);
-        let compiled_contract = compiled_contracts.remove(0);
+        let mut compiled_contract = compiled_contracts.remove(0);
+        compiled_contract.timings.frontend = frontend_time;
 
         if options.print_acir {
             for contract_function in &compiled_contract.functions {
@@ -362,6 +460,7 @@ fn compile_contract_inner(
     let mut functions = Vec::new();
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
+    let mut timings = CompilationTimings::default();
     for contract_function in &contract.functions {
         let function_id = contract_function.function_id;
         let is_entry_point = contract_function.is_entry_point;
@@ -384,6 +483,7 @@ fn compile_contract_inner(
             }
         };
         warnings.extend(function.warnings);
+        timings += function.timings;
         let modifiers = context.def_interner.function_modifiers(&function_id);
         let func_type = modifiers
             .contract_function_type
@@ -420,6 +520,7 @@ fn compile_contract_inner(
             file_map,
             noir_version: NOIR_ARTIFACT_VERSION_STRING.to_string(),
             warnings,
+            timings,
         })
     } else {
         Err(errors)
@@ -451,15 +552,19 @@ pub fn compile_no_check(
 
     // If user has specified that they want to see intermediate steps printed then we should
     // force compilation even if the program hasn't changed.
-    let force_compile =
-        force_compile || options.print_acir || options.show_brillig || options.show_ssa;
+    // Forcing Brillig also changes the output, so the cached program can't be reused.
+    let force_compile = force_compile
+        || options.print_acir
+        || options.show_brillig
+        || options.show_ssa
+        || options.force_brillig;
 
     if !force_compile && hashes_match {
         info!("Program matches existing artifact, returning early");
         return Ok(cached_program.expect("cache must exist for hashes to match"));
     }
     let visibility = program.return_visibility;
-    let (circuit, debug, input_witnesses, return_witnesses, warnings) =
+    let (circuit, debug, input_witnesses, return_witnesses, warnings, timings) =
         create_circuit(program, options.show_ssa, options.show_brillig, options.force_brillig)?;
 
     let abi =
@@ -474,5 +579,6 @@ pub fn compile_no_check(
         file_map,
         noir_version: NOIR_ARTIFACT_VERSION_STRING.to_string(),
         warnings,
+        timings: timings.into(),
     })
 }
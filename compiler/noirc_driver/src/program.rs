@@ -8,6 +8,7 @@ use noirc_evaluator::errors::SsaReport;
 use serde::{Deserialize, Serialize};
 
 use super::debug::DebugFile;
+use super::timings::CompilationTimings;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CompiledProgram {
@@ -27,4 +28,6 @@ pub struct CompiledProgram {
     pub debug: DebugInfo,
     pub file_map: BTreeMap<FileId, DebugFile>,
     pub warnings: Vec<SsaReport>,
+    #[serde(skip)]
+    pub timings: CompilationTimings,
 }
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use rust_embed::RustEmbed;
 
 #[derive(RustEmbed)]
@@ -6,6 +8,16 @@ use rust_embed::RustEmbed;
 #[cfg_attr(target_os = "windows", prefix = r"std\")] // Note reversed slash direction
 struct StdLibAssets;
 
+#[derive(RustEmbed)]
+#[folder = "../../noir_stdlib/minimal"]
+#[cfg_attr(not(target_os = "windows"), prefix = "std-min/")]
+#[cfg_attr(target_os = "windows", prefix = r"std-min\")] // Note reversed slash direction
+struct MinimalStdLibAssets;
+
+/// Modules of the full stdlib which the minimal stdlib also contains, as the compiler relies on them.
+const MINIMAL_STDLIB_SHARED_MODULES: [&str; 5] =
+    ["array.nr", "slice.nr", "cmp.nr", "ops.nr", "default.nr"];
+
 // Returns a vector of tuples containing the path to a stdlib file in the std lib crate
 // along with the source code of that file.
 //
@@ -22,3 +34,65 @@ pub(crate) fn stdlib_paths_with_source() -> Vec<(String, String)> {
         })
         .collect()
 }
+
+// Returns the path and source code of each file in the minimal stdlib crate, in the same manner
+// as `stdlib_paths_with_source`.
+pub(crate) fn minimal_stdlib_paths_with_source() -> Vec<(String, String)> {
+    let minimal_root = Path::new("std-min").join("lib.nr");
+    let minimal_stdlib_files = MinimalStdLibAssets::iter().map(|path| {
+        let file = MinimalStdLibAssets::get(&path).unwrap();
+        let mut source = std::str::from_utf8(file.data.as_ref()).unwrap().to_string();
+        if Path::new(&*path) == minimal_root {
+            source.push('\n');
+            source.push_str(&stdlib_root_items(&stdlib_source("lib.nr")));
+        }
+        (path.to_string(), source)
+    });
+    let shared_files = MINIMAL_STDLIB_SHARED_MODULES.into_iter().map(|module| {
+        let path = Path::new("std-min").join(module).to_string_lossy().into_owned();
+        (path, stdlib_source(module))
+    });
+    minimal_stdlib_files.chain(shared_files).collect()
+}
+
+/// Returns the source of the file at `path` within the full stdlib.
+fn stdlib_source(path: &str) -> String {
+    let file = StdLibAssets::get(&Path::new("std").join(path).to_string_lossy())
+        .expect("shared stdlib file should exist");
+    std::str::from_utf8(file.data.as_ref()).unwrap().to_string()
+}
+
+/// Returns the source of the root of the full stdlib without its module declarations, so that the minimal
+/// stdlib defines the same items without duplicating them.
+///
+/// The minimal stdlib declares its own modules, as most of those in the full stdlib are left out.
+fn stdlib_root_items(stdlib_root: &str) -> String {
+    stdlib_root.split_inclusive('\n').filter(|line| !is_module_declaration(line)).collect()
+}
+
+fn is_module_declaration(line: &str) -> bool {
+    let line = line.trim();
+    let line = line.strip_prefix("pub ").unwrap_or(line);
+    line.starts_with("mod ") && line.ends_with(';')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_module_declaration, stdlib_root_items, stdlib_source};
+
+    #[test]
+    fn shares_every_root_item_without_module_declarations() {
+        let root_items = stdlib_root_items(&stdlib_source("lib.nr"));
+
+        assert!(root_items.lines().all(|line| !is_module_declaration(line)));
+        for function in ["fn println", "fn assert_constant", "fn wrapping_mul"] {
+            assert!(root_items.contains(function), "minimal stdlib is missing `{function}`");
+        }
+    }
+
+    #[test]
+    fn strips_module_declarations_wherever_they_are() {
+        let source = "mod a;\nfn foo() {}\n  pub mod b;\nfn model() {}\n";
+        assert_eq!(stdlib_root_items(source), "fn foo() {}\nfn model() {}\n");
+    }
+}
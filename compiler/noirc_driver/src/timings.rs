@@ -0,0 +1,34 @@
+use std::{ops::AddAssign, time::Duration};
+
+use noirc_evaluator::ssa::CircuitTimings;
+
+/// Wall time spent in each phase of compiling a package.
+///
+/// These are not written to build artifacts so will be zeroed for any program which is read back from disk.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompilationTimings {
+    /// Time spent parsing, resolving names and type checking the crate.
+    pub frontend: Duration,
+    /// Time spent generating the SSA and running the SSA optimization passes.
+    pub ssa_generation: Duration,
+    /// Time spent converting the final SSA into ACIR and optimizing the result.
+    pub acir_generation: Duration,
+}
+
+impl From<CircuitTimings> for CompilationTimings {
+    fn from(timings: CircuitTimings) -> Self {
+        CompilationTimings {
+            frontend: Duration::ZERO,
+            ssa_generation: timings.ssa_generation,
+            acir_generation: timings.acir_generation,
+        }
+    }
+}
+
+impl AddAssign for CompilationTimings {
+    fn add_assign(&mut self, other: Self) {
+        self.frontend += other.frontend;
+        self.ssa_generation += other.ssa_generation;
+        self.acir_generation += other.acir_generation;
+    }
+}
@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use noirc_driver::{file_manager_with_stdlib, prepare_crate, CompileOptions, ErrorsAndWarnings};
+use noirc_frontend::hir::{def_map::parse_file, Context};
+
+#[test]
+fn compiles_each_public_function_into_a_separate_program() -> Result<(), ErrorsAndWarnings> {
+    let source = "
+fn main(x: Field, y: pub Field) {
+    assert(x != y);
+}
+
+pub fn double(x: Field) -> Field {
+    x * 2
+}
+
+fn private_helper(x: Field) -> Field {
+    x
+}
+
+#[test]
+pub fn test_main() {
+    main(1, 2);
+}";
+
+    let root = Path::new("");
+    let file_name = Path::new("main.nr");
+    let mut file_manager = file_manager_with_stdlib(root);
+    file_manager.add_file_with_source(file_name, source.to_owned()).expect(
+        "Adding source buffer to file manager should never fail when file manager is empty",
+    );
+    let parsed_files = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .map(|&file_id| (file_id, parse_file(&file_manager, file_id)))
+        .collect();
+
+    let mut context = Context::new(file_manager, parsed_files);
+    let root_crate_id = prepare_crate(&mut context, file_name);
+
+    let (programs, _) =
+        noirc_driver::compile_entrypoints(&mut context, root_crate_id, &CompileOptions::default())?;
+
+    assert_eq!(programs.keys().collect::<Vec<_>>(), vec!["double", "main"]);
+    assert_eq!(programs["double"].abi.parameters.len(), 1);
+    assert_eq!(programs["main"].abi.parameters.len(), 2);
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use std::path::Path;
 
-use noirc_driver::{file_manager_with_stdlib, prepare_crate, ErrorsAndWarnings};
+use noirc_driver::{
+    file_manager_with_stdlib, prepare_crate, prepare_crate_with_features, ErrorsAndWarnings,
+    MINIMAL_STD_FEATURE,
+};
 use noirc_frontend::hir::{def_map::parse_file, Context};
 
 #[test]
@@ -30,3 +33,38 @@ fn stdlib_does_not_produce_constant_warnings() -> Result<(), ErrorsAndWarnings>
 
     Ok(())
 }
+
+#[test]
+fn minimal_stdlib_does_not_produce_warnings() -> Result<(), ErrorsAndWarnings> {
+    // Operators rely on the stdlib's traits, so check that the minimal stdlib still provides them.
+    // Functions from the root of the full stdlib are shared with the minimal stdlib.
+    let source = "fn main(x: u8, y: u8) -> pub u8 {
+    dep::std::println(x);
+    let z = dep::std::wrapping_mul(x, y);
+    if x < z { x * y } else { x + y }
+}";
+
+    let root = Path::new("");
+    let file_name = Path::new("main.nr");
+    let mut file_manager = file_manager_with_stdlib(root);
+    file_manager.add_file_with_source(file_name, source.to_owned()).expect(
+        "Adding source buffer to file manager should never fail when file manager is empty",
+    );
+    let parsed_files = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .map(|&file_id| (file_id, parse_file(&file_manager, file_id)))
+        .collect();
+
+    let mut context = Context::new(file_manager, parsed_files);
+    let features = vec![MINIMAL_STD_FEATURE.to_string()];
+    let root_crate_id = prepare_crate_with_features(&mut context, file_name, &features);
+
+    let ((), warnings) = noirc_driver::check_crate(&mut context, root_crate_id, false, false)?;
+
+    assert_eq!(warnings, Vec::new(), "minimal stdlib is producing warnings");
+    let stdlib_root = context.crate_graph[context.stdlib_crate_id()].root_file_id;
+    assert_eq!(context.file_manager.path(stdlib_root), Some(Path::new("std-min/lib.nr")));
+
+    Ok(())
+}
@@ -3,6 +3,33 @@ use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::files::Files;
 use codespan_reporting::term;
 use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
+use std::sync::OnceLock;
+
+/// The [ColorChoice] used when reporting diagnostics, see [set_color_choice].
+static COLOR_CHOICE: OnceLock<ColorChoice> = OnceLock::new();
+
+/// Sets whether diagnostics should be reported with colors for the remainder of the process.
+///
+/// Diagnostics are always colored if this is not called. Only the first call has any effect.
+pub fn set_color_choice(color_choice: ColorChoice) {
+    let _ = COLOR_CHOICE.set(color_choice);
+}
+
+/// Returns the [ColorChoice] which diagnostics should be reported with.
+pub fn color_choice() -> ColorChoice {
+    COLOR_CHOICE.get().copied().unwrap_or(ColorChoice::Always)
+}
+
+/// The maximum number of warnings reported by each call to [report_all], see [set_max_warnings].
+static MAX_WARNINGS: OnceLock<usize> = OnceLock::new();
+
+/// Limits the number of warnings printed by each call to [report_all] for the remainder of the process.
+/// Any further warnings are summarized by a count rather than printed.
+///
+/// Every warning is printed if this is not called. Only the first call has any effect.
+pub fn set_max_warnings(max_warnings: usize) {
+    let _ = MAX_WARNINGS.set(max_warnings);
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CustomDiagnostic {
@@ -116,16 +143,44 @@ pub fn report_all<'files>(
     diagnostics: &[FileDiagnostic],
     deny_warnings: bool,
     silence_warnings: bool,
+) -> ReportedErrors {
+    report_all_with(diagnostics, deny_warnings, silence_warnings, |diagnostic| {
+        diagnostic.report(files, deny_warnings)
+    })
+}
+
+/// Reports the given diagnostics as [report_all] does, but passes each diagnostic which is shown to `report`
+/// rather than writing it to stderr. `report` returns whether the diagnostic was an error.
+pub fn report_all_with(
+    diagnostics: &[FileDiagnostic],
+    deny_warnings: bool,
+    silence_warnings: bool,
+    mut report: impl FnMut(&FileDiagnostic) -> bool,
 ) -> ReportedErrors {
     // Report warnings before any errors
-    let (warnings, mut errors): (Vec<_>, _) =
+    let (warnings, errors): (Vec<_>, Vec<_>) =
         diagnostics.iter().partition(|item| item.diagnostic.is_warning());
 
-    let mut diagnostics = if silence_warnings { Vec::new() } else { warnings };
-    diagnostics.append(&mut errors);
+    let mut warnings = if silence_warnings { Vec::new() } else { warnings };
+    let hidden_warnings = match MAX_WARNINGS.get() {
+        Some(&max_warnings) if warnings.len() > max_warnings => {
+            warnings.split_off(max_warnings).len()
+        }
+        _ => 0,
+    };
 
-    let error_count =
-        diagnostics.iter().map(|error| error.report(files, deny_warnings) as u32).sum();
+    let mut error_count: u32 = warnings.into_iter().map(|warning| report(warning) as u32).sum();
+    if hidden_warnings > 0 {
+        eprintln!(
+            "... and {hidden_warnings} more warning{}",
+            if hidden_warnings == 1 { "" } else { "s" }
+        );
+        // Hidden warnings are still errors when warnings are denied.
+        if deny_warnings {
+            error_count += hidden_warnings as u32;
+        }
+    }
+    error_count += errors.into_iter().map(|error| report(error) as u32).sum::<u32>();
 
     ReportedErrors { error_count }
 }
@@ -148,7 +203,7 @@ pub fn report<'files>(
     call_stack: &[Location],
     deny_warnings: bool,
 ) -> bool {
-    let writer = StandardStream::stderr(ColorChoice::Always);
+    let writer = StandardStream::stderr(color_choice());
     let config = codespan_reporting::term::Config::default();
 
     let stack_trace = stack_trace(files, call_stack);
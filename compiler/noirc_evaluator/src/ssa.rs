@@ -7,7 +7,10 @@
 //! This module heavily borrows from Cranelift
 #![allow(dead_code)]
 
-use std::collections::BTreeSet;
+use std::{
+    collections::BTreeSet,
+    time::{Duration, Instant},
+};
 
 use crate::{
     brillig::Brillig,
@@ -33,17 +36,28 @@ pub mod ir;
 mod opt;
 pub mod ssa_gen;
 
+/// Wall time spent in each phase of [`create_circuit`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CircuitTimings {
+    /// Time spent generating the SSA and running the SSA optimization passes.
+    pub ssa_generation: Duration,
+    /// Time spent converting the final SSA into ACIR and optimizing the result.
+    pub acir_generation: Duration,
+}
+
 /// Optimize the given program by converting it into SSA
 /// form and performing optimizations there. When finished,
-/// convert the final SSA into ACIR and return it.
+/// convert the final SSA into ACIR and return it alongside the time spent in SSA generation.
 pub(crate) fn optimize_into_acir(
     program: Program,
     print_ssa_passes: bool,
     print_brillig_trace: bool,
     force_brillig_output: bool,
-) -> Result<GeneratedAcir, RuntimeError> {
+) -> Result<(GeneratedAcir, Duration), RuntimeError> {
     let abi_distinctness = program.return_distinctness;
 
+    let ssa_gen_start = Instant::now();
+
     let ssa_gen_span = span!(Level::TRACE, "ssa_generation");
     let ssa_gen_span_guard = ssa_gen_span.enter();
     let ssa = SsaBuilder::new(program, print_ssa_passes, force_brillig_output)?
@@ -74,10 +88,12 @@ pub(crate) fn optimize_into_acir(
     let brillig = ssa.to_brillig(print_brillig_trace);
 
     drop(ssa_gen_span_guard);
+    let ssa_gen_time = ssa_gen_start.elapsed();
 
     let last_array_uses = ssa.find_last_array_uses();
 
-    ssa.into_acir(brillig, abi_distinctness, &last_array_uses)
+    let generated_acir = ssa.into_acir(brillig, abi_distinctness, &last_array_uses)?;
+    Ok((generated_acir, ssa_gen_time))
 }
 
 /// Compiles the [`Program`] into [`ACIR`][acvm::acir::circuit::Circuit].
@@ -90,12 +106,16 @@ pub fn create_circuit(
     enable_ssa_logging: bool,
     enable_brillig_logging: bool,
     force_brillig_output: bool,
-) -> Result<(Circuit, DebugInfo, Vec<Witness>, Vec<Witness>, Vec<SsaReport>), RuntimeError> {
+) -> Result<
+    (Circuit, DebugInfo, Vec<Witness>, Vec<Witness>, Vec<SsaReport>, CircuitTimings),
+    RuntimeError,
+> {
+    let start = Instant::now();
     let debug_variables = program.debug_variables.clone();
     let debug_types = program.debug_types.clone();
     let func_sig = program.main_function_signature.clone();
     let recursive = program.recursive;
-    let mut generated_acir = optimize_into_acir(
+    let (mut generated_acir, ssa_generation) = optimize_into_acir(
         program,
         enable_ssa_logging,
         enable_brillig_logging,
@@ -141,7 +161,10 @@ pub fn create_circuit(
     let (optimized_circuit, transformation_map) = acvm::compiler::optimize(circuit);
     debug_info.update_acir(transformation_map);
 
-    Ok((optimized_circuit, debug_info, input_witnesses, return_witnesses, warnings))
+    let timings =
+        CircuitTimings { ssa_generation, acir_generation: start.elapsed() - ssa_generation };
+
+    Ok((optimized_circuit, debug_info, input_witnesses, return_witnesses, warnings, timings))
 }
 
 // Takes each function argument and partitions the circuit's inputs witnesses according to its visibility.
@@ -5,6 +5,7 @@ use crate::macros_api::MacroProcessor;
 use crate::node_interner::{FuncId, NodeInterner, StructId};
 use crate::parser::{parse_program, ParsedModule, ParserError};
 use crate::token::{FunctionAttribute, SecondaryAttribute, TestScope};
+use crate::FunctionVisibility;
 use arena::{Arena, Index};
 use fm::{FileId, FileManager};
 use noirc_errors::Location;
@@ -148,6 +149,27 @@ impl CrateDefMap {
         root_module.find_func_with_name(&MAIN_FUNCTION.into())
     }
 
+    /// Find all functions in the root module of this crate which can be compiled into a circuit.
+    ///
+    /// These are the `main` function along with any public, non-test functions.
+    pub fn entrypoint_functions<'a>(
+        &'a self,
+        interner: &'a NodeInterner,
+    ) -> impl Iterator<Item = FuncId> + 'a {
+        let root_module = &self.modules()[self.root.0];
+        let main_function = self.main_function();
+
+        root_module.value_definitions().filter_map(move |id| {
+            let func_id = id.as_function()?;
+            let is_test = matches!(
+                interner.function_attributes(&func_id).function,
+                Some(FunctionAttribute::Test(_))
+            );
+            let is_public = interner.function_visibility(func_id) == FunctionVisibility::Public;
+            (Some(func_id) == main_function || (is_public && !is_test)).then_some(func_id)
+        })
+    }
+
     pub fn file_id(&self, module_id: LocalModuleId) -> FileId {
         self.modules[module_id.0].location.file
     }
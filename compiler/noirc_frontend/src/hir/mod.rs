@@ -230,6 +230,20 @@ impl Context<'_, '_> {
             .collect()
     }
 
+    /// Returns the names and ids of all functions in the crate which can be compiled into a circuit.
+    /// - Expects check_crate to be called beforehand
+    pub fn get_all_entrypoint_functions_in_crate(
+        &self,
+        crate_id: &CrateId,
+    ) -> Vec<(String, FuncId)> {
+        let def_map = self.def_map(crate_id).expect("The local crate should be analyzed already");
+
+        def_map
+            .entrypoint_functions(&self.def_interner)
+            .map(|function_id| (self.function_name(&function_id).to_owned(), function_id))
+            .collect()
+    }
+
     /// Return a Vec of all `contract` declarations in the source code and the functions they contain
     pub fn get_all_contracts(&self, crate_id: &CrateId) -> Vec<Contract> {
         self.def_map(crate_id)
@@ -52,6 +52,9 @@ pub enum BackendError {
 
     #[error("The backend encountered an error: {0:?}")]
     CommandFailed(String),
+
+    #[error("The verification key is malformed: {0}")]
+    MalformedVerificationKey(String),
 }
 
 #[derive(Debug)]
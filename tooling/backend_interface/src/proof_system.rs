@@ -11,7 +11,7 @@ use tempfile::tempdir;
 use tracing::warn;
 
 use crate::cli::{
-    GatesCommand, InfoCommand, ProofAsFieldsCommand, ProveCommand, VerifyCommand,
+    GatesCommand, InfoCommand, ProofAsFieldsCommand, ProveCommand, VerifyCommand, VersionCommand,
     VkAsFieldsCommand, WriteVkCommand,
 };
 use crate::{Backend, BackendError};
@@ -39,6 +39,12 @@ impl Backend {
         InfoCommand { crs_path: self.crs_directory() }.run(binary_path)
     }
 
+    /// Returns the version reported by the backend binary.
+    pub fn version(&self) -> Result<String, BackendError> {
+        let binary_path = self.assert_binary_exists()?;
+        VersionCommand.run(binary_path).map(|version| version.trim().to_string())
+    }
+
     /// If we cannot get a valid backend, returns `ExpressionWidth::Bound { width: 3 }``
     /// The function also prints a message saying we could not find a backend
     pub fn get_backend_info_or_default(&self) -> ExpressionWidth {
@@ -126,6 +132,45 @@ impl Backend {
         VerifyCommand { crs_path: self.crs_directory(), proof_path, vk_path }.run(binary_path)
     }
 
+    /// Verifies `proof` against a verification key which was previously written by the backend, rather than deriving
+    /// one from the circuit.
+    pub fn verify_with_vk(
+        &self,
+        proof: &[u8],
+        public_inputs: WitnessMap,
+        vk: &[u8],
+    ) -> Result<bool, BackendError> {
+        let binary_path = self.assert_binary_exists()?;
+        self.assert_correct_version()?;
+
+        let temp_directory = tempdir().expect("could not create a temporary directory");
+        let temp_directory = temp_directory.path().to_path_buf();
+
+        // Create a temporary file for the proof
+        let proof_with_public_inputs =
+            bb_abstraction_leaks::prepend_public_inputs(proof.to_vec(), public_inputs);
+        let proof_path = temp_directory.join("proof").with_extension("proof");
+        write_to_file(&proof_with_public_inputs, &proof_path);
+
+        // Create a temporary file for the verification key
+        let vk_path = temp_directory.join("vk");
+        write_to_file(vk, &vk_path);
+
+        // The backend does not distinguish a malformed key from an invalid proof when verifying,
+        // so we check that the key can be parsed first.
+        VkAsFieldsCommand { vk_path: vk_path.clone() }.run(binary_path).map_err(
+            |err| match err {
+                BackendError::CommandFailed(message) => {
+                    BackendError::MalformedVerificationKey(message)
+                }
+                err => err,
+            },
+        )?;
+
+        // Verify the proof
+        VerifyCommand { crs_path: self.crs_directory(), proof_path, vk_path }.run(binary_path)
+    }
+
     pub fn get_intermediate_proof_artifacts(
         &self,
         circuit: &Circuit,
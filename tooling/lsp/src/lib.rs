@@ -24,7 +24,7 @@ use lsp_types::CodeLens;
 use nargo::{
     package::{Package, PackageType},
     parse_all,
-    workspace::Workspace,
+    workspace::{Profile, Workspace},
 };
 use nargo_toml::{find_file_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{file_manager_with_stdlib, prepare_crate, NOIR_ARTIFACT_VERSION_STRING};
@@ -237,12 +237,16 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
             name: CrateName::from_str(parent_folder)
                 .map_err(|err| LspError::WorkspaceResolutionError(err.to_string()))?,
             dependencies: BTreeMap::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
         };
         let workspace = Workspace {
             root_dir: PathBuf::from(parent_folder),
             members: vec![assumed_package],
             selected_package_index: Some(0),
             is_assumed: true,
+            profile: Profile::default(),
+            warnings: Vec::new(),
         };
         Ok(workspace)
     }
@@ -0,0 +1,61 @@
+use acvm::acir::circuit::Circuit;
+use noirc_abi::Abi;
+use noirc_driver::{CompilationTimings, CompiledProgram};
+use serde::{Deserialize, Serialize};
+
+/// The version of the [`ProgramBundle`] format, which is incremented whenever its layout changes.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// A single file holding everything needed to execute, prove and verify a compiled program, so that it can be
+/// distributed without its sources.
+///
+/// Unlike a [`ProgramArtifact`][super::program::ProgramArtifact] it carries no debug information, but records the
+/// backend which the circuit was compiled for.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProgramBundle {
+    /// The [BUNDLE_FORMAT_VERSION] with which this bundle was written.
+    pub format_version: u32,
+
+    pub noir_version: String,
+
+    /// The name of the backend whose expression width the circuit was transformed for.
+    pub backend: String,
+
+    pub hash: u64,
+
+    pub abi: Abi,
+
+    #[serde(
+        serialize_with = "Circuit::serialize_circuit_base64",
+        deserialize_with = "Circuit::deserialize_circuit_base64"
+    )]
+    pub bytecode: Circuit,
+}
+
+impl ProgramBundle {
+    pub fn new(program: CompiledProgram, backend: String) -> Self {
+        ProgramBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            noir_version: program.noir_version,
+            backend,
+            hash: program.hash,
+            abi: program.abi,
+            bytecode: program.circuit,
+        }
+    }
+}
+
+impl From<ProgramBundle> for CompiledProgram {
+    fn from(bundle: ProgramBundle) -> Self {
+        CompiledProgram {
+            hash: bundle.hash,
+            abi: bundle.abi,
+            noir_version: bundle.noir_version,
+            circuit: bundle.bytecode,
+            debug: Default::default(),
+            file_map: Default::default(),
+            warnings: vec![],
+            timings: CompilationTimings::default(),
+        }
+    }
+}
@@ -3,7 +3,9 @@
 //! These artifacts are intended to remain independent of any applications being built on top of Noir.
 //! Should any projects require/desire a different artifact format, it's expected that they will write a transformer
 //! to generate them using these artifacts as a starting point.
+pub mod bundle;
 pub mod contract;
 pub mod debug;
 mod debug_vars;
 pub mod program;
+pub mod witness;
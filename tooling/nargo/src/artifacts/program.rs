@@ -3,6 +3,7 @@ use std::collections::BTreeMap;
 use acvm::acir::circuit::Circuit;
 use fm::FileId;
 use noirc_abi::Abi;
+use noirc_driver::CompilationTimings;
 use noirc_driver::CompiledProgram;
 use noirc_driver::DebugFile;
 use noirc_errors::debug_info::DebugInfo;
@@ -59,6 +60,7 @@ impl From<ProgramArtifact> for CompiledProgram {
             debug: program.debug_symbols,
             file_map: program.file_map,
             warnings: vec![],
+            timings: CompilationTimings::default(),
         }
     }
 }
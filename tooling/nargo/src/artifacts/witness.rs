@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+
+use acvm::{
+    acir::native_types::{Witness, WitnessMap},
+    FieldElement,
+};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The version of the JSON witness schema which is written by [witness_to_json].
+///
+/// This is incremented whenever the schema changes in a way which existing readers cannot handle.
+pub const WITNESS_JSON_SCHEMA_VERSION: u32 = 1;
+
+/// The JSON representation of a [WitnessMap], e.g.
+///
+/// ```json
+/// { "version": 1, "witnesses": { "1": "5", "2": "21888242871839275222246405745257275088548364400416034343698204186575808495616" } }
+/// ```
+///
+/// Each witness index is mapped to its value as a decimal string, so that values larger than `2^53` are not
+/// truncated by JSON parsers which represent numbers as floating point.
+#[derive(Serialize, Deserialize, Debug)]
+struct WitnessJson {
+    version: u32,
+    witnesses: BTreeMap<u32, String>,
+}
+
+#[derive(Debug, Error)]
+pub enum WitnessJsonError {
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(
+        "Unsupported witness schema version {0}, expected version {WITNESS_JSON_SCHEMA_VERSION}"
+    )]
+    UnsupportedVersion(u32),
+
+    #[error(
+        "Invalid value `{value}` for witness {index}, expected a field element as a decimal string"
+    )]
+    InvalidValue { index: u32, value: String },
+}
+
+/// The header row of the CSV representation of a [WitnessMap].
+const WITNESS_CSV_HEADER: &str = "witness,value";
+
+#[derive(Debug, Error)]
+pub enum WitnessCsvError {
+    #[error("Invalid row `{row}` on line {line}, expected a witness index and a value")]
+    InvalidRow { line: usize, row: String },
+
+    #[error(
+        "Invalid value `{value}` for witness {index}, expected a field element as a decimal string"
+    )]
+    InvalidValue { index: u32, value: String },
+}
+
+/// Serializes the witness map as JSON following the schema of [WITNESS_JSON_SCHEMA_VERSION].
+pub fn witness_to_json(witnesses: WitnessMap) -> String {
+    let witnesses = witnesses
+        .into_iter()
+        .map(|(witness, value)| {
+            (witness.witness_index(), BigUint::from_bytes_be(&value.to_be_bytes()).to_string())
+        })
+        .collect();
+    let witness_json = WitnessJson { version: WITNESS_JSON_SCHEMA_VERSION, witnesses };
+    serde_json::to_string(&witness_json).expect("witness map should be serializable")
+}
+
+/// Deserializes a witness map which was serialized by [witness_to_json].
+pub fn witness_from_json(json: &str) -> Result<WitnessMap, WitnessJsonError> {
+    let witness_json: WitnessJson = serde_json::from_str(json)?;
+    if witness_json.version != WITNESS_JSON_SCHEMA_VERSION {
+        return Err(WitnessJsonError::UnsupportedVersion(witness_json.version));
+    }
+
+    let mut witnesses = WitnessMap::new();
+    for (index, value) in witness_json.witnesses {
+        let field =
+            parse_decimal_field(&value).ok_or(WitnessJsonError::InvalidValue { index, value })?;
+        witnesses.insert(Witness(index), field);
+    }
+    Ok(witnesses)
+}
+
+/// Serializes the witness map as CSV with a row for each witness, holding its index and its value as a decimal
+/// string, sorted by index, e.g.
+///
+/// ```csv
+/// witness,value
+/// 1,5
+/// 2,21888242871839275222246405745257275088548364400416034343698204186575808495616
+/// ```
+pub fn witness_to_csv(witnesses: WitnessMap) -> String {
+    let mut csv = format!("{WITNESS_CSV_HEADER}\n");
+    for (witness, value) in witnesses {
+        let value = BigUint::from_bytes_be(&value.to_be_bytes());
+        csv.push_str(&format!("{},{value}\n", witness.witness_index()));
+    }
+    csv
+}
+
+/// Deserializes a witness map which was serialized by [witness_to_csv].
+pub fn witness_from_csv(csv: &str) -> Result<WitnessMap, WitnessCsvError> {
+    let mut witnesses = WitnessMap::new();
+    for (line, row) in csv.lines().enumerate() {
+        if row.is_empty() || (line == 0 && row == WITNESS_CSV_HEADER) {
+            continue;
+        }
+        let invalid_row = || WitnessCsvError::InvalidRow { line: line + 1, row: row.to_owned() };
+        let (index, value) = row.split_once(',').ok_or_else(invalid_row)?;
+        let index: u32 = index.parse().map_err(|_| invalid_row())?;
+        let field = parse_decimal_field(value)
+            .ok_or_else(|| WitnessCsvError::InvalidValue { index, value: value.to_owned() })?;
+        witnesses.insert(Witness(index), field);
+    }
+    Ok(witnesses)
+}
+
+// Only canonical decimal representations are accepted, rather than reducing values modulo the field.
+fn parse_decimal_field(value: &str) -> Option<FieldElement> {
+    value
+        .chars()
+        .all(|char| char.is_ascii_digit())
+        .then(|| BigUint::parse_bytes(value.as_bytes(), 10))
+        .flatten()
+        .filter(|number| number < &FieldElement::modulus())
+        .map(|number| FieldElement::from_be_bytes_reduce(&number.to_bytes_be()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::{
+        acir::native_types::{Witness, WitnessMap},
+        FieldElement,
+    };
+
+    use super::{
+        witness_from_csv, witness_from_json, witness_to_csv, witness_to_json, WitnessCsvError,
+        WitnessJsonError,
+    };
+
+    #[test]
+    fn witness_json_round_trip() {
+        let witnesses = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::from(5_u128)),
+            (Witness(2), -FieldElement::one()),
+            (Witness(10), FieldElement::zero()),
+        ]));
+
+        let json = witness_to_json(witnesses.clone());
+        assert_eq!(
+            json,
+            r#"{"version":1,"witnesses":{"1":"5","2":"21888242871839275222246405745257275088548364400416034343698204186575808495616","10":"0"}}"#
+        );
+        assert_eq!(witness_from_json(&json).unwrap(), witnesses);
+    }
+
+    #[test]
+    fn rejects_invalid_witness_json() {
+        assert!(matches!(
+            witness_from_json(r#"{"version":2,"witnesses":{}}"#),
+            Err(WitnessJsonError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            witness_from_json(r#"{"version":1,"witnesses":{"1":"0x05"}}"#),
+            Err(WitnessJsonError::InvalidValue { index: 1, .. })
+        ));
+        assert!(matches!(
+            witness_from_json(
+                r#"{"version":1,"witnesses":{"1":"21888242871839275222246405745257275088548364400416034343698204186575808495617"}}"#
+            ),
+            Err(WitnessJsonError::InvalidValue { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn witness_csv_round_trip() {
+        let witnesses = WitnessMap::from(BTreeMap::from([
+            (Witness(10), FieldElement::zero()),
+            (Witness(1), FieldElement::from(5_u128)),
+            (Witness(2), -FieldElement::one()),
+        ]));
+
+        let csv = witness_to_csv(witnesses.clone());
+        assert_eq!(
+            csv,
+            "witness,value\n1,5\n2,21888242871839275222246405745257275088548364400416034343698204186575808495616\n10,0\n"
+        );
+        assert_eq!(witness_from_csv(&csv).unwrap(), witnesses);
+
+        assert!(matches!(
+            witness_from_csv("witness,value\n1;5\n"),
+            Err(WitnessCsvError::InvalidRow { line: 2, .. })
+        ));
+        assert!(matches!(
+            witness_from_csv("1,0x05\n"),
+            Err(WitnessCsvError::InvalidValue { index: 1, .. })
+        ));
+    }
+}
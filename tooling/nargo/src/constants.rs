@@ -21,5 +21,13 @@ pub const PKG_FILE: &str = "Nargo.toml";
 // Extensions
 /// The extension for files containing circuit proofs.
 pub const PROOF_EXT: &str = "proof";
+/// The extension for files containing gzip compressed circuit proofs.
+pub const COMPRESSED_PROOF_EXT: &str = "proof.gz";
+/// The extension for files containing the hash of the circuit and witness which a proof was generated from.
+pub const PROOF_HASH_EXT: &str = "proof.hash";
 /// The extension for files containing proof witnesses.
 pub const WITNESS_EXT: &str = "gz";
+/// The extension for files containing proof witnesses serialized as JSON.
+pub const WITNESS_JSON_EXT: &str = "witness.json";
+/// The extension for files containing proof witnesses serialized as CSV.
+pub const WITNESS_CSV_EXT: &str = "witness.csv";
@@ -1,5 +1,5 @@
 use acvm::{
-    acir::circuit::OpcodeLocation,
+    acir::{circuit::OpcodeLocation, native_types::Witness},
     pwg::{ErrorLocation, OpcodeResolutionError},
 };
 use noirc_errors::{
@@ -8,10 +8,39 @@ use noirc_errors::{
 
 pub use noirc_errors::Location;
 
+use fm::FileManager;
 use noirc_frontend::graph::CrateName;
 use noirc_printable_type::ForeignCallError;
 use thiserror::Error;
 
+/// Receives the warnings and errors encountered while compiling a package.
+///
+/// Embedders may provide their own sink to capture structured diagnostics rather than having them printed.
+pub trait DiagnosticSink {
+    /// Receives a single diagnostic, whose location refers to a file held by `file_manager`.
+    fn receive(&mut self, diagnostic: &FileDiagnostic, file_manager: &FileManager);
+}
+
+/// The default [DiagnosticSink], which prints each diagnostic to stderr.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StderrDiagnosticSink {
+    /// Whether warnings should be printed as errors.
+    pub deny_warnings: bool,
+}
+
+impl DiagnosticSink for StderrDiagnosticSink {
+    fn receive(&mut self, diagnostic: &FileDiagnostic, file_manager: &FileManager) {
+        diagnostic.report(file_manager.as_file_map(), self.deny_warnings);
+    }
+}
+
+/// Collects every diagnostic so that they may be inspected once compilation has finished.
+impl DiagnosticSink for Vec<FileDiagnostic> {
+    fn receive(&mut self, diagnostic: &FileDiagnostic, _file_manager: &FileManager) {
+        self.push(diagnostic.clone());
+    }
+}
+
 /// Errors covering situations where a package cannot be compiled.
 #[derive(Debug, Error)]
 pub enum CompileError {
@@ -61,6 +90,7 @@ impl NargoError {
 
         match execution_error {
             ExecutionError::AssertionFailed(message, _) => Some(message),
+            ExecutionError::UnsolvedOpcodes { .. } => None,
             ExecutionError::SolvingError(error) => match error {
                 OpcodeResolutionError::IndexOutOfBounds { .. }
                 | OpcodeResolutionError::OpcodeNotSolvable(_)
@@ -79,6 +109,52 @@ pub enum ExecutionError {
 
     #[error(transparent)]
     SolvingError(#[from] OpcodeResolutionError),
+
+    /// Solving stopped at an opcode which cannot be solved because some of its witnesses were never assigned.
+    #[error(
+        "Cannot solve opcode {opcode_location} as witness{} {} unknown ({unsolved_opcodes} opcode{} left unsolved)",
+        if .unknown_witnesses.len() == 1 { "" } else { "es" },
+        format_witnesses(.unknown_witnesses),
+        if *.unsolved_opcodes == 1 { "" } else { "s" }
+    )]
+    UnsolvedOpcodes {
+        opcode_location: OpcodeLocation,
+        unknown_witnesses: Vec<Witness>,
+        unsolved_opcodes: usize,
+    },
+}
+
+impl ExecutionError {
+    /// Returns the location of the opcode at which execution failed, if the error points at one.
+    pub fn failing_opcode(&self) -> Option<OpcodeLocation> {
+        match self {
+            ExecutionError::AssertionFailed(_, call_stack)
+            | ExecutionError::SolvingError(OpcodeResolutionError::BrilligFunctionFailed {
+                call_stack,
+                ..
+            }) => call_stack.last().copied(),
+            ExecutionError::SolvingError(
+                OpcodeResolutionError::UnsatisfiedConstrain {
+                    opcode_location: ErrorLocation::Resolved(opcode_location),
+                }
+                | OpcodeResolutionError::IndexOutOfBounds {
+                    opcode_location: ErrorLocation::Resolved(opcode_location),
+                    ..
+                },
+            )
+            | ExecutionError::UnsolvedOpcodes { opcode_location, .. } => Some(*opcode_location),
+            ExecutionError::SolvingError(_) => None,
+        }
+    }
+}
+
+fn format_witnesses(witnesses: &[Witness]) -> String {
+    let witnesses: Vec<_> =
+        witnesses.iter().map(|witness| format!("_{}", witness.witness_index())).collect();
+    match witnesses.len() {
+        1 => format!("{} is", witnesses[0]),
+        _ => format!("{} are", witnesses.join(", ")),
+    }
 }
 
 /// Extracts the opcode locations from a nargo error.
@@ -104,6 +180,7 @@ fn extract_locations_from_error(
             }
             ErrorLocation::Resolved(opcode_location) => Some(vec![*opcode_location]),
         },
+        ExecutionError::UnsolvedOpcodes { opcode_location, .. } => Some(vec![*opcode_location]),
         _ => None,
     }?;
 
@@ -14,10 +14,11 @@ pub mod ops;
 pub mod package;
 pub mod workspace;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
 
-use fm::FileManager;
-use noirc_driver::{add_dep, prepare_crate, prepare_dependency};
+use fm::{FileManager, FILE_EXTENSION};
+use noirc_driver::{add_dep, prepare_crate_with_features, prepare_dependency};
 use noirc_frontend::{
     graph::{CrateId, CrateName},
     hir::{def_map::parse_file, Context, ParsedFiles},
@@ -26,6 +27,7 @@ use package::{Dependency, Package};
 use rayon::prelude::*;
 
 pub use self::errors::NargoError;
+pub use noirc_driver::KNOWN_FEATURES;
 
 pub fn prepare_dependencies(
     context: &mut Context,
@@ -47,9 +49,7 @@ pub fn insert_all_files_for_workspace_into_file_manager(
     workspace: &workspace::Workspace,
     file_manager: &mut FileManager,
 ) {
-    for package in workspace.clone().into_iter() {
-        insert_all_files_for_package_into_file_manager(package, file_manager);
-    }
+    insert_files_into_file_manager(get_all_files_in_workspace(workspace), file_manager);
 }
 // We will pre-populate the file manager with all the files in the package
 // This is so that we can avoid having to read from disk when we are compiling
@@ -61,36 +61,56 @@ fn insert_all_files_for_package_into_file_manager(
     package: &Package,
     file_manager: &mut FileManager,
 ) {
-    // Start off at the entry path and read all files in the parent directory.
-    let entry_path_parent = package
-        .entry_path
-        .parent()
-        .unwrap_or_else(|| panic!("The entry path is expected to be a single file within a directory and so should have a parent {:?}", package.entry_path))
-        .clone();
+    let mut paths = BTreeSet::new();
+    collect_all_files_for_package(package, &mut paths);
+    insert_files_into_file_manager(paths, file_manager);
+}
 
-    // Get all files in the package and add them to the file manager
-    let paths =
-        get_all_paths_in_dir(entry_path_parent).expect("could not get all paths in the package");
+fn insert_files_into_file_manager(
+    paths: impl IntoIterator<Item = PathBuf>,
+    file_manager: &mut FileManager,
+) {
     for path in paths {
         let source = std::fs::read_to_string(path.as_path())
             .unwrap_or_else(|_| panic!("could not read file {:?} into string", path));
         file_manager.add_file_with_source(path.as_path(), source);
     }
+}
 
-    insert_all_files_for_packages_dependencies_into_file_manager(package, file_manager);
+/// Returns the paths of every file which belongs to the selected packages of the workspace or to their dependencies.
+///
+/// These are the files which are loaded into the file manager before compilation, so tooling can use this to index
+/// all of the sources involved in a workspace without compiling it.
+pub fn get_all_files_in_workspace(workspace: &workspace::Workspace) -> Vec<PathBuf> {
+    let mut paths = BTreeSet::new();
+    for package in workspace.clone().into_iter() {
+        collect_all_files_for_package(package, &mut paths);
+    }
+    paths.into_iter().collect()
 }
 
-// Inserts all files for the dependencies of the package into the file manager
-// too
-fn insert_all_files_for_packages_dependencies_into_file_manager(
-    package: &Package,
-    file_manager: &mut FileManager,
-) {
+// Collects all files in the package along with the files of each of its dependencies
+fn collect_all_files_for_package(package: &Package, paths: &mut BTreeSet<PathBuf>) {
+    // Start off at the entry path and read all files in the parent directory.
+    let entry_path_parent = package
+        .entry_path
+        .parent()
+        .unwrap_or_else(|| panic!("The entry path is expected to be a single file within a directory and so should have a parent {:?}", package.entry_path));
+
+    // Get all Noir source files in the package which are selected by its `include` and `exclude` globs. Other files,
+    // such as build artifacts next to a standalone entrypoint file, are ignored as they may not be valid UTF-8.
+    paths.extend(
+        get_all_paths_in_dir(entry_path_parent)
+            .expect("could not get all paths in the package")
+            .into_iter()
+            .filter(|path| path.extension().is_some_and(|extension| extension == FILE_EXTENSION))
+            .filter(|path| package.is_source_file_included(path)),
+    );
+
     for (_, dep) in package.dependencies.iter() {
         match dep {
             Dependency::Local { package } | Dependency::Remote { package } => {
-                insert_all_files_for_package_into_file_manager(package, file_manager);
-                insert_all_files_for_packages_dependencies_into_file_manager(package, file_manager);
+                collect_all_files_for_package(package, paths);
             }
         }
     }
@@ -115,10 +135,20 @@ pub fn prepare_package<'file_manager, 'parsed_files>(
     file_manager: &'file_manager FileManager,
     parsed_files: &'parsed_files ParsedFiles,
     package: &Package,
+) -> (Context<'file_manager, 'parsed_files>, CrateId) {
+    prepare_package_with_features(file_manager, parsed_files, package, &[])
+}
+
+/// Like [prepare_package], but links the stdlib selected by the enabled `features`.
+pub fn prepare_package_with_features<'file_manager, 'parsed_files>(
+    file_manager: &'file_manager FileManager,
+    parsed_files: &'parsed_files ParsedFiles,
+    package: &Package,
+    features: &[String],
 ) -> (Context<'file_manager, 'parsed_files>, CrateId) {
     let mut context = Context::from_ref_file_manager(file_manager, parsed_files);
 
-    let crate_id = prepare_crate(&mut context, &package.entry_path);
+    let crate_id = prepare_crate_with_features(&mut context, &package.entry_path, features);
 
     prepare_dependencies(&mut context, crate_id, &package.dependencies);
 
@@ -155,10 +185,16 @@ fn get_all_paths_in_dir(dir: &std::path::Path) -> std::io::Result<Vec<std::path:
 
 #[cfg(test)]
 mod tests {
-    use crate::get_all_paths_in_dir;
+    use crate::{
+        get_all_files_in_workspace, get_all_paths_in_dir,
+        package::{Dependency, Package, PackageType},
+        workspace::Workspace,
+    };
+    use noirc_frontend::graph::CrateName;
     use std::{
+        collections::BTreeMap,
         fs::{self, File},
-        path::Path,
+        path::{Path, PathBuf},
     };
     use tempfile::tempdir;
 
@@ -192,4 +228,54 @@ mod tests {
             assert!(paths.contains(&path));
         }
     }
+
+    fn package_in(
+        root_dir: &Path,
+        name: &str,
+        package_type: PackageType,
+        dependencies: Vec<Package>,
+    ) -> Package {
+        let entry = if package_type == PackageType::Binary { "main.nr" } else { "lib.nr" };
+        fs::create_dir_all(root_dir.join("src")).unwrap();
+        File::create(root_dir.join("src").join(entry)).unwrap();
+        Package {
+            version: None,
+            compiler_required_version: None,
+            root_dir: root_dir.to_path_buf(),
+            package_type,
+            entry_path: root_dir.join("src").join(entry),
+            name: name.parse().unwrap(),
+            dependencies: dependencies
+                .into_iter()
+                .map(|package| (package.name.clone(), Dependency::Local { package }))
+                .collect::<BTreeMap<CrateName, _>>(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn lists_files_of_packages_and_their_dependencies() {
+        let temp_dir = tempdir().expect("could not create a temporary directory");
+        let root = temp_dir.path();
+
+        let dependency = package_in(&root.join("dep"), "dep", PackageType::Library, Vec::new());
+        File::create(root.join("dep/src/module.nr")).unwrap();
+        let package = package_in(&root.join("bin"), "bin", PackageType::Binary, vec![dependency]);
+        let workspace = Workspace {
+            root_dir: root.join("bin"),
+            members: vec![package],
+            selected_package_index: Some(0),
+            is_assumed: false,
+            profile: Default::default(),
+            warnings: Vec::new(),
+        };
+
+        let expected_paths: Vec<PathBuf> = vec![
+            root.join("bin/src/main.nr"),
+            root.join("dep/src/lib.nr"),
+            root.join("dep/src/module.nr"),
+        ];
+        assert_eq!(get_all_files_in_workspace(&workspace), expected_paths);
+    }
 }
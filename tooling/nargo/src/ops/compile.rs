@@ -1,12 +1,23 @@
+use std::collections::BTreeMap;
+
+use acvm::acir::circuit::Circuit;
 use fm::FileManager;
+use noirc_abi::Abi;
 use noirc_driver::{
-    link_to_debug_crate, CompilationResult, CompileOptions, CompiledContract, CompiledProgram,
+    file_manager_with_stdlib, link_to_debug_crate, CompilationResult, CompileOptions,
+    CompiledContract, CompiledProgram,
+};
+use noirc_errors::{
+    reporter::{report_all_with, ReportedErrors},
+    FileDiagnostic,
 };
 use noirc_frontend::debug::DebugInstrumenter;
 use noirc_frontend::hir::ParsedFiles;
 
-use crate::errors::CompileError;
-use crate::prepare_package;
+use crate::errors::{CompileError, DiagnosticSink, StderrDiagnosticSink};
+use crate::{
+    insert_all_files_for_package_into_file_manager, parse_all, prepare_package_with_features,
+};
 use crate::{package::Package, workspace::Workspace};
 
 use rayon::prelude::*;
@@ -21,6 +32,27 @@ pub fn compile_workspace(
     parsed_files: &ParsedFiles,
     workspace: &Workspace,
     compile_options: &CompileOptions,
+) -> Result<(Vec<CompiledProgram>, Vec<CompiledContract>), CompileError> {
+    compile_workspace_with_diagnostic_sink(
+        file_manager,
+        parsed_files,
+        workspace,
+        compile_options,
+        &mut StderrDiagnosticSink { deny_warnings: compile_options.deny_warnings },
+    )
+}
+
+/// Compiles workspace, passing any warnings or errors to `diagnostic_sink` rather than printing them.
+///
+/// # Errors
+///
+/// This function will return an error if there are any compilations errors reported.
+pub fn compile_workspace_with_diagnostic_sink(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    workspace: &Workspace,
+    compile_options: &CompileOptions,
+    diagnostic_sink: &mut dyn DiagnosticSink,
 ) -> Result<(Vec<CompiledProgram>, Vec<CompiledContract>), CompileError> {
     let (binary_packages, contract_packages): (Vec<_>, Vec<_>) = workspace
         .into_iter()
@@ -42,23 +74,13 @@ pub fn compile_workspace(
     let compiled_programs: Vec<CompiledProgram> = program_results
         .into_iter()
         .map(|compilation_result| {
-            report_errors(
-                compilation_result,
-                file_manager,
-                compile_options.deny_warnings,
-                compile_options.silence_warnings,
-            )
+            report_errors(compilation_result, file_manager, compile_options, diagnostic_sink)
         })
         .collect::<Result<_, _>>()?;
     let compiled_contracts: Vec<CompiledContract> = contract_results
         .into_iter()
         .map(|compilation_result| {
-            report_errors(
-                compilation_result,
-                file_manager,
-                compile_options.deny_warnings,
-                compile_options.silence_warnings,
-            )
+            report_errors(compilation_result, file_manager, compile_options, diagnostic_sink)
         })
         .collect::<Result<_, _>>()?;
 
@@ -90,44 +112,181 @@ pub fn compile_program_with_debug_instrumenter(
     cached_program: Option<CompiledProgram>,
     debug_instrumenter: DebugInstrumenter,
 ) -> CompilationResult<CompiledProgram> {
-    let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
+    let (mut context, crate_id) = prepare_package_with_features(
+        file_manager,
+        parsed_files,
+        package,
+        &compile_options.features,
+    );
     link_to_debug_crate(&mut context, crate_id);
     context.debug_instrumenter = debug_instrumenter;
 
     noirc_driver::compile_main(&mut context, crate_id, compile_options, cached_program)
 }
 
+/// Compiles each entrypoint function of `package` into a separate program, keyed by function name.
+pub fn compile_program_entrypoints(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    package: &Package,
+    compile_options: &CompileOptions,
+) -> CompilationResult<BTreeMap<String, CompiledProgram>> {
+    let (mut context, crate_id) = prepare_package_with_features(
+        file_manager,
+        parsed_files,
+        package,
+        &compile_options.features,
+    );
+    link_to_debug_crate(&mut context, crate_id);
+
+    noirc_driver::compile_entrypoints(&mut context, crate_id, compile_options)
+}
+
+/// Compiles the binary `package` into its ACIR [Circuit] and [Abi] without writing any artifacts to disk.
+///
+/// This loads the package's source files itself, so downstream crates can obtain the in-memory circuit
+/// in order to apply their own optimizations or to prove it using a custom backend.
+///
+/// # Errors
+///
+/// This function will return an error if `package` is a library or if any compilation errors are reported.
+pub fn compile_circuit(
+    package: &Package,
+    compile_options: &CompileOptions,
+) -> Result<(Circuit, Abi), CompileError> {
+    compile_circuit_with_diagnostic_sink(
+        package,
+        compile_options,
+        &mut StderrDiagnosticSink { deny_warnings: compile_options.deny_warnings },
+    )
+}
+
+/// Compiles the binary `package` as [compile_circuit] does, passing any warnings or errors to `diagnostic_sink`
+/// rather than printing them.
+///
+/// # Errors
+///
+/// This function will return an error if `package` is a library or if any compilation errors are reported.
+pub fn compile_circuit_with_diagnostic_sink(
+    package: &Package,
+    compile_options: &CompileOptions,
+    diagnostic_sink: &mut dyn DiagnosticSink,
+) -> Result<(Circuit, Abi), CompileError> {
+    if package.is_library() {
+        return Err(CompileError::LibraryCrate(package.name.clone()));
+    }
+
+    let mut file_manager = file_manager_with_stdlib(&package.root_dir);
+    insert_all_files_for_package_into_file_manager(package, &mut file_manager);
+    let parsed_files = parse_all(&file_manager);
+
+    let compilation_result =
+        compile_program(&file_manager, &parsed_files, package, compile_options, None);
+    let program =
+        report_errors(compilation_result, &file_manager, compile_options, diagnostic_sink)?;
+
+    Ok((program.circuit, program.abi))
+}
+
 pub fn compile_contract(
     file_manager: &FileManager,
     parsed_files: &ParsedFiles,
     package: &Package,
     compile_options: &CompileOptions,
 ) -> CompilationResult<CompiledContract> {
-    let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
+    let (mut context, crate_id) = prepare_package_with_features(
+        file_manager,
+        parsed_files,
+        package,
+        &compile_options.features,
+    );
     noirc_driver::compile_contract(&mut context, crate_id, compile_options)
 }
 
 pub(crate) fn report_errors<T>(
     result: CompilationResult<T>,
     file_manager: &FileManager,
-    deny_warnings: bool,
-    silence_warnings: bool,
+    compile_options: &CompileOptions,
+    diagnostic_sink: &mut dyn DiagnosticSink,
 ) -> Result<T, CompileError> {
     let (t, warnings) = result.map_err(|errors| {
-        noirc_errors::reporter::report_all(
-            file_manager.as_file_map(),
-            &errors,
-            deny_warnings,
-            silence_warnings,
-        )
+        report_diagnostics(&errors, file_manager, compile_options, &mut *diagnostic_sink)
     })?;
 
-    noirc_errors::reporter::report_all(
-        file_manager.as_file_map(),
-        &warnings,
-        deny_warnings,
-        silence_warnings,
-    );
+    report_diagnostics(&warnings, file_manager, compile_options, diagnostic_sink);
 
     Ok(t)
 }
+
+/// Passes `diagnostics` to `diagnostic_sink`, warnings before errors, and returns the count of those which were errors.
+fn report_diagnostics(
+    diagnostics: &[FileDiagnostic],
+    file_manager: &FileManager,
+    compile_options: &CompileOptions,
+    diagnostic_sink: &mut dyn DiagnosticSink,
+) -> ReportedErrors {
+    report_all_with(
+        diagnostics,
+        compile_options.deny_warnings,
+        compile_options.silence_warnings,
+        |diagnostic| {
+            diagnostic_sink.receive(diagnostic, file_manager);
+            compile_options.deny_warnings || diagnostic.diagnostic.is_error()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use noirc_driver::CompileOptions;
+    use noirc_errors::FileDiagnostic;
+    use tempfile::tempdir;
+
+    use crate::{
+        errors::CompileError,
+        package::{Package, PackageType},
+    };
+
+    use super::compile_circuit_with_diagnostic_sink;
+
+    #[test]
+    fn diagnostics_are_passed_to_the_sink() {
+        let root_dir = tempdir().unwrap();
+        let entry_path = root_dir.path().join("src").join("main.nr");
+        std::fs::create_dir_all(entry_path.parent().unwrap()).unwrap();
+        std::fs::write(&entry_path, "fn main(x: Field) { let unused = x; }").unwrap();
+        let package = Package {
+            version: None,
+            compiler_required_version: None,
+            root_dir: root_dir.path().to_path_buf(),
+            package_type: PackageType::Binary,
+            entry_path,
+            name: "foo".parse().unwrap(),
+            dependencies: BTreeMap::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        };
+
+        let mut diagnostics: Vec<FileDiagnostic> = Vec::new();
+        compile_circuit_with_diagnostic_sink(
+            &package,
+            &CompileOptions::default(),
+            &mut diagnostics,
+        )
+        .unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].diagnostic.is_warning());
+
+        // Denied warnings are still passed to the sink, but are counted as errors.
+        let mut diagnostics: Vec<FileDiagnostic> = Vec::new();
+        let deny_warnings = CompileOptions { deny_warnings: true, ..CompileOptions::default() };
+        let result =
+            compile_circuit_with_diagnostic_sink(&package, &deny_warnings, &mut diagnostics);
+        assert!(
+            matches!(result, Err(CompileError::ReportedErrors(errors)) if errors.error_count == 1)
+        );
+        assert_eq!(diagnostics.len(), 1);
+    }
+}
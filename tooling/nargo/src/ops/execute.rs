@@ -1,3 +1,7 @@
+use std::collections::BTreeSet;
+
+use acvm::acir::circuit::OpcodeLocation;
+use acvm::acir::native_types::Witness;
 use acvm::brillig_vm::brillig::ForeignCallResult;
 use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeResolutionError, ACVM};
 use acvm::BlackBoxFunctionSolver;
@@ -7,6 +11,7 @@ use crate::errors::ExecutionError;
 use crate::NargoError;
 
 use super::foreign_calls::{ForeignCallExecutor, NargoForeignCallResult};
+use super::trace::SolverTrace;
 
 #[tracing::instrument(level = "trace", skip_all)]
 pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
@@ -16,17 +21,84 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
     foreign_call_executor: &mut F,
 ) -> Result<WitnessMap, NargoError> {
     let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness);
+    solve_circuit(&mut acvm, circuit, foreign_call_executor, None)?;
+    Ok(acvm.finalize())
+}
+
+/// Executes `circuit`, additionally returning the witnesses from `initial_witness` which were never used
+/// while solving the circuit.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_circuit_tracking_unused_witnesses<
+    B: BlackBoxFunctionSolver,
+    F: ForeignCallExecutor,
+>(
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut F,
+) -> Result<(WitnessMap, BTreeSet<Witness>), NargoError> {
+    execute_circuit_with_partial_witness(
+        circuit,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+        None,
+    )
+    .map_err(|(error, _)| error)
+}
+
+/// Executes `circuit` as [execute_circuit_tracking_unused_witnesses] does, but on failure also returns
+/// the witness values which had been solved when execution stopped.
+///
+/// If a `trace` is provided then each opcode is recorded in it as it is solved.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_circuit_with_partial_witness<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut F,
+    trace: Option<&mut SolverTrace>,
+) -> Result<(WitnessMap, BTreeSet<Witness>), (NargoError, WitnessMap)> {
+    let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness)
+        .track_unused_initial_witnesses();
+    if let Err(error) = solve_circuit(&mut acvm, circuit, foreign_call_executor, trace) {
+        return Err((error, acvm.witness_map().clone()));
+    }
+
+    let unused_witnesses =
+        acvm.unused_initial_witnesses().expect("unused initial witnesses should be tracked");
+    Ok((acvm.finalize(), unused_witnesses))
+}
 
+/// Runs `acvm` until `circuit` is solved, resolving any foreign calls with `foreign_call_executor`.
+fn solve_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
+    acvm: &mut ACVM<'_, B>,
+    circuit: &Circuit,
+    foreign_call_executor: &mut F,
+    mut trace: Option<&mut SolverTrace>,
+) -> Result<(), NargoError> {
     // This message should be resolved by a nargo foreign call only when we have an unsatisfied assertion.
     let mut assert_message: Option<String> = None;
     loop {
-        let solver_status = acvm.solve();
+        let solver_status = match trace.as_deref_mut() {
+            Some(trace) => trace.solve(acvm, circuit),
+            None => acvm.solve(),
+        };
 
         match solver_status {
             ACVMStatus::Solved => break,
             ACVMStatus::InProgress => {
                 unreachable!("Execution should not stop while in `InProgress` state.")
             }
+            ACVMStatus::Failure(OpcodeResolutionError::OpcodeNotSolvable(_))
+                if !acvm.unknown_witnesses().is_empty() =>
+            {
+                return Err(NargoError::ExecutionError(ExecutionError::UnsolvedOpcodes {
+                    opcode_location: OpcodeLocation::Acir(acvm.instruction_pointer()),
+                    unknown_witnesses: acvm.unknown_witnesses().into_iter().collect(),
+                    unsolved_opcodes: acvm.unsolved_opcodes().len(),
+                }));
+            }
             ACVMStatus::Failure(error) => {
                 let call_stack = match &error {
                     OpcodeResolutionError::UnsatisfiedConstrain {
@@ -76,5 +148,5 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
         }
     }
 
-    Ok(acvm.finalize())
+    Ok(())
 }
@@ -0,0 +1,137 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use acvm::{
+    acir::{
+        circuit::{Circuit, Opcode, OpcodeLocation},
+        native_types::{Expression, Witness, WitnessMap},
+    },
+    pwg::collect_opcode_witnesses,
+    FieldElement,
+};
+
+use crate::errors::ExecutionError;
+
+/// The opcode at which execution of a circuit failed, along with the values of the witnesses it reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedOpcode {
+    pub opcode_location: OpcodeLocation,
+    pub opcode: Opcode,
+    /// The value of each witness read by the opcode, or `None` if it was never assigned.
+    pub witness_values: Vec<(Witness, Option<FieldElement>)>,
+}
+
+/// Finds the opcode of `circuit` at which `error` occurred, reading the values of its witnesses from `witness`,
+/// the witness map as it was when execution stopped.
+///
+/// Returns `None` if the error does not point at a specific opcode.
+pub fn explain_failed_opcode(
+    circuit: &Circuit,
+    error: &ExecutionError,
+    witness: &WitnessMap,
+) -> Option<FailedOpcode> {
+    let opcode_location = error.failing_opcode()?;
+    let acir_index = match opcode_location {
+        OpcodeLocation::Acir(index) | OpcodeLocation::Brillig { acir_index: index, .. } => index,
+    };
+    let opcode = circuit.opcodes.get(acir_index)?.clone();
+
+    let mut read_witnesses = BTreeSet::new();
+    collect_opcode_witnesses(&opcode, &mut read_witnesses);
+    let witness_values =
+        read_witnesses.into_iter().map(|input| (input, witness.get(&input).copied())).collect();
+    Some(FailedOpcode { opcode_location, opcode, witness_values })
+}
+
+/// Evaluates `expr` if the values of all of its witnesses are known.
+fn evaluate_expression(
+    expr: &Expression,
+    values: &[(Witness, Option<FieldElement>)],
+) -> Option<FieldElement> {
+    let value_of = |witness: &Witness| {
+        values.iter().find(|(known, _)| known == witness).and_then(|(_, value)| *value)
+    };
+
+    let mut result = expr.q_c;
+    for (coefficient, lhs, rhs) in &expr.mul_terms {
+        result += *coefficient * value_of(lhs)? * value_of(rhs)?;
+    }
+    for (coefficient, witness) in &expr.linear_combinations {
+        result += *coefficient * value_of(witness)?;
+    }
+    Some(result)
+}
+
+impl fmt::Display for FailedOpcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Failing opcode {}: {}", self.opcode_location, self.opcode)?;
+        if let Opcode::AssertZero(expr) = &self.opcode {
+            if let Some(value) = evaluate_expression(expr, &self.witness_values) {
+                writeln!(f, "  evaluates to {value} rather than 0")?;
+            }
+        }
+        for (witness, value) in &self.witness_values {
+            match value {
+                Some(value) => writeln!(f, "  _{} = {value}", witness.witness_index())?,
+                None => writeln!(f, "  _{} is unknown", witness.witness_index())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::{
+            circuit::{Circuit, Opcode, OpcodeLocation},
+            native_types::{Expression, Witness, WitnessMap},
+        },
+        pwg::{ErrorLocation, OpcodeResolutionError},
+        FieldElement,
+    };
+
+    use crate::errors::ExecutionError;
+
+    use super::explain_failed_opcode;
+
+    #[test]
+    fn reports_witness_values_of_failed_constraint() {
+        // Constrains `_1 - _2 + 1 == 0`, i.e. `_2 == _1 + 1`.
+        let constraint = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (-FieldElement::one(), Witness(2)),
+            ],
+            q_c: FieldElement::one(),
+        });
+        let circuit = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![Opcode::AssertZero(Expression::default()), constraint],
+            ..Circuit::default()
+        };
+        let error = ExecutionError::SolvingError(OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(1)),
+        });
+        let witness = WitnessMap::from(std::collections::BTreeMap::from([
+            (Witness(1), FieldElement::from(1u128)),
+            (Witness(2), FieldElement::from(3u128)),
+        ]));
+
+        let failed_opcode = explain_failed_opcode(&circuit, &error, &witness).unwrap();
+        assert_eq!(failed_opcode.opcode_location, OpcodeLocation::Acir(1));
+        assert_eq!(
+            failed_opcode.witness_values,
+            vec![
+                (Witness(1), Some(FieldElement::from(1u128))),
+                (Witness(2), Some(FieldElement::from(3u128)))
+            ]
+        );
+
+        let explanation = failed_opcode.to_string();
+        let lines: Vec<_> = explanation.lines().collect();
+        assert_eq!(lines[0], format!("Failing opcode 1: {}", circuit.opcodes[1]));
+        assert_eq!(&lines[1..], ["  evaluates to -1 rather than 0", "  _1 = 1", "  _2 = 3"]);
+    }
+}
@@ -1,18 +1,29 @@
 pub use self::compile::{
-    compile_contract, compile_program, compile_program_with_debug_instrumenter, compile_workspace,
+    compile_circuit, compile_circuit_with_diagnostic_sink, compile_contract, compile_program,
+    compile_program_entrypoints, compile_program_with_debug_instrumenter, compile_workspace,
+    compile_workspace_with_diagnostic_sink,
 };
-pub use self::execute::execute_circuit;
+pub use self::execute::{
+    execute_circuit, execute_circuit_tracking_unused_witnesses,
+    execute_circuit_with_partial_witness,
+};
+pub use self::explain::{explain_failed_opcode, FailedOpcode};
 pub use self::foreign_calls::{
     DefaultForeignCallExecutor, ForeignCall, ForeignCallExecutor, NargoForeignCallResult,
 };
 pub use self::optimize::{optimize_contract, optimize_program};
+pub use self::trace::{OpcodeStatus, SolverTrace, TraceEvent};
 pub use self::transform::{transform_contract, transform_program};
+pub use self::unconstrained::{find_unconstrained_witnesses, UnconstrainedWitness};
 
 pub use self::test::{run_test, TestStatus};
 
 mod compile;
 mod execute;
+mod explain;
 mod foreign_calls;
 mod optimize;
 mod test;
+mod trace;
 mod transform;
+mod unconstrained;
@@ -0,0 +1,182 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use acvm::{
+    acir::{
+        circuit::{brillig::BrilligOutputs, directives::Directive, Circuit, Opcode},
+        native_types::{Expression, Witness, WitnessMap},
+    },
+    pwg::{collect_opcode_witnesses, get_value, ACVMStatus, ACVM},
+    BlackBoxFunctionSolver,
+};
+use serde::Serialize;
+
+/// A record of each opcode which the solver stepped through while executing a circuit, in order.
+#[derive(Debug, Default)]
+pub struct SolverTrace {
+    pub events: Vec<TraceEvent>,
+}
+
+/// The outcome of solving a single opcode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceEvent {
+    pub opcode_index: usize,
+    pub opcode: String,
+    pub status: OpcodeStatus,
+    /// The witnesses which were assigned a value by solving the opcode.
+    pub assigned_witnesses: WitnessMap,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpcodeStatus {
+    Solved,
+    /// The opcode's predicate was zero so it was not executed.
+    Skipped,
+    Failed,
+}
+
+impl SolverTrace {
+    /// Solves `circuit` with `acvm` one opcode at a time, recording an event for each opcode.
+    ///
+    /// Like [ACVM::solve], this returns as soon as the ACVM requires a foreign call. Opcodes which are paused
+    /// on a foreign call are only recorded once they are finished.
+    pub(super) fn solve<B: BlackBoxFunctionSolver>(
+        &mut self,
+        acvm: &mut ACVM<'_, B>,
+        circuit: &Circuit,
+    ) -> ACVMStatus {
+        while *acvm.get_status() == ACVMStatus::InProgress {
+            let opcode_index = acvm.instruction_pointer();
+            let opcode = &circuit.opcodes[opcode_index];
+
+            let witness_map = acvm.witness_map();
+            let skipped = opcode_predicate(opcode).is_some_and(|predicate| {
+                get_value(predicate, witness_map).is_ok_and(|value| value.is_zero())
+            });
+            let unassigned_witnesses: Vec<_> = written_witnesses(opcode)
+                .into_iter()
+                .filter(|witness| !witness_map.contains_key(witness))
+                .collect();
+
+            let status = acvm.solve_opcode();
+            let (status, error) = match &status {
+                ACVMStatus::RequiresForeignCall(_) => return status,
+                ACVMStatus::Failure(error) => (OpcodeStatus::Failed, Some(error.to_string())),
+                _ if skipped => (OpcodeStatus::Skipped, None),
+                _ => (OpcodeStatus::Solved, None),
+            };
+
+            let witness_map = acvm.witness_map();
+            let assigned_witnesses: BTreeMap<_, _> = unassigned_witnesses
+                .into_iter()
+                .filter_map(|witness| Some((witness, *witness_map.get(&witness)?)))
+                .collect();
+            self.events.push(TraceEvent {
+                opcode_index,
+                opcode: opcode.to_string(),
+                status,
+                assigned_witnesses: assigned_witnesses.into(),
+                error,
+            });
+        }
+        acvm.get_status().clone()
+    }
+}
+
+/// Returns the predicate which decides whether `opcode` is executed, if it has one.
+fn opcode_predicate(opcode: &Opcode) -> Option<&Expression> {
+    match opcode {
+        Opcode::Brillig(brillig) => brillig.predicate.as_ref(),
+        Opcode::MemoryOp { predicate, .. } => predicate.as_ref(),
+        _ => None,
+    }
+}
+
+/// Returns the witnesses which solving `opcode` may assign a value to.
+///
+/// Besides its outputs, an opcode may solve for a witness which it reads, e.g. the single unknown of an `AssertZero`.
+fn written_witnesses(opcode: &Opcode) -> BTreeSet<Witness> {
+    let mut witnesses = BTreeSet::new();
+    collect_opcode_witnesses(opcode, &mut witnesses);
+    match opcode {
+        Opcode::BlackBoxFuncCall(call) => witnesses.extend(call.get_outputs_vec()),
+        Opcode::Directive(Directive::ToLeRadix { b, .. }) => witnesses.extend(b.iter().copied()),
+        Opcode::Brillig(brillig) => {
+            for output in &brillig.outputs {
+                match output {
+                    BrilligOutputs::Simple(witness) => {
+                        witnesses.insert(*witness);
+                    }
+                    BrilligOutputs::Array(outputs) => witnesses.extend(outputs.iter().copied()),
+                }
+            }
+        }
+        Opcode::AssertZero(_) | Opcode::MemoryOp { .. } | Opcode::MemoryInit { .. } => {}
+    }
+    witnesses
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::{
+            circuit::{Circuit, Opcode},
+            native_types::{Expression, Witness, WitnessMap},
+        },
+        blackbox_solver::StubbedBlackBoxSolver,
+        FieldElement,
+    };
+
+    use crate::ops::{execute_circuit_with_partial_witness, DefaultForeignCallExecutor};
+
+    use super::{OpcodeStatus, SolverTrace};
+
+    #[test]
+    fn records_assigned_witnesses_and_failures() {
+        // `_2 = _1 + 1` followed by `_2 == 3`.
+        let assign = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (-FieldElement::one(), Witness(2)),
+            ],
+            q_c: FieldElement::one(),
+        });
+        let check = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(FieldElement::one(), Witness(2))],
+            q_c: -FieldElement::from(3u128),
+        });
+        let circuit = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![assign, check],
+            ..Circuit::default()
+        };
+        let initial_witness =
+            WitnessMap::from(std::collections::BTreeMap::from([(Witness(1), FieldElement::one())]));
+
+        let mut trace = SolverTrace::default();
+        let result = execute_circuit_with_partial_witness(
+            &circuit,
+            initial_witness,
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None),
+            Some(&mut trace),
+        );
+        assert!(result.is_err());
+
+        let [solved, failed] = &trace.events[..] else {
+            panic!("expected two events but got {:?}", trace.events);
+        };
+        assert_eq!(solved.status, OpcodeStatus::Solved);
+        assert_eq!(
+            serde_json::to_string(&solved.assigned_witnesses).unwrap(),
+            format!(r#"{{"2":"{}"}}"#, FieldElement::from(2u128).to_hex())
+        );
+        assert_eq!((failed.opcode_index, failed.status), (1, OpcodeStatus::Failed));
+        assert_eq!(failed.assigned_witnesses, WitnessMap::default());
+        assert!(failed.error.is_some());
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use acvm::{
+    acir::{
+        circuit::{
+            brillig::BrilligOutputs, directives::Directive, Circuit, Opcode, OpcodeLocation,
+        },
+        native_types::Witness,
+    },
+    pwg::collect_expression_witnesses,
+};
+use noirc_errors::{debug_info::DebugInfo, CustomDiagnostic, FileDiagnostic};
+
+/// A witness which is assigned a value by the circuit but never constrained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnconstrainedWitness {
+    pub witness: Witness,
+    /// The location of the opcode which assigns a value to the witness.
+    pub opcode_location: OpcodeLocation,
+}
+
+impl UnconstrainedWitness {
+    /// Creates a warning pointing at the source of the opcode which assigns this witness.
+    ///
+    /// Returns `None` if the opcode has no associated source location.
+    pub fn to_diagnostic(&self, debug: &DebugInfo) -> Option<FileDiagnostic> {
+        let location = debug.opcode_location(&self.opcode_location)?.pop()?;
+        let diagnostic = CustomDiagnostic::simple_warning(
+            format!("Witness {} is assigned but never constrained", self.witness.witness_index()),
+            "A malicious prover can set this value freely".to_string(),
+            location.span,
+        );
+        Some(diagnostic.in_file(location.file))
+    }
+}
+
+/// Finds the witnesses which are assigned by a [Directive] or [Brillig][acvm::acir::circuit::brillig::Brillig]
+/// opcode but which never appear in any constraint.
+///
+/// The prover is free to set such witnesses to any value so these are generally soundness bugs.
+pub fn find_unconstrained_witnesses(circuit: &Circuit) -> Vec<UnconstrainedWitness> {
+    let mut assigned_witnesses: BTreeMap<Witness, OpcodeLocation> = BTreeMap::new();
+    let mut constrained_witnesses: BTreeSet<Witness> = BTreeSet::new();
+
+    for (index, opcode) in circuit.opcodes.iter().enumerate() {
+        let mut assign = |witness: Witness| {
+            assigned_witnesses.entry(witness).or_insert(OpcodeLocation::Acir(index));
+        };
+
+        match opcode {
+            Opcode::AssertZero(expr) => {
+                collect_expression_witnesses(expr, &mut constrained_witnesses);
+            }
+            Opcode::BlackBoxFuncCall(bb_func) => {
+                constrained_witnesses
+                    .extend(bb_func.get_inputs_vec().iter().map(|input| input.witness));
+                constrained_witnesses.extend(bb_func.get_outputs_vec());
+            }
+            Opcode::MemoryOp { op, .. } => {
+                for expr in [&op.operation, &op.index, &op.value] {
+                    collect_expression_witnesses(expr, &mut constrained_witnesses);
+                }
+            }
+            Opcode::MemoryInit { init, .. } => constrained_witnesses.extend(init),
+            Opcode::Directive(Directive::ToLeRadix { b, .. }) => {
+                b.iter().copied().for_each(&mut assign);
+            }
+            Opcode::Brillig(brillig) => {
+                for output in &brillig.outputs {
+                    match output {
+                        BrilligOutputs::Simple(witness) => assign(*witness),
+                        BrilligOutputs::Array(witnesses) => {
+                            witnesses.iter().copied().for_each(&mut assign);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    assigned_witnesses
+        .into_iter()
+        .filter(|(witness, _)| !constrained_witnesses.contains(witness))
+        .map(|(witness, opcode_location)| UnconstrainedWitness { witness, opcode_location })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::{
+            brillig::Opcode as BrilligOpcode,
+            circuit::{
+                brillig::{Brillig, BrilligInputs, BrilligOutputs},
+                Circuit, Opcode, OpcodeLocation,
+            },
+            native_types::{Expression, Witness},
+        },
+        FieldElement,
+    };
+
+    use super::{find_unconstrained_witnesses, UnconstrainedWitness};
+
+    #[test]
+    fn flags_brillig_outputs_which_are_never_constrained() {
+        let brillig = Opcode::Brillig(Brillig {
+            inputs: vec![BrilligInputs::Single(Witness(1).into())],
+            outputs: vec![BrilligOutputs::Simple(Witness(2)), BrilligOutputs::Simple(Witness(3))],
+            bytecode: vec![BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 2 }],
+            predicate: None,
+        });
+        // Only `_2` is constrained, `_3` is left free.
+        let constraint = Opcode::AssertZero(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+            linear_combinations: Vec::new(),
+            q_c: -FieldElement::one(),
+        });
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![brillig, constraint],
+            ..Circuit::default()
+        };
+
+        assert_eq!(
+            find_unconstrained_witnesses(&circuit),
+            vec![UnconstrainedWitness {
+                witness: Witness(3),
+                opcode_location: OpcodeLocation::Acir(0)
+            }]
+        );
+    }
+}
@@ -1,6 +1,12 @@
-use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
+use glob::{MatchOptions, Pattern};
 use noirc_frontend::graph::CrateName;
+use thiserror::Error;
 
 use crate::constants::{PROVER_INPUT_FILE, VERIFIER_INPUT_FILE};
 
@@ -34,6 +40,12 @@ impl Dependency {
         }
     }
 
+    pub fn package_type(&self) -> PackageType {
+        match self {
+            Self::Local { package } | Self::Remote { package } => package.package_type,
+        }
+    }
+
     pub fn package_name(&self) -> &CrateName {
         match self {
             Self::Local { package } | Self::Remote { package } => &package.name,
@@ -51,8 +63,30 @@ pub struct Package {
     pub entry_path: PathBuf,
     pub name: CrateName,
     pub dependencies: BTreeMap<CrateName, Dependency>,
+    /// Globs, relative to `root_dir`, of the source files which are compiled. Every source file is compiled if empty.
+    pub include: Vec<String>,
+    /// Globs, relative to `root_dir`, of the source files which are not compiled.
+    pub exclude: Vec<String>,
 }
 
+/// Errors in the `include` and `exclude` globs of a package.
+#[derive(Debug, Error)]
+pub enum SourceGlobError {
+    #[error("`{glob}` is not a valid glob: {message}")]
+    InvalidGlob { glob: String, message: String },
+    #[error("{} is matched by both `include` and `exclude`", .0.display())]
+    IncludedAndExcluded(PathBuf),
+    #[error("the entry file {} must be compiled but is not included", .0.display())]
+    EntryNotIncluded(PathBuf),
+}
+
+// `*` should not match across directories so that `**` can be used to select files in nested directories.
+const GLOB_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
 impl Package {
     pub fn prover_input_path(&self) -> PathBuf {
         // TODO: This should be configurable, such as if we are looking for .json or .toml or custom paths
@@ -76,4 +110,50 @@ impl Package {
     pub fn is_library(&self) -> bool {
         self.package_type == PackageType::Library
     }
+
+    /// Whether the source file at `path` is compiled as part of this package, according to its `include` and
+    /// `exclude` globs.
+    pub fn is_source_file_included(&self, path: &Path) -> bool {
+        (self.include.is_empty() || self.matches_any_glob(&self.include, path))
+            && !self.matches_any_glob(&self.exclude, path)
+    }
+
+    /// Checks that the package's `include` and `exclude` globs are valid, that they include its entry file and that
+    /// none of the source files in the package are matched by both.
+    pub fn check_source_globs(&self) -> Result<(), SourceGlobError> {
+        for glob in self.include.iter().chain(&self.exclude) {
+            if let Err(error) = Pattern::new(glob) {
+                return Err(SourceGlobError::InvalidGlob {
+                    glob: glob.clone(),
+                    message: error.msg.to_string(),
+                });
+            }
+        }
+        if !self.is_source_file_included(&self.entry_path) {
+            return Err(SourceGlobError::EntryNotIncluded(self.entry_path.clone()));
+        }
+        if self.include.is_empty() || self.exclude.is_empty() {
+            return Ok(());
+        }
+
+        // The source files are those which are loaded alongside the entry file.
+        let source_dir = self.entry_path.parent().unwrap_or(&self.root_dir);
+        let source_files = crate::get_all_paths_in_dir(source_dir).unwrap_or_default();
+        match source_files.into_iter().find(|path| {
+            self.matches_any_glob(&self.include, path) && self.matches_any_glob(&self.exclude, path)
+        }) {
+            Some(path) => Err(SourceGlobError::IncludedAndExcluded(path)),
+            None => Ok(()),
+        }
+    }
+
+    fn matches_any_glob(&self, globs: &[String], path: &Path) -> bool {
+        let Ok(relative_path) = path.strip_prefix(&self.root_dir) else {
+            return false;
+        };
+        globs
+            .iter()
+            .filter_map(|glob| Pattern::new(glob).ok())
+            .any(|pattern| pattern.matches_path_with(relative_path, GLOB_MATCH_OPTIONS))
+    }
 }
@@ -22,6 +22,26 @@ pub struct Workspace {
     pub selected_package_index: Option<usize>,
     /// If we could not resolve the workspace we would inform the user we have assumed it (ie. from lsp file path given)
     pub is_assumed: bool,
+    /// Settings from the `[profile]` section of the workspace's root `Nargo.toml`
+    pub profile: Profile,
+    /// Problems found in the manifests while resolving the workspace which did not prevent it from being resolved,
+    /// left for the caller to report.
+    pub warnings: Vec<String>,
+}
+
+/// Defaults for command line options which may be set once in a `Nargo.toml` rather than on every invocation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    /// Whether warnings are allowed during compilation. If `Some(false)` then warnings are treated as errors,
+    /// unless they are silenced with `--silence-warnings`.
+    pub allow_warnings: Option<bool>,
+    /// The directory, relative to the workspace root, which proofs are written to and read from.
+    /// Defaults to [PROOFS_DIR] if not set.
+    pub proofs_dir: Option<PathBuf>,
+    /// The name of the backend used for proving and verification.
+    pub backend: Option<String>,
+    /// The features to enable when compiling the workspace's packages, in addition to those passed with `--features`.
+    pub features: Vec<String>,
 }
 
 impl Workspace {
@@ -36,7 +56,10 @@ impl Workspace {
     }
 
     pub fn proofs_directory_path(&self) -> PathBuf {
-        self.root_dir.join(PROOFS_DIR)
+        match &self.profile.proofs_dir {
+            Some(proofs_dir) => self.root_dir.join(proofs_dir),
+            None => self.root_dir.join(PROOFS_DIR),
+        }
     }
 
     pub fn target_directory_path(&self) -> PathBuf {
@@ -27,6 +27,31 @@ pub(crate) fn set_active_backend(backend_name: &str) {
         .expect("Could not write to active backend file");
 }
 
+/// Environment variable holding the name of the backend to use, which takes precedence over `Nargo.toml`.
+const BACKEND_ENV: &str = "NARGO_BACKEND";
+
+/// Returns the name of the backend to use.
+///
+/// In order of precedence, this is taken from the `--backend` flag, the [BACKEND_ENV] environment variable,
+/// the `backend` set in the `[profile]` of the workspace's `Nargo.toml`, and finally the active backend
+/// selected through `nargo backend use`.
+pub(crate) fn select_backend(
+    cli_backend: Option<String>,
+    manifest_backend: Option<String>,
+) -> String {
+    let env_backend = std::env::var(BACKEND_ENV).ok().filter(|backend| !backend.is_empty());
+    select_backend_from(cli_backend, env_backend, manifest_backend, get_active_backend)
+}
+
+fn select_backend_from(
+    cli_backend: Option<String>,
+    env_backend: Option<String>,
+    manifest_backend: Option<String>,
+    active_backend: impl FnOnce() -> String,
+) -> String {
+    cli_backend.or(env_backend).or(manifest_backend).unwrap_or_else(active_backend)
+}
+
 pub(crate) fn get_active_backend() -> String {
     let active_backend_file = active_backend_file_path();
 
@@ -37,3 +62,25 @@ pub(crate) fn get_active_backend() -> String {
 
     std::fs::read_to_string(active_backend_file).expect("Could not read active backend file")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::select_backend_from;
+
+    fn select(cli: Option<&str>, env: Option<&str>, manifest: Option<&str>) -> String {
+        select_backend_from(
+            cli.map(String::from),
+            env.map(String::from),
+            manifest.map(String::from),
+            || "active".to_string(),
+        )
+    }
+
+    #[test]
+    fn backend_selection_precedence() {
+        assert_eq!(select(Some("cli"), Some("env"), Some("manifest")), "cli");
+        assert_eq!(select(None, Some("env"), Some("manifest")), "env");
+        assert_eq!(select(None, None, Some("manifest")), "manifest");
+        assert_eq!(select(None, None, None), "active");
+    }
+}
@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use nargo::ops::compile_program;
+use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
+use nargo_toml::{get_package_manifest, PackageSelection};
+use noirc_abi::{diff::diff_abis, Abi};
+use noirc_driver::{file_manager_with_stdlib, CompileOptions};
+use noirc_frontend::graph::CrateName;
+
+use super::compile_cmd::{apply_profile, report_errors};
+use super::prove_cmd::single_binary_package;
+use super::{resolve_workspace, NargoConfig};
+use crate::errors::{CliError, FilesystemError};
+
+/// Compare the ABI of the program against a previously saved ABI
+///
+/// Changes to the public inputs of the program are reported as breaking, causing the command to fail.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct AbiDiffCommand {
+    /// Path to the saved ABI, either as a JSON ABI or as a compiled program artifact
+    old_abi: PathBuf,
+
+    /// The name of the package to compare, which is required when the workspace has several binary packages
+    #[clap(long)]
+    package: Option<CrateName>,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(args: AbiDiffCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let selection = args.package.map_or(PackageSelection::DefaultOrAll, PackageSelection::Selected);
+    let workspace = resolve_workspace(&config, &toml_path, selection)?;
+
+    let old_abi = read_abi_from_file(&config.program_dir.join(&args.old_abi))?;
+
+    let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
+    let binary_packages: Vec<_> =
+        workspace.into_iter().filter(|package| package.is_binary()).collect();
+    let package = single_binary_package(&binary_packages, "An ABI")?;
+
+    let compilation_result =
+        compile_program(&workspace_file_manager, &parsed_files, package, &compile_options, None);
+    let compiled_program = report_errors(
+        compilation_result,
+        &workspace_file_manager,
+        compile_options.deny_warnings,
+        compile_options.silence_warnings,
+    )?;
+
+    let changes = diff_abis(&old_abi, &compiled_program.abi);
+    if changes.is_empty() {
+        println!("[{}] ABI is unchanged", package.name);
+        return Ok(());
+    }
+
+    for change in &changes {
+        let kind = if change.is_breaking() { "breaking" } else { "compatible" };
+        println!("[{}] {kind}: {change}", package.name);
+    }
+
+    let breaking_changes = changes.iter().filter(|change| change.is_breaking()).count();
+    if breaking_changes != 0 {
+        return Err(CliError::BreakingAbiChanges {
+            package: package.name.to_string(),
+            count: breaking_changes,
+        });
+    }
+
+    Ok(())
+}
+
+/// Reads an ABI from a JSON file containing either the ABI itself or a program artifact written by `nargo compile`.
+pub(crate) fn read_abi_from_file(path: &Path) -> Result<Abi, FilesystemError> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|_| FilesystemError::PathNotValid(path.into()))?;
+    let mut json: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|err| FilesystemError::InvalidAbiFile(path.into(), err))?;
+    if let Some(abi) = json.get_mut("abi") {
+        json = abi.take();
+    }
+    serde_json::from_value(json).map_err(|err| FilesystemError::InvalidAbiFile(path.into(), err))
+}
@@ -5,22 +5,23 @@ use clap::Args;
 use fm::FileManager;
 use iter_extended::btree_map;
 use nargo::{
-    errors::CompileError, insert_all_files_for_workspace_into_file_manager, package::Package,
-    parse_all, prepare_package,
+    errors::CompileError, insert_all_files_for_workspace_into_file_manager,
+    ops::find_unconstrained_witnesses, package::Package, parse_all, prepare_package_with_features,
 };
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use nargo_toml::{check_publishable, get_package_manifest, validate_manifest, PackageSelection};
 use noirc_abi::{AbiParameter, AbiType, MAIN_RETURN_NAME};
 use noirc_driver::{
-    check_crate, compute_function_abi, file_manager_with_stdlib, CompileOptions,
-    NOIR_ARTIFACT_VERSION_STRING,
+    check_crate, compile_no_check, compute_function_abi, file_manager_with_stdlib, CompileOptions,
 };
+use noirc_errors::FileDiagnostic;
 use noirc_frontend::{
     graph::{CrateId, CrateName},
     hir::{Context, ParsedFiles},
 };
 
+use super::compile_cmd::apply_profile;
 use super::fs::write_to_file;
-use super::NargoConfig;
+use super::{resolve_workspace, NargoConfig};
 
 /// Checks the constraint system for errors
 #[derive(Debug, Clone, Args)]
@@ -33,6 +34,18 @@ pub(crate) struct CheckCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// Compile the program and warn about any witnesses which are assigned but never constrained
+    #[clap(long)]
+    unconstrained: bool,
+
+    /// Check that the package is ready to be published as a library before checking its constraint system
+    #[clap(long)]
+    publish: bool,
+
+    /// Only check that Nargo.toml is valid and that all dependencies resolve, without reading any Noir sources
+    #[clap(long, conflicts_with = "unconstrained")]
+    manifest_only: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -43,21 +56,35 @@ pub(crate) fn run(
     config: NargoConfig,
 ) -> Result<(), CliError> {
     let toml_path = get_package_manifest(&config.program_dir)?;
+    // Report every problem with the manifests before resolving dependencies, which stops at the first error.
+    validate_manifest(&toml_path)?;
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
     let selection = args.package.map_or(default_selection, PackageSelection::Selected);
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
-    )?;
+    let workspace = resolve_workspace(&config, &toml_path, selection)?;
+    if args.publish {
+        check_publishable(&workspace)?;
+    }
+    if args.manifest_only {
+        for package in &workspace {
+            println!("[{}] Manifest and dependencies successfully resolved!", package.name);
+        }
+        return Ok(());
+    }
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
 
     for package in &workspace {
-        check_package(&workspace_file_manager, &parsed_files, package, &args.compile_options)?;
+        check_package(
+            &workspace_file_manager,
+            &parsed_files,
+            package,
+            &compile_options,
+            args.unconstrained,
+        )?;
         println!("[{}] Constraint system successfully built!", package.name);
     }
     Ok(())
@@ -68,8 +95,14 @@ fn check_package(
     parsed_files: &ParsedFiles,
     package: &Package,
     compile_options: &CompileOptions,
+    check_unconstrained: bool,
 ) -> Result<(), CompileError> {
-    let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
+    let (mut context, crate_id) = prepare_package_with_features(
+        file_manager,
+        parsed_files,
+        package,
+        &compile_options.features,
+    );
     check_crate_and_report_errors(
         &mut context,
         crate_id,
@@ -100,6 +133,10 @@ fn check_package(
                 write_to_file(verifier_toml.as_bytes(), &path_to_verifier_input);
             }
 
+            if check_unconstrained {
+                check_unconstrained_witnesses(&mut context, crate_id, compile_options)?;
+            }
+
             Ok(())
         } else {
             Err(CompileError::MissingMainFunction(package.name.clone()))
@@ -107,6 +144,37 @@ fn check_package(
     }
 }
 
+/// Compiles the crate's `main` function and warns about any witnesses which are assigned but never constrained.
+///
+/// This assumes that the crate has already been checked for errors.
+fn check_unconstrained_witnesses(
+    context: &mut Context,
+    crate_id: CrateId,
+    compile_options: &CompileOptions,
+) -> Result<(), CompileError> {
+    let main = context.get_main_function(&crate_id).expect("crate should have a main function");
+    let result = compile_no_check(context, compile_options, main, None, false).map(|program| {
+        let mut warnings = Vec::new();
+        for unconstrained in find_unconstrained_witnesses(&program.circuit) {
+            match unconstrained.to_diagnostic(&program.debug) {
+                Some(warning) => warnings.push(warning),
+                None => eprintln!(
+                    "Warning: witness {} is assigned but never constrained",
+                    unconstrained.witness.witness_index()
+                ),
+            }
+        }
+        ((), warnings)
+    });
+
+    super::compile_cmd::report_errors(
+        result.map_err(|error| vec![FileDiagnostic::from(error)]),
+        &context.file_manager,
+        compile_options.deny_warnings,
+        compile_options.silence_warnings,
+    )
+}
+
 /// Generates the contents of a toml file with fields for each of the passed parameters.
 fn create_input_toml_template(
     parameters: Vec<AbiParameter>,
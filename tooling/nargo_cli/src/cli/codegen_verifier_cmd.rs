@@ -1,14 +1,14 @@
 use super::fs::{create_named_dir, write_to_file};
-use super::NargoConfig;
+use super::{resolve_workspace, NargoConfig};
 use crate::backends::Backend;
-use crate::cli::compile_cmd::report_errors;
+use crate::cli::compile_cmd::{apply_profile, report_errors};
 use crate::errors::CliError;
 
 use clap::Args;
 use nargo::ops::compile_program;
 use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::{file_manager_with_stdlib, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use nargo_toml::{get_package_manifest, PackageSelection};
+use noirc_driver::{file_manager_with_stdlib, CompileOptions};
 use noirc_frontend::graph::CrateName;
 
 /// Generates a Solidity verifier smart contract for the program
@@ -35,16 +35,13 @@ pub(crate) fn run(
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
     let selection = args.package.map_or(default_selection, PackageSelection::Selected);
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
-    )?;
+    let workspace = resolve_workspace(&config, &toml_path, selection)?;
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
 
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
     let expression_width = backend.get_backend_info()?;
     let binary_packages = workspace.into_iter().filter(|package| package.is_binary());
     for package in binary_packages {
@@ -52,15 +49,15 @@ pub(crate) fn run(
             &workspace_file_manager,
             &parsed_files,
             package,
-            &args.compile_options,
+            &compile_options,
             None,
         );
 
         let program = report_errors(
             compilation_result,
             &workspace_file_manager,
-            args.compile_options.deny_warnings,
-            args.compile_options.silence_warnings,
+            compile_options.deny_warnings,
+            compile_options.silence_warnings,
         )?;
 
         let program = nargo::ops::transform_program(program, expression_width);
@@ -1,28 +1,42 @@
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
-use fm::FileManager;
+use acvm::acir::circuit::{Circuit, ExpressionWidth};
+use fm::{FileId, FileManager};
+use nargo::artifacts::bundle::ProgramBundle;
 use nargo::artifacts::program::ProgramArtifact;
 use nargo::errors::CompileError;
-use nargo::ops::{compile_contract, compile_program};
-use nargo::package::Package;
-use nargo::workspace::Workspace;
-use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
+use nargo::ops::{compile_contract, compile_program, compile_program_entrypoints};
+use nargo::package::{Dependency, Package};
+use nargo::workspace::{Profile, Workspace};
+use nargo::{
+    get_all_files_in_workspace, insert_all_files_for_workspace_into_file_manager, parse_all,
+};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::file_manager_with_stdlib;
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
-use noirc_driver::{CompilationResult, CompileOptions, CompiledContract, CompiledProgram};
+use noirc_driver::{
+    CompilationResult, CompilationTimings, CompileOptions, CompiledContract, CompiledProgram,
+    DebugFile,
+};
 
 use noirc_frontend::graph::CrateName;
 
-use clap::Args;
+use clap::{Args, ValueEnum};
 use noirc_frontend::hir::ParsedFiles;
+use prettytable::{row, table};
 
 use crate::backends::Backend;
 use crate::errors::CliError;
 
-use super::fs::program::only_acir;
-use super::fs::program::{read_program_from_file, save_contract_to_file, save_program_to_file};
-use super::NargoConfig;
+use super::fs::fingerprint::CompilationFingerprint;
+use super::fs::program::{
+    acir_file, acir_json_file, brillig_file, bundle_file, contract_file, entrypoint_file,
+    only_acir_file, program_file,
+};
+use super::fs::{program::read_program_from_file, PendingFile};
+use super::{resolve_workspace, NargoConfig};
 use rayon::prelude::*;
 
 /// Compile the program and its secret execution trace into ACIR format
@@ -36,41 +50,148 @@ pub(crate) struct CompileCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// Print the time spent in each phase of compilation
+    #[clap(long)]
+    time_report: bool,
+
+    /// Compile `main` and every public function of each binary package into its own circuit
+    ///
+    /// Cannot be combined with `--time-report`, as compilation timings are only collected for `main`.
+    #[clap(long, conflicts_with = "time_report")]
+    all_functions: bool,
+
+    /// Report the artifacts which would be written, and their sizes, without writing them to disk
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Strip environment-dependent data, such as absolute source paths, so that artifacts are reproducible
+    #[clap(long)]
+    deterministic: bool,
+
+    /// Fail if any compiled circuit has more than this many opcodes
+    #[clap(long, value_name = "N")]
+    max_gates: Option<usize>,
+
+    /// Recompile whenever a source file, manifest or prover input of the workspace changes, until interrupted
+    #[clap(long, conflicts_with = "dry_run")]
+    watch: bool,
+
+    /// Also write each circuit as human-readable JSON to `<name>.acir.json`, for inspecting its opcodes
+    #[clap(long)]
+    emit_acir_json: bool,
+
+    /// Also write the circuit, ABI, compiler version and backend name of each binary package to a single versioned
+    /// `<name>.bundle.json` file, which can be executed, proven and verified without the package's sources
+    #[clap(long, conflicts_with = "all_functions")]
+    bundle: bool,
+
+    /// The bytecode to emit for each circuit
+    #[clap(long, value_enum, default_value_t = CompileTarget::Acir, conflicts_with = "only_acir")]
+    target: CompileTarget,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
 
+/// The bytecode which `nargo compile` emits
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CompileTarget {
+    /// The build artifact containing the constrained ACIR circuit
+    Acir,
+    /// The unconstrained Brillig bytecode of the program, written to `<name>.brillig.json`.
+    /// Every function is compiled as unconstrained so that the whole program is emitted as Brillig
+    Brillig,
+}
+
+/// How often the workspace's files are checked for changes when compiling with `--watch`.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 pub(crate) fn run(
     backend: &Backend,
     args: CompileCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
-    let toml_path = get_package_manifest(&config.program_dir)?;
+    if !args.watch {
+        return compile(backend, &args, &config);
+    }
+
+    loop {
+        // The files are snapshotted before compiling so that any saved while the compiler runs trigger a rebuild.
+        let files = watched_files(&args, &config.program_dir);
+        let snapshot = modification_times(&files);
+        if let Err(error) = compile(backend, &args, &config) {
+            eprintln!("{error}");
+        }
+        println!("Watching for changes...");
+        wait_for_changes(&files, snapshot);
+    }
+}
+
+fn package_selection(args: &CompileCommand) -> PackageSelection {
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
-    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    args.package.clone().map_or(default_selection, PackageSelection::Selected)
+}
 
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
-    )?;
+fn compile(backend: &Backend, args: &CompileCommand, config: &NargoConfig) -> Result<(), CliError> {
+    let resolution_start = Instant::now();
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let workspace = resolve_workspace(config, &toml_path, package_selection(args))?;
     let circuit_dir = workspace.target_directory_path();
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let resolution_time = resolution_start.elapsed();
+
+    let mut compile_options = apply_profile(&args.compile_options, &workspace.profile);
+    if args.target == CompileTarget::Brillig {
+        compile_options.force_brillig = true;
+    }
+    let expression_width =
+        compile_options.expression_width.unwrap_or_else(|| backend.get_backend_info_or_default());
+
+    // If nothing has changed since the last compilation then its artifacts are still up to date.
+    // Any command line option which asks for output from the compiler always results in a full compilation.
+    let can_skip_compilation = !(args.dry_run
+        || args.time_report
+        || args.all_functions
+        || args.emit_acir_json
+        || args.bundle
+        || args.max_gates.is_some()
+        || args.target != CompileTarget::Acir
+        || requests_compiler_output(&compile_options));
+    let fingerprint = can_skip_compilation
+        .then(|| {
+            let configuration = compilation_configuration(
+                &workspace,
+                &compile_options,
+                expression_width,
+                args.deterministic,
+            );
+            CompilationFingerprint::new(configuration, &compilation_inputs(&workspace))
+        })
+        .flatten();
+    if fingerprint.as_ref().is_some_and(|fingerprint| fingerprint.is_up_to_date(&circuit_dir)) {
+        return Ok(());
+    }
+
+    let parsing_start = Instant::now();
     let parsed_files = parse_all(&workspace_file_manager);
+    let parsing_time = parsing_start.elapsed();
+
+    if args.all_functions {
+        return compile_all_entrypoints(
+            &workspace_file_manager,
+            &parsed_files,
+            &workspace,
+            &compile_options,
+            expression_width,
+            args,
+        );
+    }
 
-    let expression_width = args
-        .compile_options
-        .expression_width
-        .unwrap_or_else(|| backend.get_backend_info_or_default());
-    let (compiled_program, compiled_contracts) = compile_workspace(
-        &workspace_file_manager,
-        &parsed_files,
-        &workspace,
-        &args.compile_options,
-    )?;
+    let (compiled_program, compiled_contracts) =
+        compile_workspace(&workspace_file_manager, &parsed_files, &workspace, &compile_options)?;
 
     let (binary_packages, contract_packages): (Vec<_>, Vec<_>) = workspace
         .into_iter()
@@ -78,20 +199,228 @@ pub(crate) fn run(
         .cloned()
         .partition(|package| package.is_binary());
 
+    if args.time_report {
+        let program_timings = binary_packages
+            .iter()
+            .zip(&compiled_program)
+            .map(|(package, program)| (package.name.to_string(), program.timings));
+        let contract_timings =
+            contract_packages.iter().zip(&compiled_contracts).map(|(package, contract)| {
+                (format!("{}-{}", package.name, contract.name), contract.timings)
+            });
+        print_time_report(resolution_time, parsing_time, program_timings.chain(contract_timings));
+    }
+
     // Save build artifacts to disk.
-    let only_acir = args.compile_options.only_acir;
+    let only_acir = compile_options.only_acir;
+    let mut artifacts = Vec::new();
     for (package, program) in binary_packages.into_iter().zip(compiled_program) {
-        let program = nargo::ops::transform_program(program, expression_width);
-        save_program(program.clone(), &package, &workspace.target_directory_path(), only_acir);
+        let mut program = nargo::ops::transform_program(program, expression_width);
+        check_gate_budget(&package.name.to_string(), &program.circuit, args.max_gates)?;
+        if args.deterministic {
+            strip_source_locations(&mut program.file_map, &workspace);
+        }
+        if args.emit_acir_json {
+            acir_json_file(&program.circuit, &package.name.to_string(), &circuit_dir)
+                .write_or_report(args.dry_run);
+        }
+        if args.bundle {
+            let bundle = ProgramBundle::new(program.clone(), backend.name().to_string());
+            bundle_file(&bundle, &package.name, &circuit_dir).write_or_report(args.dry_run);
+        }
+        let artifact = match args.target {
+            CompileTarget::Acir => {
+                program_artifact_file(program, &package, &circuit_dir, only_acir)
+            }
+            CompileTarget::Brillig => {
+                brillig_file(&program.circuit, &package.name.to_string(), &circuit_dir)
+            }
+        };
+        artifact.write_or_report(args.dry_run);
+        artifacts.push(artifact.path);
     }
     for (package, contract) in contract_packages.into_iter().zip(compiled_contracts) {
-        let contract = nargo::ops::transform_contract(contract, expression_width);
-        save_contract(contract, &package, &circuit_dir);
+        let mut contract = nargo::ops::transform_contract(contract, expression_width);
+        for function in &contract.functions {
+            let circuit_name = format!("{}-{}-{}", package.name, contract.name, function.name);
+            check_gate_budget(&circuit_name, &function.bytecode, args.max_gates)?;
+        }
+        if args.deterministic {
+            strip_source_locations(&mut contract.file_map, &workspace);
+        }
+        if args.emit_acir_json {
+            for function in &contract.functions {
+                let artifact_name = format!("{}-{}-{}", package.name, contract.name, function.name);
+                acir_json_file(&function.bytecode, &artifact_name, &circuit_dir)
+                    .write_or_report(args.dry_run);
+            }
+        }
+        if args.target == CompileTarget::Brillig {
+            for function in &contract.functions {
+                let artifact_name = format!("{}-{}-{}", package.name, contract.name, function.name);
+                let artifact = brillig_file(&function.bytecode, &artifact_name, &circuit_dir);
+                artifact.write_or_report(args.dry_run);
+                artifacts.push(artifact.path);
+            }
+            continue;
+        }
+        let artifact = contract_artifact_file(contract, &package, &circuit_dir);
+        artifact.write_or_report(args.dry_run);
+        artifacts.push(artifact.path);
+    }
+
+    if let Some(fingerprint) = fingerprint {
+        fingerprint.write(&circuit_dir, artifacts);
     }
 
     Ok(())
 }
 
+/// Blocks until one of `files` has been created, modified or deleted since `initial_snapshot` was taken.
+///
+/// Rapid successive changes, such as an editor saving several files, are debounced by waiting until the files have
+/// been left untouched for a full polling interval.
+fn wait_for_changes(files: &BTreeSet<PathBuf>, initial_snapshot: Vec<Option<SystemTime>>) {
+    let mut latest_snapshot = initial_snapshot.clone();
+    loop {
+        std::thread::sleep(WATCH_POLL_INTERVAL);
+        let snapshot = modification_times(files);
+        if snapshot == latest_snapshot && snapshot != initial_snapshot {
+            return;
+        }
+        latest_snapshot = snapshot;
+    }
+}
+
+/// Returns the files which `--watch` waits on: those which the compilation depends upon and each package's prover
+/// inputs, along with the directories containing them so that the creation of new source files is noticed.
+///
+/// These are listed once per rebuild, as the workspace only needs resolving again once something has changed.
+fn watched_files(args: &CompileCommand, program_dir: &Path) -> BTreeSet<PathBuf> {
+    let mut files = BTreeSet::new();
+    if let Ok(toml_path) = get_package_manifest(program_dir) {
+        if let Ok(workspace) =
+            resolve_workspace_from_toml(&toml_path, package_selection(args), None)
+        {
+            files.extend(compilation_inputs(&workspace));
+            files.extend(workspace.into_iter().map(|package| package.prover_input_path()));
+        }
+        files.insert(toml_path);
+    }
+
+    let directories: Vec<_> =
+        files.iter().filter_map(|file| file.parent()).map(Path::to_path_buf).collect();
+    files.extend(directories);
+    files
+}
+
+/// Returns the modification time of each of `files`, or `None` for those which do not exist.
+fn modification_times(files: &BTreeSet<PathBuf>) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .collect()
+}
+
+/// Checks that `circuit` has no more opcodes than the `--max-gates` budget, if one was set.
+fn check_gate_budget(
+    circuit_name: &str,
+    circuit: &Circuit,
+    max_gates: Option<usize>,
+) -> Result<(), CliError> {
+    let opcode_count = circuit.opcodes.len();
+    match max_gates {
+        Some(max_gates) if opcode_count > max_gates => Err(CliError::GateBudgetExceeded {
+            circuit: circuit_name.to_string(),
+            opcode_count,
+            max_gates,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Returns whether the compile options ask for the compiler to run, or for output other than the compiled artifacts.
+fn requests_compiler_output(compile_options: &CompileOptions) -> bool {
+    compile_options.force_compile
+        || compile_options.show_ssa
+        || compile_options.show_brillig
+        || compile_options.print_acir
+        || compile_options.show_monomorphized
+}
+
+/// Describes everything other than the contents of the workspace's files which affects the compiled artifacts.
+fn compilation_configuration(
+    workspace: &Workspace,
+    compile_options: &CompileOptions,
+    expression_width: ExpressionWidth,
+    deterministic: bool,
+) -> String {
+    let packages: Vec<_> = workspace
+        .into_iter()
+        .map(|package| format!("{}:{}", package.name, package.entry_path.display()))
+        .collect();
+    format!(
+        "{NOIR_ARTIFACT_VERSION_STRING} {compile_options:?} {expression_width:?} {deterministic} {packages:?}"
+    )
+}
+
+/// Returns the selected packages of the workspace along with all of their dependencies, each only once.
+fn packages_with_dependencies(workspace: &Workspace) -> Vec<&Package> {
+    let mut root_dirs = BTreeSet::new();
+    let mut packages = Vec::new();
+    let mut unvisited: Vec<&Package> = workspace.into_iter().collect();
+    while let Some(package) = unvisited.pop() {
+        if root_dirs.insert(&package.root_dir) {
+            packages.push(package);
+            unvisited.extend(
+                package.dependencies.values().map(
+                    |(Dependency::Local { package } | Dependency::Remote { package })| package,
+                ),
+            );
+        }
+    }
+    packages
+}
+
+/// Returns the source files and manifests of the selected packages of the workspace and of their dependencies.
+fn compilation_inputs(workspace: &Workspace) -> Vec<PathBuf> {
+    let mut manifests = BTreeSet::from([workspace.root_dir.join("Nargo.toml")]);
+    manifests.extend(
+        packages_with_dependencies(workspace)
+            .into_iter()
+            .map(|package| package.root_dir.join("Nargo.toml")),
+    );
+
+    let mut inputs = get_all_files_in_workspace(workspace);
+    inputs.extend(manifests);
+    inputs
+}
+
+/// Rewrites the absolute source paths in `file_map` so that artifacts do not depend on where the workspace and its
+/// dependencies are located on disk.
+///
+/// Files within the workspace are made relative to its root. Files of dependencies outside of the workspace, such as
+/// git dependencies, are made relative to the root of their package and prefixed with the package's name.
+fn strip_source_locations(file_map: &mut BTreeMap<FileId, DebugFile>, workspace: &Workspace) {
+    let packages = packages_with_dependencies(workspace);
+    for file in file_map.values_mut() {
+        if let Ok(path) = file.path.strip_prefix(&workspace.root_dir) {
+            file.path = path.to_path_buf();
+            continue;
+        }
+
+        // Dependencies may be nested within each other, in which case the file belongs to the innermost one.
+        let package = packages
+            .iter()
+            .filter(|package| file.path.starts_with(&package.root_dir))
+            .max_by_key(|package| package.root_dir.components().count());
+        if let Some(package) = package {
+            let path = file.path.strip_prefix(&package.root_dir).expect("path is within package");
+            file.path = Path::new(&package.name.to_string()).join(path);
+        }
+    }
+}
+
 pub(super) fn compile_workspace(
     file_manager: &FileManager,
     parsed_files: &ParsedFiles,
@@ -150,27 +479,125 @@ pub(super) fn compile_workspace(
     Ok((compiled_programs, compiled_contracts))
 }
 
-pub(super) fn save_program(
+/// Compiles each entrypoint of the workspace's binary packages and saves one artifact per function.
+fn compile_all_entrypoints(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    workspace: &Workspace,
+    compile_options: &CompileOptions,
+    expression_width: ExpressionWidth,
+    args: &CompileCommand,
+) -> Result<(), CliError> {
+    let binary_packages: Vec<_> =
+        workspace.into_iter().filter(|package| package.is_binary()).collect();
+
+    let compilation_results: Vec<_> = binary_packages
+        .par_iter()
+        .map(|package| {
+            compile_program_entrypoints(file_manager, parsed_files, package, compile_options)
+        })
+        .collect();
+
+    let circuit_dir = workspace.target_directory_path();
+    for (package, compilation_result) in binary_packages.into_iter().zip(compilation_results) {
+        let programs = report_errors(
+            compilation_result,
+            file_manager,
+            compile_options.deny_warnings,
+            compile_options.silence_warnings,
+        )?;
+
+        for (function_name, program) in programs {
+            let mut program = nargo::ops::transform_program(program, expression_width);
+            let circuit_name = format!("{}-{function_name}", package.name);
+            check_gate_budget(&circuit_name, &program.circuit, args.max_gates)?;
+            if args.deterministic {
+                strip_source_locations(&mut program.file_map, workspace);
+            }
+            if args.emit_acir_json {
+                let artifact_name = format!("{}-{function_name}", package.name);
+                acir_json_file(&program.circuit, &artifact_name, &circuit_dir)
+                    .write_or_report(args.dry_run);
+            }
+            if args.target == CompileTarget::Brillig {
+                let artifact_name = format!("{}-{function_name}", package.name);
+                brillig_file(&program.circuit, &artifact_name, &circuit_dir)
+                    .write_or_report(args.dry_run);
+                continue;
+            }
+            let program_artifact = ProgramArtifact::from(program);
+            let artifact_file = if compile_options.only_acir {
+                let file_name = format!("{}-{function_name}.acir.gz", package.name);
+                acir_file(&program_artifact, &file_name, &circuit_dir)
+            } else {
+                entrypoint_file(&program_artifact, &package.name, &function_name, &circuit_dir)
+            };
+            artifact_file.write_or_report(args.dry_run);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_time_report(
+    resolution_time: Duration,
+    parsing_time: Duration,
+    package_timings: impl Iterator<Item = (String, CompilationTimings)>,
+) {
+    println!("Resolved workspace in {resolution_time:?}");
+    println!("Parsed workspace in {parsing_time:?}");
+
+    let mut timings_table =
+        table!([Fm->"Package", Fm->"Frontend", Fm->"SSA Generation", Fm->"ACIR Generation"]);
+    for (name, timings) in package_timings {
+        timings_table.add_row(row![
+            Fm->name,
+            Fc->format!("{:?}", timings.frontend),
+            Fc->format!("{:?}", timings.ssa_generation),
+            Fc->format!("{:?}", timings.acir_generation),
+        ]);
+    }
+    timings_table.printstd();
+}
+
+fn program_artifact_file(
     program: CompiledProgram,
     package: &Package,
     circuit_dir: &Path,
     only_acir_opt: bool,
-) {
-    let program_artifact = ProgramArtifact::from(program.clone());
+) -> PendingFile {
+    let program_artifact = ProgramArtifact::from(program);
     if only_acir_opt {
-        only_acir(&program_artifact, circuit_dir);
+        only_acir_file(&program_artifact, circuit_dir)
     } else {
-        save_program_to_file(&program_artifact, &package.name, circuit_dir);
+        program_file(&program_artifact, &package.name, circuit_dir)
     }
 }
 
-fn save_contract(contract: CompiledContract, package: &Package, circuit_dir: &Path) {
+fn contract_artifact_file(
+    contract: CompiledContract,
+    package: &Package,
+    circuit_dir: &Path,
+) -> PendingFile {
     let contract_name = contract.name.clone();
-    save_contract_to_file(
-        &contract.into(),
-        &format!("{}-{}", package.name, contract_name),
-        circuit_dir,
-    );
+    contract_file(&contract.into(), &format!("{}-{}", package.name, contract_name), circuit_dir)
+}
+
+/// Applies the defaults from the workspace's `[profile]` to the compile options.
+///
+/// Flags passed on the command line take precedence over the manifest. In particular `--silence-warnings` overrides
+/// `allow-warnings = false`, so that warnings can be hidden while iterating on a package which denies them.
+pub(crate) fn apply_profile(compile_options: &CompileOptions, profile: &Profile) -> CompileOptions {
+    let mut compile_options = compile_options.clone();
+    if !compile_options.deny_warnings && !compile_options.silence_warnings {
+        compile_options.deny_warnings = profile.allow_warnings == Some(false);
+    }
+    for feature in &profile.features {
+        if !compile_options.features.contains(feature) {
+            compile_options.features.push(feature.clone());
+        }
+    }
+    compile_options
 }
 
 /// Helper function for reporting any errors in a `CompilationResult<T>`
@@ -199,3 +626,145 @@ pub(crate) fn report_errors<T>(
 
     Ok(t)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use acvm::acir::{
+        brillig::Opcode as BrilligOpcode,
+        circuit::{brillig::Brillig, Circuit, Opcode},
+        native_types::{Expression, Witness},
+    };
+    use nargo::{
+        artifacts::program::ProgramArtifact, insert_all_files_for_workspace_into_file_manager,
+        parse_all, workspace::Profile,
+    };
+    use nargo_toml::{resolve_workspace_from_toml, PackageSelection};
+    use noirc_driver::{file_manager_with_stdlib, CompileOptions};
+
+    use super::{apply_profile, check_gate_budget, compile_workspace, strip_source_locations};
+    use crate::cli::fs::program::{acir_json_file, brillig_file};
+    use crate::errors::CliError;
+
+    #[test]
+    fn profile_allow_warnings_is_overridden_by_flags() {
+        let deny_profile = Profile { allow_warnings: Some(false), ..Profile::default() };
+        let allow_profile = Profile { allow_warnings: Some(true), ..Profile::default() };
+        let no_flags = CompileOptions::default();
+        let deny_flag = CompileOptions { deny_warnings: true, ..CompileOptions::default() };
+        let silence_flag = CompileOptions { silence_warnings: true, ..CompileOptions::default() };
+
+        // Without any flags, the manifest decides whether warnings are allowed.
+        assert!(!apply_profile(&no_flags, &Profile::default()).deny_warnings);
+        assert!(!apply_profile(&no_flags, &allow_profile).deny_warnings);
+        assert!(apply_profile(&no_flags, &deny_profile).deny_warnings);
+
+        // `--deny-warnings` wins over `allow-warnings = true`.
+        assert!(apply_profile(&deny_flag, &allow_profile).deny_warnings);
+
+        // `--silence-warnings` wins over `allow-warnings = false`.
+        let silenced = apply_profile(&silence_flag, &deny_profile);
+        assert!(!silenced.deny_warnings);
+        assert!(silenced.silence_warnings);
+    }
+
+    #[test]
+    fn deterministic_artifacts_do_not_depend_on_the_workspace_location() {
+        let compile_artifact = |root_dir: &Path| {
+            std::fs::create_dir_all(root_dir.join("src")).unwrap();
+            std::fs::write(
+                root_dir.join("Nargo.toml"),
+                "[package]\nname = \"foo\"\ntype = \"bin\"",
+            )
+            .unwrap();
+            std::fs::write(
+                root_dir.join("src").join("main.nr"),
+                "fn main(x: Field, y: pub Field) { assert(x != y, \"equal\"); }",
+            )
+            .unwrap();
+
+            let workspace = resolve_workspace_from_toml(
+                &root_dir.join("Nargo.toml"),
+                PackageSelection::All,
+                None,
+            )
+            .unwrap();
+            let mut file_manager = file_manager_with_stdlib(&workspace.root_dir);
+            insert_all_files_for_workspace_into_file_manager(&workspace, &mut file_manager);
+            let parsed_files = parse_all(&file_manager);
+            let (mut programs, _) = compile_workspace(
+                &file_manager,
+                &parsed_files,
+                &workspace,
+                &CompileOptions::default(),
+            )
+            .unwrap();
+
+            let mut program = programs.pop().unwrap();
+            strip_source_locations(&mut program.file_map, &workspace);
+            assert!(program.file_map.values().all(|file| file.path.is_relative()));
+            serde_json::to_vec(&ProgramArtifact::from(program)).unwrap()
+        };
+
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        assert_eq!(
+            compile_artifact(&first.path().join("foo")),
+            compile_artifact(&second.path().join("nested").join("foo"))
+        );
+    }
+
+    #[test]
+    fn acir_json_renders_each_opcode_by_kind() {
+        let circuit = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![Opcode::AssertZero(Expression::from(Witness(1)))],
+            ..Circuit::default()
+        };
+        let file = acir_json_file(&circuit, "foo", "target");
+        assert_eq!(file.path, Path::new("target").join("foo.acir.json"));
+
+        let json: serde_json::Value = serde_json::from_slice(&file.contents).unwrap();
+        let opcodes = json["opcodes"].as_array().unwrap();
+        assert_eq!(opcodes.len(), 1);
+        assert!(opcodes[0].get("AssertZero").is_some());
+    }
+
+    #[test]
+    fn brillig_target_emits_only_brillig_bytecode() {
+        let brillig = Brillig {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            bytecode: vec![BrilligOpcode::Trap],
+            predicate: None,
+        };
+        let circuit = Circuit {
+            opcodes: vec![
+                Opcode::AssertZero(Expression::from(Witness(1))),
+                Opcode::Brillig(brillig),
+            ],
+            ..Circuit::default()
+        };
+        let file = brillig_file(&circuit, "foo", "target");
+        assert_eq!(file.path, Path::new("target").join("foo.brillig.json"));
+
+        let json: serde_json::Value = serde_json::from_slice(&file.contents).unwrap();
+        assert_eq!(json, serde_json::json!([["Trap"]]));
+    }
+
+    #[test]
+    fn rejects_circuits_exceeding_the_gate_budget() {
+        let circuit = Circuit {
+            opcodes: vec![Opcode::AssertZero(Expression::from(Witness(1))); 3],
+            ..Circuit::default()
+        };
+
+        assert!(check_gate_budget("foo", &circuit, None).is_ok());
+        assert!(check_gate_budget("foo", &circuit, Some(3)).is_ok());
+        assert!(matches!(
+            check_gate_budget("foo", &circuit, Some(2)),
+            Err(CliError::GateBudgetExceeded { opcode_count: 3, max_gates: 2, .. })
+        ));
+    }
+}
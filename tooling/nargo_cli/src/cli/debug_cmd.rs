@@ -12,19 +12,20 @@ use nargo::ops::{compile_program, compile_program_with_debug_instrumenter};
 use nargo::package::Package;
 use nargo::workspace::Workspace;
 use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use nargo_toml::{get_package_manifest, PackageSelection};
 use noirc_abi::input_parser::{Format, InputValue};
 use noirc_abi::InputMap;
-use noirc_driver::{
-    file_manager_with_stdlib, CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
-};
+use noirc_driver::{file_manager_with_stdlib, CompileOptions, CompiledProgram};
 use noirc_frontend::debug::DebugInstrumenter;
 use noirc_frontend::graph::CrateName;
 use noirc_frontend::hir::ParsedFiles;
 
-use super::compile_cmd::report_errors;
-use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
-use super::NargoConfig;
+use super::compile_cmd::{apply_profile, report_errors};
+use super::fs::{
+    inputs::read_inputs_from_file,
+    witness::{save_witness_to_dir, WitnessFormat},
+};
+use super::{resolve_workspace, NargoConfig};
 use crate::backends::Backend;
 use crate::errors::CliError;
 
@@ -64,16 +65,11 @@ pub(crate) fn run(
 
     let toml_path = get_package_manifest(&config.program_dir)?;
     let selection = args.package.map_or(PackageSelection::DefaultOrAll, PackageSelection::Selected);
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
-    )?;
+    let workspace = resolve_workspace(&config, &toml_path, selection)?;
     let target_dir = &workspace.target_directory_path();
-    let expression_width = args
-        .compile_options
-        .expression_width
-        .unwrap_or_else(|| backend.get_backend_info_or_default());
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
+    let expression_width =
+        compile_options.expression_width.unwrap_or_else(|| backend.get_backend_info_or_default());
 
     let Some(package) = workspace.into_iter().find(|p| p.is_binary()) else {
         println!(
@@ -87,7 +83,7 @@ pub(crate) fn run(
         package,
         acir_mode,
         skip_instrumentation,
-        args.compile_options.clone(),
+        compile_options,
     )?;
 
     let compiled_program = nargo::ops::transform_program(compiled_program, expression_width);
@@ -178,18 +174,28 @@ fn run_async(
 
     runtime.block_on(async {
         println!("[{}] Starting debugger", package.name);
+        let return_type = program.abi.return_type.clone();
         let (return_value, solved_witness) =
             debug_program_and_decode(program, package, prover_name)?;
 
         if let Some(solved_witness) = solved_witness {
             println!("[{}] Circuit witness successfully solved", package.name);
 
-            if let Some(return_value) = return_value {
-                println!("[{}] Circuit output: {return_value:?}", package.name);
+            if let (Some(return_value), Some(return_type)) = (return_value, return_type) {
+                println!(
+                    "[{}] Circuit output: {}",
+                    package.name,
+                    return_value.display(&return_type.abi_type)
+                );
             }
 
             if let Some(witness_name) = witness_name {
-                let witness_path = save_witness_to_dir(solved_witness, witness_name, target_dir)?;
+                let witness_path = save_witness_to_dir(
+                    solved_witness,
+                    witness_name,
+                    target_dir,
+                    WitnessFormat::Binary,
+                )?;
 
                 println!("[{}] Witness saved to {}", package.name, witness_path.display());
             }
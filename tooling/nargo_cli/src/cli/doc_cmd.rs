@@ -0,0 +1,491 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use fm::FileManager;
+use nargo::{
+    insert_all_files_for_workspace_into_file_manager, parse_all, prepare_package_with_features,
+};
+use nargo_toml::{get_package_manifest, PackageSelection};
+use noirc_driver::{file_manager_with_stdlib, CompileOptions};
+use noirc_frontend::{
+    graph::CrateName,
+    hir::{
+        def_map::{CrateDefMap, LocalModuleId},
+        ParsedFiles,
+    },
+    lexer::Lexer,
+    parser::{Item, ItemKind, ParsedModule},
+    token::{DocStyle, SpannedToken, Token},
+    FunctionVisibility,
+};
+
+use super::check_cmd::check_crate_and_report_errors;
+use super::compile_cmd::apply_profile;
+use super::fs::PendingFile;
+use super::{resolve_workspace, NargoConfig};
+use crate::errors::CliError;
+
+/// Generate Markdown documentation from the doc comments on a package's public items
+#[derive(Debug, Clone, Args)]
+pub(crate) struct DocCommand {
+    /// The name of the package to document
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Document all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(args: DocCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace(&config, &toml_path, selection)?;
+
+    let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
+    let doc_dir = workspace.target_directory_path().join("doc");
+    for package in &workspace {
+        let (mut context, crate_id) = prepare_package_with_features(
+            &workspace_file_manager,
+            &parsed_files,
+            package,
+            &compile_options.features,
+        );
+        check_crate_and_report_errors(
+            &mut context,
+            crate_id,
+            compile_options.deny_warnings,
+            compile_options.disable_macros,
+            compile_options.silence_warnings,
+        )?;
+
+        let def_map = context.def_map(&crate_id).expect("checked crate should have a def map");
+        let modules = document_crate(&workspace_file_manager, &parsed_files, def_map);
+
+        let package_doc_dir = doc_dir.join(package.name.to_string());
+        for (path, module) in &modules {
+            let contents = render_module(&package.name.to_string(), path, module).into_bytes();
+            PendingFile { path: module_doc_path(&package_doc_dir, path), contents }.write();
+        }
+        println!("[{}] Documentation written to {}", package.name, package_doc_dir.display());
+    }
+
+    Ok(())
+}
+
+/// The documentation of a single item, e.g. a function or struct.
+#[derive(Debug, Default)]
+struct ItemDocs {
+    /// The item's declaration as written in the source, without its body.
+    signature: String,
+    docs: String,
+}
+
+/// The documentation of an `impl` block and its public methods.
+#[derive(Debug, Default)]
+struct ImplDocs {
+    header: ItemDocs,
+    methods: Vec<ItemDocs>,
+}
+
+/// The documented items declared directly within a module.
+#[derive(Debug, Default)]
+struct ModuleDocs {
+    docs: String,
+    submodules: Vec<String>,
+    functions: Vec<ItemDocs>,
+    structs: Vec<ItemDocs>,
+    traits: Vec<ItemDocs>,
+    type_aliases: Vec<ItemDocs>,
+    impls: Vec<ImplDocs>,
+}
+
+/// Documents every module of a crate which has been checked, keyed by each module's path from the crate root.
+fn document_crate(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    def_map: &CrateDefMap,
+) -> BTreeMap<Vec<String>, ModuleDocs> {
+    let mut documenter =
+        CrateDocumenter { file_manager, parsed_files, def_map, modules: BTreeMap::new() };
+    documenter.document_file(def_map.root(), Vec::new(), String::new());
+    documenter.modules
+}
+
+struct CrateDocumenter<'a> {
+    file_manager: &'a FileManager,
+    parsed_files: &'a ParsedFiles,
+    def_map: &'a CrateDefMap,
+    modules: BTreeMap<Vec<String>, ModuleDocs>,
+}
+
+impl<'a> CrateDocumenter<'a> {
+    /// Documents a module which is defined by a whole file, e.g. through `mod foo;`.
+    fn document_file(&mut self, module_id: LocalModuleId, path: Vec<String>, outer_docs: String) {
+        let file_id = self.def_map[module_id].location.file;
+        let source = self.file_manager.fetch_file(file_id).expect("module file should be loaded");
+        let parsed_files = self.parsed_files;
+        let (parsed_module, _) = &parsed_files[&file_id];
+
+        let extractor = DocExtractor::new(source);
+        let first_item_start = parsed_module.items.first().map(|item| item.span.start());
+        let inner_docs = extractor.inner_docs(first_item_start.unwrap_or(u32::MAX));
+        let docs = [outer_docs, inner_docs]
+            .into_iter()
+            .filter(|docs| !docs.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        self.document_module(&extractor, parsed_module, module_id, path, docs);
+    }
+
+    fn document_module(
+        &mut self,
+        extractor: &DocExtractor,
+        parsed_module: &ParsedModule,
+        module_id: LocalModuleId,
+        path: Vec<String>,
+        docs: String,
+    ) {
+        let mut module = document_items(extractor, &parsed_module.items);
+        module.docs = docs;
+
+        for item in &parsed_module.items {
+            let name = match &item.kind {
+                ItemKind::Submodules(submodule) => &submodule.name,
+                ItemKind::ModuleDecl(name) => name,
+                _ => continue,
+            };
+            let Some(&child_id) = self.def_map[module_id].children.get(name) else {
+                continue;
+            };
+
+            let mut child_path = path.clone();
+            child_path.push(name.to_string());
+            module.submodules.push(name.to_string());
+
+            let child_docs = extractor.outer_docs(item.span.start());
+            match &item.kind {
+                ItemKind::Submodules(submodule) => self.document_module(
+                    extractor,
+                    &submodule.contents,
+                    child_id,
+                    child_path,
+                    child_docs,
+                ),
+                _ => self.document_file(child_id, child_path, child_docs),
+            }
+        }
+
+        self.modules.insert(path, module);
+    }
+}
+
+/// Documents the public functions, structs, traits, type aliases and inherent methods among `items`.
+fn document_items(extractor: &DocExtractor, items: &[Item]) -> ModuleDocs {
+    let mut module = ModuleDocs::default();
+    for item in items {
+        let start = item.span.start();
+        match &item.kind {
+            ItemKind::Function(function)
+                if function.def.visibility == FunctionVisibility::Public =>
+            {
+                module.functions.push(extractor.item(start, function.span().start()));
+            }
+            ItemKind::Struct(_) => module.structs.push(extractor.item(start, item.span.end())),
+            ItemKind::TypeAlias(_) => {
+                module.type_aliases.push(extractor.item(start, item.span.end()));
+            }
+            ItemKind::Trait(_) => {
+                module.traits.push(extractor.item(start, extractor.next_brace(start)));
+            }
+            ItemKind::Impl(type_impl) => {
+                let methods: Vec<_> = type_impl
+                    .methods
+                    .iter()
+                    .filter(|(method, _)| method.def.visibility == FunctionVisibility::Public)
+                    .map(|(method, span)| extractor.item(span.start(), method.span().start()))
+                    .collect();
+                if !methods.is_empty() {
+                    let header = extractor.item(start, extractor.next_brace(start));
+                    module.impls.push(ImplDocs { header, methods });
+                }
+            }
+            _ => {}
+        }
+    }
+    module
+}
+
+/// Recovers the doc comments and declarations of items from the tokens of a source file.
+///
+/// The parser discards comments, so the file is lexed again with comments included
+/// and the doc comments are matched to items by their position.
+struct DocExtractor<'a> {
+    source: &'a str,
+    tokens: Vec<SpannedToken>,
+}
+
+impl<'a> DocExtractor<'a> {
+    fn new(source: &'a str) -> Self {
+        let tokens = Lexer::new(source).skip_comments(false).filter_map(Result::ok).collect();
+        DocExtractor { source, tokens }
+    }
+
+    /// Documents the item whose span starts at `start`, taking its declaration up to `end`.
+    fn item(&self, start: u32, end: u32) -> ItemDocs {
+        ItemDocs { signature: self.signature(start, end), docs: self.outer_docs(start) }
+    }
+
+    /// Returns the outer doc comments (`///` or `/** */`) directly preceding position `start`.
+    fn outer_docs(&self, start: u32) -> String {
+        let index = self.tokens.partition_point(|token| token.to_span().start() < start);
+        let mut comments: Vec<_> = self.tokens[..index]
+            .iter()
+            .rev()
+            .map_while(|token| match token.token() {
+                Token::LineComment(..) | Token::BlockComment(..) => Some(token.token()),
+                _ => None,
+            })
+            .filter(|token| doc_style(token) == Some(DocStyle::Outer))
+            .collect();
+        comments.reverse();
+        format_doc_comments(comments)
+    }
+
+    /// Returns the inner doc comments (`//!` or `/*! */`) before position `end`.
+    fn inner_docs(&self, end: u32) -> String {
+        let comments = self
+            .tokens
+            .iter()
+            .take_while(|token| token.to_span().start() < end)
+            .map(SpannedToken::token)
+            .filter(|token| doc_style(token) == Some(DocStyle::Inner))
+            .collect();
+        format_doc_comments(comments)
+    }
+
+    /// Returns the source between `start` and `end` on a single line, skipping any attributes at the start.
+    fn signature(&self, start: u32, end: u32) -> String {
+        let start = self
+            .tokens
+            .iter()
+            .filter(|token| token.to_span().start() >= start)
+            .find(|token| {
+                !matches!(
+                    token.token(),
+                    Token::Attribute(_) | Token::LineComment(..) | Token::BlockComment(..)
+                )
+            })
+            .map_or(start, |token| token.to_span().start());
+        let declaration = &self.source[start as usize..end.max(start) as usize];
+        declaration.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Returns the position of the first `{` at or after `start`.
+    fn next_brace(&self, start: u32) -> u32 {
+        self.tokens
+            .iter()
+            .find(|token| token.to_span().start() >= start && *token.token() == Token::LeftBrace)
+            .map_or(start, |token| token.to_span().start())
+    }
+}
+
+fn doc_style(token: &Token) -> Option<DocStyle> {
+    match token {
+        Token::LineComment(_, style) | Token::BlockComment(_, style) => *style,
+        _ => None,
+    }
+}
+
+/// Joins doc comments into Markdown text, stripping the leading space of each line and the `*` gutter
+/// of block comments.
+fn format_doc_comments(comments: Vec<&Token>) -> String {
+    let mut lines = Vec::new();
+    for comment in comments {
+        match comment {
+            Token::LineComment(text, _) => {
+                lines.push(text.strip_prefix(' ').unwrap_or(text).trim_end());
+            }
+            Token::BlockComment(text, _) => {
+                for line in text.lines() {
+                    let line = line.trim();
+                    let line = line.strip_prefix('*').unwrap_or(line);
+                    lines.push(line.strip_prefix(' ').unwrap_or(line));
+                }
+            }
+            _ => {}
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Returns where the documentation of the module at `path` is written, with the crate root at `index.md`.
+fn module_doc_path(package_doc_dir: &Path, path: &[String]) -> PathBuf {
+    if path.is_empty() {
+        package_doc_dir.join("index.md")
+    } else {
+        package_doc_dir.join(path.join("/")).with_extension("md")
+    }
+}
+
+fn render_module(crate_name: &str, path: &[String], module: &ModuleDocs) -> String {
+    let module_name =
+        std::iter::once(crate_name).chain(path.iter().map(String::as_str)).collect::<Vec<_>>();
+    let mut output = format!("# Module `{}`\n", module_name.join("::"));
+    write_docs(&mut output, &module.docs);
+
+    if !module.submodules.is_empty() {
+        output.push_str("\n## Modules\n\n");
+        // Submodules are written to a directory named after this module, alongside this module's file.
+        let link_dir = path.last().map(|name| format!("{name}/")).unwrap_or_default();
+        for submodule in &module.submodules {
+            writeln!(output, "- [`{submodule}`]({link_dir}{submodule}.md)")
+                .expect("writing to a string should not fail");
+        }
+    }
+
+    write_items(&mut output, "Functions", &module.functions);
+    write_items(&mut output, "Structs", &module.structs);
+    write_items(&mut output, "Traits", &module.traits);
+    write_items(&mut output, "Type aliases", &module.type_aliases);
+
+    if !module.impls.is_empty() {
+        output.push_str("\n## Implementations\n");
+        for type_impl in &module.impls {
+            write_item(&mut output, "###", &type_impl.header);
+            for method in &type_impl.methods {
+                write_item(&mut output, "####", method);
+            }
+        }
+    }
+
+    output
+}
+
+fn write_items(output: &mut String, title: &str, items: &[ItemDocs]) {
+    if items.is_empty() {
+        return;
+    }
+    writeln!(output, "\n## {title}").expect("writing to a string should not fail");
+    for item in items {
+        write_item(output, "###", item);
+    }
+}
+
+fn write_item(output: &mut String, heading: &str, item: &ItemDocs) {
+    writeln!(output, "\n{heading} `{}`", item.signature)
+        .expect("writing to a string should not fail");
+    write_docs(output, &item.docs);
+}
+
+fn write_docs(output: &mut String, docs: &str) {
+    if !docs.is_empty() {
+        writeln!(output, "\n{docs}").expect("writing to a string should not fail");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noirc_frontend::parse_program;
+
+    use super::{document_items, render_module, DocExtractor};
+
+    #[test]
+    fn renders_public_items_with_doc_comments() {
+        let source = r#"
+        //! Helpers for working with points.
+
+        /// A point on the plane.
+        struct Point {
+            x: Field,
+            y: Field,
+        }
+
+        // Not a doc comment.
+        /// Adds two fields together.
+        ///
+        /// Overflows wrap around the field modulus.
+        #[deprecated]
+        pub fn add(x: Field,
+                   y: Field) -> Field {
+            x + y
+        }
+
+        /// Hidden from the documentation.
+        fn private_helper() {}
+
+        /** An alias for a pair of fields. */
+        type Pair = (Field, Field);
+
+        impl Point {
+            /// Creates a point at the origin.
+            pub fn origin() -> Self {
+                Point { x: 0, y: 0 }
+            }
+
+            fn private_method(self) {}
+        }
+
+        impl Point {
+            fn only_private(self) {}
+        }
+        "#;
+        let (parsed_module, errors) = parse_program(source);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let extractor = DocExtractor::new(source);
+        let mut module = document_items(&extractor, &parsed_module.items);
+        module.docs = extractor.inner_docs(parsed_module.items[0].span.start());
+        module.submodules.push("inner".to_string());
+
+        let expected = "\
+# Module `my_lib::geometry`
+
+Helpers for working with points.
+
+## Modules
+
+- [`inner`](geometry/inner.md)
+
+## Functions
+
+### `pub fn add(x: Field, y: Field) -> Field`
+
+Adds two fields together.
+
+Overflows wrap around the field modulus.
+
+## Structs
+
+### `struct Point { x: Field, y: Field, }`
+
+A point on the plane.
+
+## Type aliases
+
+### `type Pair = (Field, Field);`
+
+An alias for a pair of fields.
+
+## Implementations
+
+### `impl Point`
+
+#### `pub fn origin() -> Self`
+
+Creates a point at the origin.
+";
+        assert_eq!(render_module("my_lib", &["geometry".to_string()], &module), expected);
+    }
+}
@@ -1,26 +1,40 @@
-use acvm::acir::native_types::WitnessMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use acvm::acir::circuit::Circuit;
+use acvm::acir::native_types::{Witness, WitnessMap};
+use acvm::FieldElement;
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
-use clap::Args;
+use clap::{Args, ValueEnum};
 
 use nargo::artifacts::debug::DebugArtifact;
 use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::try_to_diagnose_runtime_error;
-use nargo::ops::{compile_program, DefaultForeignCallExecutor};
+use nargo::ops::{
+    compile_program, execute_circuit_with_partial_witness, explain_failed_opcode,
+    DefaultForeignCallExecutor, SolverTrace,
+};
 use nargo::package::Package;
-use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all, NargoError};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_entry_file, PackageSelection};
 use noirc_abi::input_parser::{Format, InputValue};
-use noirc_abi::InputMap;
-use noirc_driver::{
-    file_manager_with_stdlib, CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
-};
+use noirc_abi::{Abi, InputMap, MAIN_RETURN_NAME};
+use noirc_driver::{file_manager_with_stdlib, CompileOptions, CompiledProgram};
 use noirc_frontend::graph::CrateName;
 
-use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
-use super::NargoConfig;
+use super::fs::{
+    inputs::{format_from_path, parse_inputs, read_inputs_from_file, read_inputs_from_stdin},
+    program::load_bundle,
+    witness::{read_witness_from_file, save_witness_to_dir, WitnessFormat},
+    PendingFile,
+};
+use super::{resolve_workspace, NargoConfig};
 use crate::backends::Backend;
-use crate::cli::compile_cmd::report_errors;
-use crate::errors::CliError;
+use crate::cli::compile_cmd::{apply_profile, report_errors};
+use crate::cli::prove_cmd::single_binary_package;
+use crate::errors::{CliError, FilesystemError};
 
 /// Executes a circuit to calculate its return value
 #[derive(Debug, Clone, Args)]
@@ -28,24 +42,97 @@ pub(crate) struct ExecuteCommand {
     /// Write the execution witness to named file
     witness_name: Option<String>,
 
+    /// The format in which to write the execution witness
+    #[clap(long, value_enum, default_value_t = WitnessFormat::Binary, requires = "witness_name")]
+    witness_format: WitnessFormat,
+
     /// The name of the toml file which contains the inputs for the prover
     #[clap(long, short, default_value = PROVER_INPUT_FILE)]
     prover_name: String,
 
+    /// Read the inputs for the prover from stdin rather than from a file
+    #[clap(long, conflicts_with = "prover_name")]
+    input_stdin: bool,
+
+    /// The format of the inputs read from stdin
+    #[clap(long, value_enum, default_value_t = InputFormat::Toml)]
+    format: InputFormat,
+
+    /// Override the value of a single input, written as `NAME=VALUE` where the value is a TOML value
+    /// (e.g. `--input x=5`). May be repeated and takes precedence over the prover inputs
+    #[clap(long = "input", value_name = "NAME=VALUE", value_parser = parse_input_override)]
+    inputs: Vec<(String, String)>,
+
     /// The name of the package to execute
     #[clap(long, conflicts_with = "workspace")]
     package: Option<CrateName>,
 
+    /// Execute the `main` function of this standalone Noir source file rather than a package,
+    /// reading its inputs from the adjacent prover inputs or stdin
+    #[clap(long, value_name = "FILE", conflicts_with_all = ["package", "workspace"])]
+    pub(crate) file: Option<PathBuf>,
+
     /// Execute all packages in the workspace
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// Execute the program in this bundle, written by `nargo compile --bundle`, rather than compiling the package
+    #[clap(long, value_name = "FILE", conflicts_with = "workspace")]
+    bundle: Option<PathBuf>,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 
     /// JSON RPC url to solve oracle calls
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    /// Check that the circuit's return value is equal to this value, written as a TOML value (e.g. `3` or `[1, 2]`)
+    #[clap(long)]
+    assert_output: Option<String>,
+
+    /// When a constraint fails, also print the failing opcode and the values of the witnesses it reads
+    #[clap(long)]
+    explain: bool,
+
+    /// Write a trace of each opcode stepped through by the solver to this file, as one JSON object per line
+    #[clap(long, value_name = "FILE", conflicts_with = "workspace")]
+    trace: Option<PathBuf>,
+
+    /// Write the values of the public inputs and return value to this file, as JSON if it has a `.json` extension
+    /// and otherwise as TOML
+    #[clap(long, value_name = "FILE", conflicts_with = "workspace")]
+    public_inputs: Option<PathBuf>,
+
+    /// Refuse to execute a circuit whose solved witness is estimated to need more than this many megabytes of memory
+    #[clap(long, value_name = "MB")]
+    max_memory: Option<u64>,
+
+    /// Start solving from a witness previously saved by `nargo execute` in the binary format, so that only the
+    /// witnesses which it does not assign a value are solved. Values of the inputs take precedence over this witness
+    #[clap(long, value_name = "FILE", conflicts_with = "workspace")]
+    initial_witness: Option<PathBuf>,
+
+    /// Solve the witness this many times with the same inputs before executing, printing the minimum, median and
+    /// maximum time taken to stderr
+    #[clap(long, value_name = "N")]
+    repeat: Option<NonZeroUsize>,
+}
+
+/// The formats in which inputs may be provided on stdin
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum InputFormat {
+    Toml,
+    Json,
+}
+
+impl From<InputFormat> for Format {
+    fn from(format: InputFormat) -> Self {
+        match format {
+            InputFormat::Toml => Format::Toml,
+            InputFormat::Json => Format::Json,
+        }
+    }
 }
 
 pub(crate) fn run(
@@ -53,98 +140,390 @@ pub(crate) fn run(
     args: ExecuteCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
-    let toml_path = get_package_manifest(&config.program_dir)?;
-    let default_selection =
-        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
-    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
-    )?;
+    let workspace = match &args.file {
+        Some(entry_path) => resolve_workspace_from_entry_file(entry_path)?,
+        None => {
+            let toml_path = get_package_manifest(&config.program_dir)?;
+            let default_selection =
+                if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+            let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+            resolve_workspace(&config, &toml_path, selection)?
+        }
+    };
     let target_dir = &workspace.target_directory_path();
+    // Stdin can only be read once so the same inputs are used for every package.
+    let stdin_inputs = if args.input_stdin { Some(read_inputs_from_stdin()?) } else { None };
+    let partial_witness = match &args.initial_witness {
+        Some(witness_path) => read_witness_from_file(witness_path)?,
+        None => WitnessMap::new(),
+    };
+    let binary_packages: Vec<_> =
+        workspace.into_iter().filter(|package| package.is_binary()).collect();
+
+    if let Some(bundle_path) = &args.bundle {
+        let package = single_binary_package(&binary_packages, "A bundle")?;
+        let compiled_program = load_bundle(bundle_path)?.into();
+        return execute_package(
+            &args,
+            package,
+            compiled_program,
+            stdin_inputs.as_deref(),
+            &partial_witness,
+            target_dir,
+        );
+    }
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
 
-    let expression_width = args
-        .compile_options
-        .expression_width
-        .unwrap_or_else(|| backend.get_backend_info_or_default());
-    let binary_packages = workspace.into_iter().filter(|package| package.is_binary());
+    let expression_width =
+        compile_options.expression_width.unwrap_or_else(|| backend.get_backend_info_or_default());
     for package in binary_packages {
         let compilation_result = compile_program(
             &workspace_file_manager,
             &parsed_files,
             package,
-            &args.compile_options,
+            &compile_options,
             None,
         );
 
         let compiled_program = report_errors(
             compilation_result,
             &workspace_file_manager,
-            args.compile_options.deny_warnings,
-            args.compile_options.silence_warnings,
+            compile_options.deny_warnings,
+            compile_options.silence_warnings,
         )?;
 
         let compiled_program = nargo::ops::transform_program(compiled_program, expression_width);
-
-        let (return_value, solved_witness) = execute_program_and_decode(
-            compiled_program,
+        execute_package(
+            &args,
             package,
-            &args.prover_name,
-            args.oracle_resolver.as_deref(),
+            compiled_program,
+            stdin_inputs.as_deref(),
+            &partial_witness,
+            target_dir,
         )?;
+    }
+    Ok(())
+}
 
-        println!("[{}] Circuit witness successfully solved", package.name);
-        if let Some(return_value) = return_value {
-            println!("[{}] Circuit output: {return_value:?}", package.name);
-        }
-        if let Some(witness_name) = &args.witness_name {
-            let witness_path = save_witness_to_dir(solved_witness, witness_name, target_dir)?;
+fn execute_package(
+    args: &ExecuteCommand,
+    package: &Package,
+    compiled_program: CompiledProgram,
+    stdin_inputs: Option<&str>,
+    partial_witness: &WitnessMap,
+    target_dir: &Path,
+) -> Result<(), CliError> {
+    if let Some(max_memory_mb) = args.max_memory {
+        check_memory_limit(&package.name, &compiled_program.circuit, max_memory_mb)?;
+    }
 
-            println!("[{}] Witness saved to {}", package.name, witness_path.display());
+    let abi = compiled_program.abi.clone();
+    let input_overrides = parse_input_overrides(&abi, &args.inputs)?;
+    // Overridden parameters do not need to be provided, and the return value is not used when executing.
+    let inputs_abi = Abi {
+        parameters: abi
+            .parameters
+            .iter()
+            .filter(|parameter| !input_overrides.contains_key(&parameter.name))
+            .cloned()
+            .collect(),
+        return_type: None,
+        ..abi.clone()
+    };
+    let (mut inputs_map, _) = match stdin_inputs {
+        Some(input_string) => parse_inputs(input_string, args.format.into(), &inputs_abi)?,
+        None => {
+            read_inputs_from_file(&package.root_dir, &args.prover_name, Format::Toml, &inputs_abi)?
         }
+    };
+    inputs_map.extend(input_overrides);
+    if let Some(repeat) = args.repeat {
+        let timings = time_solving(
+            &compiled_program,
+            &inputs_map,
+            partial_witness,
+            args.oracle_resolver.as_deref(),
+            repeat,
+        )?;
+        let (min, median, max) = summarize_timings(timings);
+        eprintln!(
+            "[{}] Solved witness {repeat} times: min {min:?}, median {median:?}, max {max:?}",
+            package.name
+        );
+    }
+    let (return_value, solved_witness) = execute_program_and_decode(
+        compiled_program,
+        &inputs_map,
+        partial_witness.clone(),
+        args.oracle_resolver.as_deref(),
+        args.explain,
+        args.trace.as_deref(),
+    )?;
+
+    println!("[{}] Circuit witness successfully solved", package.name);
+    if let (Some(return_value), Some(return_type)) = (&return_value, &abi.return_type) {
+        println!(
+            "[{}] Circuit output: {}",
+            package.name,
+            return_value.display(&return_type.abi_type)
+        );
+    }
+    if let Some(expected_output) = &args.assert_output {
+        check_return_value(&abi, return_value.as_ref(), expected_output)?;
+    }
+    if let Some(public_inputs_path) = &args.public_inputs {
+        let public_inputs_path =
+            public_inputs_file(&abi, &solved_witness, public_inputs_path)?.write();
+        println!("[{}] Public inputs saved to {}", package.name, public_inputs_path.display());
+    }
+    if let Some(witness_name) = &args.witness_name {
+        let witness_path =
+            save_witness_to_dir(solved_witness, witness_name, target_dir, args.witness_format)?;
+
+        println!("[{}] Witness saved to {}", package.name, witness_path.display());
     }
     Ok(())
 }
 
+/// Checks that solving `circuit` should fit within `max_memory_mb` megabytes, so that a circuit which is too large
+/// is rejected before the solver exhausts the available memory.
+///
+/// This is an approximation based upon the size of the solved witness, as every witness of the circuit may be
+/// assigned a value.
+fn check_memory_limit(
+    package_name: &CrateName,
+    circuit: &Circuit,
+    max_memory_mb: u64,
+) -> Result<(), CliError> {
+    // Each entry of the witness map is counted twice to account for the overhead of the map itself.
+    let entry_size = 2 * (std::mem::size_of::<Witness>() + std::mem::size_of::<FieldElement>());
+    let estimated_bytes = (u64::from(circuit.current_witness_index) + 1) * entry_size as u64;
+    const BYTES_PER_MB: u64 = 1024 * 1024;
+    let estimated_mb = (estimated_bytes + BYTES_PER_MB - 1) / BYTES_PER_MB;
+    if estimated_mb > max_memory_mb {
+        return Err(CliError::MemoryLimitExceeded {
+            package: package_name.to_string(),
+            estimated_mb,
+            max_memory_mb,
+        });
+    }
+    Ok(())
+}
+
+/// Solves the witness of `program` `repeat` times, returning the time taken by each solve.
+fn time_solving(
+    program: &CompiledProgram,
+    inputs_map: &InputMap,
+    partial_witness: &WitnessMap,
+    foreign_call_resolver_url: Option<&str>,
+    repeat: NonZeroUsize,
+) -> Result<Vec<Duration>, CliError> {
+    (0..repeat.get())
+        .map(|_| {
+            let solving_start = Instant::now();
+            execute_program_from_partial_witness(
+                program,
+                inputs_map,
+                partial_witness.clone(),
+                foreign_call_resolver_url,
+                false,
+                None,
+                // Printing the program's output on every run would flood the terminal and skew the timings.
+                false,
+            )?;
+            Ok(solving_start.elapsed())
+        })
+        .collect()
+}
+
+/// Returns the minimum, median and maximum of a non-empty list of timings.
+fn summarize_timings(mut timings: Vec<Duration>) -> (Duration, Duration, Duration) {
+    timings.sort();
+    let middle = timings.len() / 2;
+    let median = if timings.len() % 2 == 0 {
+        (timings[middle - 1] + timings[middle]) / 2
+    } else {
+        timings[middle]
+    };
+    (timings[0], median, timings[timings.len() - 1])
+}
+
 fn execute_program_and_decode(
     program: CompiledProgram,
-    package: &Package,
-    prover_name: &str,
+    inputs_map: &InputMap,
+    partial_witness: WitnessMap,
     foreign_call_resolver_url: Option<&str>,
+    explain: bool,
+    trace_path: Option<&Path>,
 ) -> Result<(Option<InputValue>, WitnessMap), CliError> {
-    // Parse the initial witness values from Prover.toml
-    let (inputs_map, _) =
-        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
-    let solved_witness = execute_program(&program, &inputs_map, foreign_call_resolver_url)?;
+    let solved_witness = execute_program_from_partial_witness(
+        &program,
+        inputs_map,
+        partial_witness,
+        foreign_call_resolver_url,
+        explain,
+        trace_path,
+        true,
+    )?;
     let public_abi = program.abi.public_abi();
     let (_, return_value) = public_abi.decode(&solved_witness)?;
 
     Ok((return_value, solved_witness))
 }
 
+/// Serializes the values of the public parameters and return value in `solved_witness` into a file ready to be
+/// written to `path`.
+///
+/// The file is in the same format as a `Verifier.toml`, or its JSON equivalent if `path` has a `.json` extension.
+pub(crate) fn public_inputs_file(
+    abi: &Abi,
+    solved_witness: &WitnessMap,
+    path: &Path,
+) -> Result<PendingFile, CliError> {
+    let format = format_from_path(path);
+    let public_abi = abi.clone().public_abi();
+    let (mut public_inputs, return_value) = public_abi.decode(solved_witness)?;
+    if let Some(return_value) = return_value {
+        public_inputs.insert(MAIN_RETURN_NAME.to_owned(), return_value);
+    }
+
+    let contents = format.serialize(&public_inputs, &public_abi).map_err(FilesystemError::from)?;
+    Ok(PendingFile { path: path.to_path_buf(), contents: contents.into_bytes() })
+}
+
+fn parse_input_override(input: &str) -> Result<(String, String), String> {
+    let (name, value) = input
+        .split_once('=')
+        .ok_or_else(|| format!("expected an input of the form `NAME=VALUE` but found `{input}`"))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+/// Parses each of the `--input` overrides against the type of the parameter of the same name in `abi`.
+fn parse_input_overrides(abi: &Abi, overrides: &[(String, String)]) -> Result<InputMap, CliError> {
+    let mut input_overrides = InputMap::new();
+    for (name, value) in overrides {
+        let Some(parameter) = abi.parameters.iter().find(|parameter| &parameter.name == name)
+        else {
+            return Err(CliError::Generic(format!(
+                "Invalid value for `--input`: the circuit has no parameter named `{name}`"
+            )));
+        };
+
+        // Parse the value as if it were the only input in a `Prover.toml` for a program with this single parameter.
+        let parameter_abi = Abi {
+            parameters: vec![parameter.clone()],
+            param_witnesses: BTreeMap::new(),
+            return_type: None,
+            return_witnesses: Vec::new(),
+        };
+        let mut parsed_inputs =
+            Format::Toml.parse(&format!("{name} = {value}"), &parameter_abi).map_err(|err| {
+                CliError::Generic(format!("Invalid value for `--input {name}`: {err}"))
+            })?;
+        let value = parsed_inputs.remove(name).expect("the parameter's value should be parsed");
+        input_overrides.insert(name.clone(), value);
+    }
+    Ok(input_overrides)
+}
+
+/// Checks that `return_value` is equal to `expected_output`, which is parsed against the return type of `abi`.
+fn check_return_value(
+    abi: &Abi,
+    return_value: Option<&InputValue>,
+    expected_output: &str,
+) -> Result<(), CliError> {
+    let Some(return_type) = &abi.return_type else {
+        return Err(CliError::Generic(
+            "`--assert-output` was provided but the circuit has no return value".to_string(),
+        ));
+    };
+
+    // Parse the expected output as if it were the return value in a `Prover.toml` for a program with no parameters.
+    let return_abi = Abi {
+        parameters: Vec::new(),
+        param_witnesses: BTreeMap::new(),
+        return_type: Some(return_type.clone()),
+        return_witnesses: Vec::new(),
+    };
+    let mut expected_inputs = Format::Toml
+        .parse(&format!("{MAIN_RETURN_NAME} = {expected_output}"), &return_abi)
+        .map_err(|err| CliError::Generic(format!("Invalid value for `--assert-output`: {err}")))?;
+    let expected_output = expected_inputs.remove(MAIN_RETURN_NAME).ok_or_else(|| {
+        CliError::Generic("Invalid value for `--assert-output`: no value provided".to_string())
+    })?;
+
+    if return_value == Some(&expected_output) {
+        Ok(())
+    } else {
+        let expected_output = format!("{expected_output:#?}");
+        let return_value = return_value.map(|value| format!("{value:#?}")).unwrap_or_default();
+        let diff = similar_asserts::SimpleDiff::from_str(
+            &expected_output,
+            &return_value,
+            "expected",
+            "actual",
+        );
+        Err(CliError::UnexpectedOutput(diff.to_string()))
+    }
+}
+
 pub(crate) fn execute_program(
     compiled_program: &CompiledProgram,
     inputs_map: &InputMap,
     foreign_call_resolver_url: Option<&str>,
+    explain: bool,
+    trace_path: Option<&Path>,
+) -> Result<WitnessMap, CliError> {
+    execute_program_from_partial_witness(
+        compiled_program,
+        inputs_map,
+        WitnessMap::new(),
+        foreign_call_resolver_url,
+        explain,
+        trace_path,
+        true,
+    )
+}
+
+/// Like [execute_program], but starts solving from the values already assigned in `partial_witness`.
+///
+/// Output printed by the program is only shown if `show_output` is set.
+fn execute_program_from_partial_witness(
+    compiled_program: &CompiledProgram,
+    inputs_map: &InputMap,
+    partial_witness: WitnessMap,
+    foreign_call_resolver_url: Option<&str>,
+    explain: bool,
+    trace_path: Option<&Path>,
+    show_output: bool,
 ) -> Result<WitnessMap, CliError> {
     let blackbox_solver = Bn254BlackBoxSolver::new();
 
-    let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
+    let encoded_inputs = compiled_program.abi.encode(inputs_map, None)?;
+    let initial_witness = merge_partial_witness(encoded_inputs, partial_witness);
 
-    let solved_witness_err = nargo::ops::execute_circuit(
+    let mut trace = trace_path.map(|_| SolverTrace::default());
+    let solved_witness_err = execute_circuit_with_partial_witness(
         &compiled_program.circuit,
         initial_witness,
         &blackbox_solver,
-        &mut DefaultForeignCallExecutor::new(true, foreign_call_resolver_url),
+        &mut DefaultForeignCallExecutor::new(show_output, foreign_call_resolver_url),
+        trace.as_mut(),
     );
+    // The trace is written even if execution fails, as that is when it is most useful.
+    if let (Some(trace_path), Some(trace)) = (trace_path, &trace) {
+        write_trace(trace_path, trace)?;
+    }
     match solved_witness_err {
-        Ok(solved_witness) => Ok(solved_witness),
-        Err(err) => {
+        Ok((solved_witness, unused_witnesses)) => {
+            warn_on_unused_inputs(&compiled_program.abi, &unused_witnesses);
+            Ok(solved_witness)
+        }
+        Err((err, partial_witness)) => {
             let debug_artifact = DebugArtifact {
                 debug_symbols: vec![compiled_program.debug.clone()],
                 file_map: compiled_program.file_map.clone(),
@@ -154,8 +533,232 @@ pub(crate) fn execute_program(
             if let Some(diagnostic) = try_to_diagnose_runtime_error(&err, &compiled_program.debug) {
                 diagnostic.report(&debug_artifact, false);
             }
+            if explain {
+                if let NargoError::ExecutionError(execution_error) = &err {
+                    let failed_opcode = explain_failed_opcode(
+                        &compiled_program.circuit,
+                        execution_error,
+                        &partial_witness,
+                    );
+                    if let Some(failed_opcode) = failed_opcode {
+                        eprint!("{failed_opcode}");
+                    }
+                }
+            }
 
             Err(crate::errors::CliError::NargoError(err))
         }
     }
 }
+
+/// Adds each value of `partial_witness` to `initial_witness` unless that witness is already assigned a value.
+fn merge_partial_witness(
+    mut initial_witness: WitnessMap,
+    partial_witness: WitnessMap,
+) -> WitnessMap {
+    for (witness, value) in partial_witness {
+        if initial_witness.get(&witness).is_none() {
+            initial_witness.insert(witness, value);
+        }
+    }
+    initial_witness
+}
+
+/// Writes each event of `trace` to the file at `trace_path` as a line of JSON.
+fn write_trace(trace_path: &Path, trace: &SolverTrace) -> Result<(), FilesystemError> {
+    let mut contents = Vec::new();
+    for event in &trace.events {
+        serde_json::to_writer(&mut contents, event).expect("trace events should be serializable");
+        contents.push(b'\n');
+    }
+    std::fs::write(trace_path, contents)
+        .map_err(|err| FilesystemError::TraceWriteError(trace_path.to_path_buf(), err))
+}
+
+/// Prints a warning for each input which was provided but never used while solving the circuit.
+fn warn_on_unused_inputs(abi: &Abi, unused_witnesses: &BTreeSet<Witness>) {
+    for (param_name, witness_ranges) in &abi.param_witnesses {
+        let param_witnesses: Vec<Witness> = witness_ranges
+            .iter()
+            .flat_map(|range| range.start.witness_index()..range.end.witness_index())
+            .map(Witness)
+            .collect();
+
+        let is_unused = !param_witnesses.is_empty()
+            && param_witnesses.iter().all(|witness| unused_witnesses.contains(witness));
+        if is_unused {
+            eprintln!("Warning: input `{param_name}` was provided but never used");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use std::path::Path;
+    use std::time::Duration;
+
+    use acvm::{
+        acir::{
+            circuit::Circuit,
+            native_types::{Witness, WitnessMap},
+        },
+        FieldElement,
+    };
+    use noirc_abi::{
+        input_parser::InputValue, Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility,
+    };
+
+    use crate::errors::CliError;
+
+    use super::{
+        check_memory_limit, check_return_value, merge_partial_witness, parse_input_override,
+        parse_input_overrides, public_inputs_file, summarize_timings,
+    };
+
+    fn abi_returning(abi_type: AbiType) -> Abi {
+        Abi {
+            parameters: Vec::new(),
+            param_witnesses: BTreeMap::new(),
+            return_type: Some(AbiReturnType { abi_type, visibility: AbiVisibility::Public }),
+            return_witnesses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn checks_return_value_against_expected_output() {
+        let abi = abi_returning(AbiType::Array { length: 2, typ: Box::new(AbiType::Field) });
+        let return_value = InputValue::Vec(vec![
+            InputValue::Field(FieldElement::from(1_u128)),
+            InputValue::Field(FieldElement::from(2_u128)),
+        ]);
+
+        assert!(check_return_value(&abi, Some(&return_value), "[1, \"0x02\"]").is_ok());
+        assert!(check_return_value(&abi, Some(&return_value), "[1, 3]").is_err());
+        assert!(check_return_value(&abi, Some(&return_value), "[1]").is_err());
+    }
+
+    #[test]
+    fn parses_input_overrides_against_parameter_types() {
+        let abi = Abi {
+            parameters: vec![AbiParameter {
+                name: "x".to_string(),
+                typ: AbiType::Field,
+                visibility: AbiVisibility::Private,
+            }],
+            param_witnesses: BTreeMap::new(),
+            return_type: None,
+            return_witnesses: Vec::new(),
+        };
+        let input = |name: &str, value: &str| vec![(name.to_string(), value.to_string())];
+
+        let overrides = parse_input_overrides(&abi, &input("x", "0x1f")).unwrap();
+        assert_eq!(overrides["x"], InputValue::Field(FieldElement::from(31_u128)));
+
+        assert!(parse_input_overrides(&abi, &input("y", "1")).is_err());
+        assert!(parse_input_overrides(&abi, &input("x", "[1, 2]")).is_err());
+        assert!(parse_input_override("x").is_err());
+        assert_eq!(parse_input_override("x = 5"), Ok(("x".to_string(), "5".to_string())));
+    }
+
+    #[test]
+    fn rejects_expected_output_for_program_without_return_value() {
+        let abi = Abi {
+            parameters: Vec::new(),
+            param_witnesses: BTreeMap::new(),
+            return_type: None,
+            return_witnesses: Vec::new(),
+        };
+        assert!(check_return_value(&abi, None, "1").is_err());
+    }
+
+    #[test]
+    fn writes_only_public_inputs() {
+        let parameter = |name: &str, visibility| AbiParameter {
+            name: name.to_string(),
+            typ: AbiType::Field,
+            visibility,
+        };
+        let abi = Abi {
+            parameters: vec![
+                parameter("x", AbiVisibility::Private),
+                parameter("y", AbiVisibility::Public),
+            ],
+            param_witnesses: BTreeMap::from([
+                ("x".to_string(), vec![Witness(1)..Witness(2)]),
+                ("y".to_string(), vec![Witness(2)..Witness(3)]),
+            ]),
+            return_type: Some(AbiReturnType {
+                abi_type: AbiType::Field,
+                visibility: AbiVisibility::Public,
+            }),
+            return_witnesses: vec![Witness(3)],
+        };
+        let solved_witness = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::from(1_u128)),
+            (Witness(2), FieldElement::from(2_u128)),
+            (Witness(3), FieldElement::from(3_u128)),
+        ]));
+
+        let toml_file =
+            public_inputs_file(&abi, &solved_witness, Path::new("public.toml")).unwrap();
+        let toml: toml::Value =
+            toml::from_str(std::str::from_utf8(&toml_file.contents).unwrap()).unwrap();
+        let hex = |value: u128| format!("0x{}", FieldElement::from(value).to_hex());
+        assert_eq!(toml.get("x"), None);
+        assert_eq!(toml["y"].as_str(), Some(hex(2).as_str()));
+        assert_eq!(toml["return"].as_str(), Some(hex(3).as_str()));
+
+        let json_file =
+            public_inputs_file(&abi, &solved_witness, Path::new("public.json")).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json_file.contents).unwrap();
+        assert_eq!(json.as_object().unwrap().keys().collect::<Vec<_>>(), vec!["return", "y"]);
+    }
+
+    #[test]
+    fn rejects_circuits_exceeding_the_memory_limit() {
+        let package_name = "large".parse().unwrap();
+        let circuit = Circuit { current_witness_index: 1 << 20, ..Circuit::default() };
+        let estimated_mb = match check_memory_limit(&package_name, &circuit, 1) {
+            Err(CliError::MemoryLimitExceeded { estimated_mb, max_memory_mb: 1, .. }) => {
+                estimated_mb
+            }
+            result => panic!("expected the memory limit to be exceeded but got {result:?}"),
+        };
+        assert!(check_memory_limit(&package_name, &circuit, estimated_mb).is_ok());
+    }
+
+    #[test]
+    fn inputs_take_precedence_over_partial_witness() {
+        let witness_map = |values: &[(u32, u128)]| {
+            WitnessMap::from(
+                values
+                    .iter()
+                    .map(|&(index, value)| (Witness(index), FieldElement::from(value)))
+                    .collect::<BTreeMap<_, _>>(),
+            )
+        };
+        let encoded_inputs = witness_map(&[(1, 1)]);
+        let partial_witness = witness_map(&[(1, 5), (2, 2), (3, 3)]);
+
+        assert_eq!(
+            merge_partial_witness(encoded_inputs, partial_witness),
+            witness_map(&[(1, 1), (2, 2), (3, 3)])
+        );
+    }
+
+    #[test]
+    fn summarizes_timings() {
+        let millis = |values: &[u64]| -> Vec<Duration> {
+            values.iter().copied().map(Duration::from_millis).collect()
+        };
+        let as_millis = |(min, median, max): (Duration, Duration, Duration)| {
+            (min.as_millis(), median.as_millis(), max.as_millis())
+        };
+
+        assert_eq!(as_millis(summarize_timings(millis(&[5]))), (5, 5, 5));
+        assert_eq!(as_millis(summarize_timings(millis(&[9, 1, 4]))), (1, 4, 9));
+        assert_eq!(as_millis(summarize_timings(millis(&[8, 2, 4, 6]))), (2, 5, 8));
+    }
+}
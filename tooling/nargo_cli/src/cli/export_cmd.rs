@@ -6,14 +6,11 @@ use rayon::prelude::*;
 use fm::FileManager;
 use iter_extended::try_vecmap;
 use nargo::package::Package;
-use nargo::prepare_package;
+use nargo::prepare_package_with_features;
 use nargo::workspace::Workspace;
 use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::{
-    compile_no_check, file_manager_with_stdlib, CompileOptions, CompiledProgram,
-    NOIR_ARTIFACT_VERSION_STRING,
-};
+use nargo_toml::{get_package_manifest, PackageSelection};
+use noirc_driver::{compile_no_check, file_manager_with_stdlib, CompileOptions, CompiledProgram};
 
 use noirc_frontend::graph::CrateName;
 
@@ -24,9 +21,9 @@ use crate::errors::CliError;
 
 use super::check_cmd::check_crate_and_report_errors;
 
-use super::compile_cmd::report_errors;
+use super::compile_cmd::{apply_profile, report_errors};
 use super::fs::program::save_program_to_file;
-use super::NargoConfig;
+use super::{resolve_workspace, NargoConfig};
 
 /// Exports functions marked with #[export] attribute
 #[derive(Debug, Clone, Args)]
@@ -53,15 +50,12 @@ pub(crate) fn run(
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
     let selection = args.package.map_or(default_selection, PackageSelection::Selected);
 
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
-    )?;
+    let workspace = resolve_workspace(&config, &toml_path, selection)?;
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
 
     let library_packages: Vec<_> =
         workspace.into_iter().filter(|package| package.is_library()).collect();
@@ -74,7 +68,7 @@ pub(crate) fn run(
                 &parsed_files,
                 &workspace,
                 package,
-                &args.compile_options,
+                &compile_options,
             )
         })
         .collect()
@@ -87,7 +81,12 @@ fn compile_exported_functions(
     package: &Package,
     compile_options: &CompileOptions,
 ) -> Result<(), CliError> {
-    let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
+    let (mut context, crate_id) = prepare_package_with_features(
+        file_manager,
+        parsed_files,
+        package,
+        &compile_options.features,
+    );
     check_crate_and_report_errors(
         &mut context,
         crate_id,
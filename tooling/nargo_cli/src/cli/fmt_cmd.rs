@@ -2,14 +2,14 @@ use std::{fs::DirEntry, path::Path};
 
 use clap::Args;
 use nargo::insert_all_files_for_workspace_into_file_manager;
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::{file_manager_with_stdlib, NOIR_ARTIFACT_VERSION_STRING};
+use nargo_toml::{get_package_manifest, PackageSelection};
+use noirc_driver::file_manager_with_stdlib;
 use noirc_errors::CustomDiagnostic;
 use noirc_frontend::{hir::def_map::parse_file, parser::ParserError};
 
 use crate::errors::CliError;
 
-use super::NargoConfig;
+use super::{resolve_workspace, NargoConfig};
 
 /// Format the Noir files in a workspace
 #[derive(Debug, Clone, Args)]
@@ -23,11 +23,7 @@ pub(crate) fn run(args: FormatCommand, config: NargoConfig) -> Result<(), CliErr
     let check_mode = args.check;
 
     let toml_path = get_package_manifest(&config.program_dir)?;
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        PackageSelection::All,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
-    )?;
+    let workspace = resolve_workspace(&config, &toml_path, PackageSelection::All)?;
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
@@ -0,0 +1,112 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::PendingFile;
+
+/// The file within the target directory which records the inputs of the last successful compilation.
+const FINGERPRINT_FILE: &str = "compilation_fingerprint.json";
+
+/// The inputs to a compilation of the workspace, used to tell whether compiling again would reproduce the
+/// artifacts which are already on disk.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CompilationFingerprint {
+    /// Describes everything other than the source files which affects the artifacts, e.g. the compile options.
+    configuration: String,
+    files: BTreeMap<PathBuf, FileFingerprint>,
+    /// The artifacts which were written by the compilation.
+    artifacts: Vec<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct FileFingerprint {
+    /// The modification time as seconds and nanoseconds since the Unix epoch.
+    modified: (u64, u32),
+    content_hash: u64,
+}
+
+impl FileFingerprint {
+    fn new(path: &Path) -> Option<FileFingerprint> {
+        let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+        let modified = modified.duration_since(UNIX_EPOCH).ok()?;
+        let contents = std::fs::read(path).ok()?;
+
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        Some(FileFingerprint {
+            modified: (modified.as_secs(), modified.subsec_nanos()),
+            content_hash: hasher.finish(),
+        })
+    }
+}
+
+impl CompilationFingerprint {
+    /// Fingerprints the files at `paths`, returning `None` if any of them cannot be fingerprinted.
+    pub(crate) fn new(configuration: String, paths: &[PathBuf]) -> Option<CompilationFingerprint> {
+        let files = paths
+            .iter()
+            .map(|path| Some((path.clone(), FileFingerprint::new(path)?)))
+            .collect::<Option<_>>()?;
+        Some(CompilationFingerprint { configuration, files, artifacts: Vec::new() })
+    }
+
+    /// Returns whether the last compilation in `target_dir` had the same inputs and all of its artifacts still exist.
+    ///
+    /// A source file only counts as unchanged if both its modification time and its contents are the same.
+    pub(crate) fn is_up_to_date(&self, target_dir: &Path) -> bool {
+        let Some(previous) = std::fs::read(target_dir.join(FINGERPRINT_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CompilationFingerprint>(&bytes).ok())
+        else {
+            return false;
+        };
+
+        previous.configuration == self.configuration
+            && previous.files == self.files
+            && previous.artifacts.iter().all(|artifact| artifact.is_file())
+    }
+
+    /// Records a successful compilation which wrote `artifacts`, so that it can be skipped if nothing changes.
+    pub(crate) fn write(mut self, target_dir: &Path, artifacts: Vec<PathBuf>) {
+        self.artifacts = artifacts;
+        let contents =
+            serde_json::to_vec(&self).expect("compilation fingerprint should be serializable");
+        PendingFile { path: target_dir.join(FINGERPRINT_FILE), contents }.write();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompilationFingerprint;
+
+    #[test]
+    fn detects_changed_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let target_dir = dir.path().join("target");
+        let source = dir.path().join("main.nr");
+        let artifact = target_dir.join("program.json");
+        std::fs::write(&source, "fn main() {}").unwrap();
+
+        let fingerprint = |configuration: &str| {
+            CompilationFingerprint::new(configuration.to_string(), &[source.clone()]).unwrap()
+        };
+        assert!(!fingerprint("options").is_up_to_date(&target_dir));
+
+        fingerprint("options").write(&target_dir, vec![artifact.clone()]);
+        std::fs::write(&artifact, "{}").unwrap();
+        assert!(fingerprint("options").is_up_to_date(&target_dir));
+        assert!(!fingerprint("other options").is_up_to_date(&target_dir));
+
+        std::fs::remove_file(&artifact).unwrap();
+        assert!(!fingerprint("options").is_up_to_date(&target_dir));
+        std::fs::write(&artifact, "{}").unwrap();
+
+        std::fs::write(&source, "fn main() { assert(true); }").unwrap();
+        assert!(!fingerprint("options").is_up_to_date(&target_dir));
+    }
+}
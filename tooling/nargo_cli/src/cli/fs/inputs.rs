@@ -2,11 +2,15 @@ use noirc_abi::{
     input_parser::{Format, InputValue},
     Abi, InputMap, MAIN_RETURN_NAME,
 };
-use std::{collections::BTreeMap, path::Path};
+use std::{
+    collections::BTreeMap,
+    io::{IsTerminal, Read},
+    path::Path,
+};
 
 use crate::errors::FilesystemError;
 
-use super::write_to_file;
+use super::PendingFile;
 
 /// Returns the circuit's parameters and its return value, if one exists.
 /// # Examples
@@ -31,20 +35,85 @@ pub(crate) fn read_inputs_from_file<P: AsRef<Path>>(
     }
 
     let input_string = std::fs::read_to_string(file_path).unwrap();
-    let mut input_map = format.parse(&input_string, abi)?;
+    parse_inputs(&input_string, format, abi)
+}
+
+/// Returns the format of the inputs file at `path`: JSON if it has a `.json` extension and otherwise TOML.
+pub(crate) fn format_from_path(path: &Path) -> Format {
+    if path.extension().is_some_and(|extension| extension == Format::Json.ext()) {
+        Format::Json
+    } else {
+        Format::Toml
+    }
+}
+
+/// Returns the circuit's parameters and its return value, if one exists, from the inputs file at `path`.
+///
+/// Unlike [read_inputs_from_file], `path` is the full path of the file and its format is detected from its extension.
+pub(crate) fn read_inputs_from_path(
+    path: &Path,
+    abi: &Abi,
+) -> Result<(InputMap, Option<InputValue>), FilesystemError> {
+    if abi.is_empty() {
+        return Ok((BTreeMap::new(), None));
+    }
+
+    let input_string = std::fs::read_to_string(path)
+        .map_err(|_| FilesystemError::MissingInputFile(path.to_path_buf()))?;
+    parse_inputs(&input_string, format_from_path(path), abi)
+}
+
+/// Reads the circuit's inputs from stdin so that they may be piped in from another program.
+///
+/// Returns an error if stdin is a terminal rather than a pipe or file.
+pub(crate) fn read_inputs_from_stdin() -> Result<String, FilesystemError> {
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Err(FilesystemError::MissingStdinInputs);
+    }
+
+    let mut input_string = String::new();
+    stdin.read_to_string(&mut input_string).map_err(FilesystemError::StdinReadError)?;
+    Ok(input_string)
+}
+
+/// Parses the circuit's parameters and its return value, if one exists, from `input_string`.
+pub(crate) fn parse_inputs(
+    input_string: &str,
+    format: Format,
+    abi: &Abi,
+) -> Result<(InputMap, Option<InputValue>), FilesystemError> {
+    if abi.is_empty() {
+        return Ok((BTreeMap::new(), None));
+    }
+    if provides_no_inputs(input_string, &format) && !abi.parameters.is_empty() {
+        let parameter_names = abi.parameters.iter().map(|param| param.name.clone()).collect();
+        return Err(FilesystemError::NoInputsProvided(parameter_names));
+    }
+
+    let mut input_map = format.parse(input_string, abi)?;
     let return_value = input_map.remove(MAIN_RETURN_NAME);
 
     Ok((input_map, return_value))
 }
 
-pub(crate) fn write_inputs_to_file<P: AsRef<Path>>(
+/// Returns whether `input_string` is blank or, for TOML, contains nothing but comments.
+fn provides_no_inputs(input_string: &str, format: &Format) -> bool {
+    input_string
+        .lines()
+        .map(str::trim)
+        .all(|line| line.is_empty() || (matches!(format, Format::Toml) && line.starts_with('#')))
+}
+
+/// Serializes the circuit's parameters and return value into a file ready to be written to `path`.
+pub(crate) fn inputs_file<P: AsRef<Path>>(
     input_map: &InputMap,
     return_value: &Option<InputValue>,
     abi: &Abi,
     path: P,
     file_name: &str,
     format: Format,
-) -> Result<(), FilesystemError> {
+) -> Result<PendingFile, FilesystemError> {
     let file_path = path.as_ref().join(file_name).with_extension(format.ext());
 
     // We must insert the return value into the `InputMap` in order for it to be written to file.
@@ -60,9 +129,7 @@ pub(crate) fn write_inputs_to_file<P: AsRef<Path>>(
         None => format.serialize(input_map, abi)?,
     };
 
-    write_to_file(serialized_output.as_bytes(), &file_path);
-
-    Ok(())
+    Ok(PendingFile { path: file_path, contents: serialized_output.into_bytes() })
 }
 
 #[cfg(test)]
@@ -77,15 +144,14 @@ mod tests {
     };
     use tempfile::TempDir;
 
-    use super::{read_inputs_from_file, write_inputs_to_file};
+    use crate::errors::FilesystemError;
 
-    #[test]
-    fn write_and_read_recovers_inputs_and_return_value() {
-        let input_dir = TempDir::new().unwrap().into_path();
+    use super::{inputs_file, parse_inputs, read_inputs_from_file, read_inputs_from_path};
 
-        // We purposefully test a simple ABI here as we're focussing on `fs`.
-        // Tests for serializing complex types should exist in `noirc_abi`.
-        let abi = Abi {
+    // We purposefully test a simple ABI here as we're focussing on `fs`.
+    // Tests for serializing complex types should exist in `noirc_abi`.
+    fn test_abi() -> Abi {
+        Abi {
             parameters: vec![
                 AbiParameter {
                     name: "foo".into(),
@@ -107,22 +173,22 @@ mod tests {
             // Neither of these should be relevant so we leave them empty.
             param_witnesses: BTreeMap::new(),
             return_witnesses: Vec::new(),
-        };
+        }
+    }
+
+    #[test]
+    fn write_and_read_recovers_inputs_and_return_value() {
+        let input_dir = TempDir::new().unwrap().into_path();
+        let abi = test_abi();
         let input_map = BTreeMap::from([
             ("foo".to_owned(), InputValue::Field(42u128.into())),
             ("bar".to_owned(), InputValue::String("hello world".to_owned())),
         ]);
         let return_value = Some(InputValue::Field(FieldElement::zero()));
 
-        write_inputs_to_file(
-            &input_map,
-            &return_value,
-            &abi,
-            &input_dir,
-            VERIFIER_INPUT_FILE,
-            Format::Toml,
-        )
-        .unwrap();
+        inputs_file(&input_map, &return_value, &abi, &input_dir, VERIFIER_INPUT_FILE, Format::Toml)
+            .unwrap()
+            .write();
 
         let (loaded_inputs, loaded_return_value) =
             read_inputs_from_file(input_dir, VERIFIER_INPUT_FILE, Format::Toml, &abi).unwrap();
@@ -130,4 +196,43 @@ mod tests {
         assert_eq!(loaded_inputs, input_map);
         assert_eq!(loaded_return_value, return_value);
     }
+
+    #[test]
+    fn reads_inputs_in_the_format_of_the_file_extension() {
+        let input_dir = TempDir::new().unwrap();
+        let abi = test_abi();
+        let input_map = BTreeMap::from([
+            ("foo".to_owned(), InputValue::Field(7u128.into())),
+            ("bar".to_owned(), InputValue::String("hello world".to_owned())),
+        ]);
+
+        for format in [Format::Json, Format::Toml] {
+            let path = input_dir.path().join("inputs").with_extension(format.ext());
+            inputs_file(&input_map, &None, &abi, input_dir.path(), "inputs", format)
+                .unwrap()
+                .write();
+            let (loaded_inputs, loaded_return_value) = read_inputs_from_path(&path, &abi).unwrap();
+            assert_eq!(loaded_inputs, input_map);
+            assert_eq!(loaded_return_value, None);
+        }
+
+        assert!(read_inputs_from_path(&input_dir.path().join("missing.toml"), &abi).is_err());
+    }
+
+    #[test]
+    fn comment_only_inputs_report_every_missing_parameter() {
+        let abi = test_abi();
+        let input_string = "# Fill in the inputs below\n\n  # foo = 1\n";
+
+        match parse_inputs(input_string, Format::Toml, &abi) {
+            Err(FilesystemError::NoInputsProvided(names)) => assert_eq!(names, vec!["foo", "bar"]),
+            result => panic!("expected missing inputs to be reported but got {result:?}"),
+        }
+
+        // A circuit without parameters needs no inputs.
+        let abi = Abi { parameters: Vec::new(), ..test_abi() };
+        let (input_map, return_value) = parse_inputs(input_string, Format::Toml, &abi).unwrap();
+        assert!(input_map.is_empty());
+        assert_eq!(return_value, None);
+    }
 }
@@ -4,8 +4,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::errors::FilesystemError;
-
+pub(super) mod fingerprint;
 pub(super) mod inputs;
 pub(super) mod program;
 pub(super) mod proof;
@@ -18,6 +17,37 @@ pub(super) fn create_named_dir(named_dir: &Path, name: &str) -> PathBuf {
     PathBuf::from(named_dir)
 }
 
+/// A file whose contents have been computed but which has not yet been written to disk.
+pub(crate) struct PendingFile {
+    pub(crate) path: PathBuf,
+    pub(crate) contents: Vec<u8>,
+}
+
+impl PendingFile {
+    /// Writes the file to disk, creating its parent directory if necessary.
+    pub(crate) fn write(&self) -> PathBuf {
+        if let Some(parent_dir) = self.path.parent() {
+            create_named_dir(parent_dir, "output");
+        }
+        write_to_file(&self.contents, &self.path);
+        self.path.clone()
+    }
+
+    /// Reports where the file would be written along with its size, without touching the disk.
+    pub(crate) fn report(&self) {
+        println!("Would write {} ({} bytes)", self.path.display(), self.contents.len());
+    }
+
+    /// Writes the file to disk or, if `dry_run` is set, only reports where it would have been written.
+    pub(crate) fn write_or_report(&self, dry_run: bool) {
+        if dry_run {
+            self.report();
+        } else {
+            self.write();
+        }
+    }
+}
+
 pub(super) fn write_to_file(bytes: &[u8], path: &Path) -> String {
     let display = path.display();
 
@@ -31,12 +61,3 @@ pub(super) fn write_to_file(bytes: &[u8], path: &Path) -> String {
         Ok(_) => display.to_string(),
     }
 }
-
-pub(super) fn load_hex_data<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FilesystemError> {
-    let hex_data: Vec<_> = std::fs::read(&path)
-        .map_err(|_| FilesystemError::PathNotValid(path.as_ref().to_path_buf()))?;
-
-    let raw_bytes = hex::decode(hex_data).map_err(FilesystemError::HexArtifactNotValid)?;
-
-    Ok(raw_bytes)
-}
@@ -1,54 +1,116 @@
 use std::path::{Path, PathBuf};
 
-use acvm::acir::circuit::Circuit;
-use nargo::artifacts::{contract::ContractArtifact, program::ProgramArtifact};
+use acvm::acir::circuit::{Circuit, Opcode};
+use nargo::artifacts::{
+    bundle::{ProgramBundle, BUNDLE_FORMAT_VERSION},
+    contract::ContractArtifact,
+    program::ProgramArtifact,
+};
 use noirc_frontend::graph::CrateName;
 
 use crate::errors::FilesystemError;
 
-use super::{create_named_dir, write_to_file};
+use super::PendingFile;
 
 pub(crate) fn save_program_to_file<P: AsRef<Path>>(
     program_artifact: &ProgramArtifact,
     crate_name: &CrateName,
     circuit_dir: P,
 ) -> PathBuf {
+    program_file(program_artifact, crate_name, circuit_dir).write()
+}
+
+pub(crate) fn program_file<P: AsRef<Path>>(
+    program_artifact: &ProgramArtifact,
+    crate_name: &CrateName,
+    circuit_dir: P,
+) -> PendingFile {
     let circuit_name: String = crate_name.into();
-    save_build_artifact_to_file(program_artifact, &circuit_name, circuit_dir)
+    build_artifact_file(program_artifact, &circuit_name, circuit_dir)
 }
 
-/// Writes the bytecode as acir.gz
-pub(crate) fn only_acir<P: AsRef<Path>>(
+/// Serializes the bytecode ready to be written as acir.gz
+pub(crate) fn only_acir_file<P: AsRef<Path>>(
     program_artifact: &ProgramArtifact,
     circuit_dir: P,
-) -> PathBuf {
-    create_named_dir(circuit_dir.as_ref(), "target");
-    let circuit_path = circuit_dir.as_ref().join("acir").with_extension("gz");
-    let bytes = Circuit::serialize_circuit(&program_artifact.bytecode);
-    write_to_file(&bytes, &circuit_path);
+) -> PendingFile {
+    acir_file(program_artifact, "acir.gz", circuit_dir)
+}
+
+/// Serializes the bytecode ready to be written to `file_name` within `circuit_dir`
+pub(crate) fn acir_file<P: AsRef<Path>>(
+    program_artifact: &ProgramArtifact,
+    file_name: &str,
+    circuit_dir: P,
+) -> PendingFile {
+    let path = circuit_dir.as_ref().join(file_name);
+    PendingFile { path, contents: Circuit::serialize_circuit(&program_artifact.bytecode) }
+}
+
+/// Serializes the circuit as human-readable JSON to `{artifact_name}.acir.json` within `circuit_dir`
+pub(crate) fn acir_json_file<P: AsRef<Path>>(
+    circuit: &Circuit,
+    artifact_name: &str,
+    circuit_dir: P,
+) -> PendingFile {
+    let path = circuit_dir.as_ref().join(format!("{artifact_name}.acir.json"));
+    PendingFile { path, contents: serde_json::to_vec_pretty(circuit).unwrap() }
+}
+
+/// Serializes the bytecode of each of the circuit's Brillig opcodes as JSON to `{artifact_name}.brillig.json`
+/// within `circuit_dir`
+pub(crate) fn brillig_file<P: AsRef<Path>>(
+    circuit: &Circuit,
+    artifact_name: &str,
+    circuit_dir: P,
+) -> PendingFile {
+    let brillig_bytecode: Vec<_> = circuit
+        .opcodes
+        .iter()
+        .filter_map(|opcode| match opcode {
+            Opcode::Brillig(brillig) => Some(&brillig.bytecode),
+            _ => None,
+        })
+        .collect();
+    let path = circuit_dir.as_ref().join(format!("{artifact_name}.brillig.json"));
+    PendingFile { path, contents: serde_json::to_vec_pretty(&brillig_bytecode).unwrap() }
+}
+
+/// Serializes the program artifact compiled from a single entrypoint function of a package.
+pub(crate) fn entrypoint_file<P: AsRef<Path>>(
+    program_artifact: &ProgramArtifact,
+    crate_name: &CrateName,
+    function_name: &str,
+    circuit_dir: P,
+) -> PendingFile {
+    build_artifact_file(program_artifact, &format!("{crate_name}-{function_name}"), circuit_dir)
+}
 
-    circuit_path
+/// Serializes the bundle of a package's program to `{crate_name}.bundle.json` within `circuit_dir`
+pub(crate) fn bundle_file<P: AsRef<Path>>(
+    bundle: &ProgramBundle,
+    crate_name: &CrateName,
+    circuit_dir: P,
+) -> PendingFile {
+    let path = circuit_dir.as_ref().join(format!("{crate_name}.bundle.json"));
+    PendingFile { path, contents: serde_json::to_vec(bundle).unwrap() }
 }
 
-pub(crate) fn save_contract_to_file<P: AsRef<Path>>(
+pub(crate) fn contract_file<P: AsRef<Path>>(
     compiled_contract: &ContractArtifact,
     circuit_name: &str,
     circuit_dir: P,
-) -> PathBuf {
-    save_build_artifact_to_file(compiled_contract, circuit_name, circuit_dir)
+) -> PendingFile {
+    build_artifact_file(compiled_contract, circuit_name, circuit_dir)
 }
 
-fn save_build_artifact_to_file<P: AsRef<Path>, T: ?Sized + serde::Serialize>(
+fn build_artifact_file<P: AsRef<Path>, T: ?Sized + serde::Serialize>(
     build_artifact: &T,
     artifact_name: &str,
     circuit_dir: P,
-) -> PathBuf {
-    create_named_dir(circuit_dir.as_ref(), "target");
-    let circuit_path = circuit_dir.as_ref().join(artifact_name).with_extension("json");
-
-    write_to_file(&serde_json::to_vec(build_artifact).unwrap(), &circuit_path);
-
-    circuit_path
+) -> PendingFile {
+    let path = circuit_dir.as_ref().join(artifact_name).with_extension("json");
+    PendingFile { path, contents: serde_json::to_vec(build_artifact).unwrap() }
 }
 
 pub(crate) fn read_program_from_file<P: AsRef<Path>>(
@@ -63,3 +125,97 @@ pub(crate) fn read_program_from_file<P: AsRef<Path>>(
 
     Ok(program)
 }
+
+/// Reads a bundle written by `nargo compile --bundle`, checking that it was written in a format which can be read.
+pub(crate) fn load_bundle(bundle_path: &Path) -> Result<ProgramBundle, FilesystemError> {
+    let contents = std::fs::read(bundle_path)
+        .map_err(|_| FilesystemError::PathNotValid(bundle_path.to_path_buf()))?;
+    let json: serde_json::Value = serde_json::from_slice(&contents)
+        .map_err(|err| FilesystemError::ProgramSerializationError(err.to_string()))?;
+
+    // The version is checked first so that bundles with a different layout are reported as such.
+    let format_version = json.get("format_version").and_then(serde_json::Value::as_u64);
+    if format_version != Some(BUNDLE_FORMAT_VERSION.into()) {
+        return Err(FilesystemError::UnsupportedBundleVersion(
+            bundle_path.to_path_buf(),
+            format_version,
+        ));
+    }
+
+    serde_json::from_value(json)
+        .map_err(|err| FilesystemError::ProgramSerializationError(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::acir::{
+        circuit::{Circuit, Opcode},
+        native_types::{Expression, Witness},
+    };
+    use nargo::artifacts::bundle::{ProgramBundle, BUNDLE_FORMAT_VERSION};
+    use noirc_abi::{Abi, AbiParameter, AbiType, AbiVisibility};
+    use tempfile::tempdir;
+
+    use super::{bundle_file, load_bundle};
+    use crate::errors::FilesystemError;
+
+    fn test_bundle() -> ProgramBundle {
+        ProgramBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            noir_version: "0.1.0".into(),
+            backend: "acvm-backend-barretenberg".into(),
+            hash: 42,
+            abi: Abi {
+                parameters: vec![AbiParameter {
+                    name: "x".into(),
+                    typ: AbiType::Field,
+                    visibility: AbiVisibility::Public,
+                }],
+                param_witnesses: BTreeMap::from([("x".into(), vec![Witness(1)..Witness(2)])]),
+                return_type: None,
+                return_witnesses: Vec::new(),
+            },
+            bytecode: Circuit {
+                current_witness_index: 1,
+                opcodes: vec![Opcode::AssertZero(Expression::from(Witness(1)))],
+                ..Circuit::default()
+            },
+        }
+    }
+
+    #[test]
+    fn bundles_round_trip() {
+        let circuit_dir = tempdir().unwrap();
+        let bundle = test_bundle();
+
+        let bundle_path = bundle_file(&bundle, &"foo".parse().unwrap(), circuit_dir.path()).write();
+        assert_eq!(bundle_path, circuit_dir.path().join("foo.bundle.json"));
+
+        let loaded = load_bundle(&bundle_path).unwrap();
+        assert_eq!(loaded.format_version, bundle.format_version);
+        assert_eq!(loaded.noir_version, bundle.noir_version);
+        assert_eq!(loaded.backend, bundle.backend);
+        assert_eq!(loaded.hash, bundle.hash);
+        assert_eq!(loaded.bytecode, bundle.bytecode);
+        assert_eq!(
+            serde_json::to_value(&loaded.abi).unwrap(),
+            serde_json::to_value(&bundle.abi).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_bundles_with_another_format_version() {
+        let circuit_dir = tempdir().unwrap();
+        let bundle = ProgramBundle { format_version: BUNDLE_FORMAT_VERSION + 1, ..test_bundle() };
+
+        let bundle_path = bundle_file(&bundle, &"foo".parse().unwrap(), circuit_dir.path()).write();
+
+        assert!(matches!(
+            load_bundle(&bundle_path),
+            Err(FilesystemError::UnsupportedBundleVersion(_, Some(version)))
+                if version == u64::from(BUNDLE_FORMAT_VERSION + 1)
+        ));
+    }
+}
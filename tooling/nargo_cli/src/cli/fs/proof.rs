@@ -1,20 +1,221 @@
-use std::path::{Path, PathBuf};
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
 
-use nargo::constants::PROOF_EXT;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use nargo::constants::{COMPRESSED_PROOF_EXT, PROOF_EXT, PROOF_HASH_EXT};
 
 use crate::errors::FilesystemError;
 
-use super::{create_named_dir, write_to_file};
+use super::PendingFile;
+
+/// The magic bytes found at the start of any gzip file.
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
 
 pub(crate) fn save_proof_to_dir<P: AsRef<Path>>(
     proof: &[u8],
     proof_name: &str,
     proof_dir: P,
+    compress: bool,
 ) -> Result<PathBuf, FilesystemError> {
-    create_named_dir(proof_dir.as_ref(), "proof");
-    let proof_path = proof_dir.as_ref().join(proof_name).with_extension(PROOF_EXT);
+    let proof_file = proof_file(proof, proof_name, proof_dir.as_ref(), compress);
+
+    // Remove any proof previously written in the other format so that it can't be picked up by `nargo verify`.
+    let stale_proof_ext = if compress { PROOF_EXT } else { COMPRESSED_PROOF_EXT };
+    let stale_proof_path = proof_dir.as_ref().join(proof_name).with_extension(stale_proof_ext);
+    if stale_proof_path.exists() {
+        std::fs::remove_file(&stale_proof_path)
+            .map_err(|_| FilesystemError::PathNotValid(stale_proof_path))?;
+    }
+
+    Ok(proof_file.write())
+}
+
+/// Encodes the proof into a file ready to be written to `proof_dir`, either as hex or gzip compressed.
+pub(crate) fn proof_file<P: AsRef<Path>>(
+    proof: &[u8],
+    proof_name: &str,
+    proof_dir: P,
+    compress: bool,
+) -> PendingFile {
+    if compress {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(proof).expect("writing to an in-memory buffer should not fail");
+        let compressed_proof =
+            encoder.finish().expect("writing to an in-memory buffer should not fail");
+        let path = proof_dir.as_ref().join(proof_name).with_extension(COMPRESSED_PROOF_EXT);
+        PendingFile { path, contents: compressed_proof }
+    } else {
+        let path = proof_dir.as_ref().join(proof_name).with_extension(PROOF_EXT);
+        PendingFile { path, contents: hex::encode(proof).into_bytes() }
+    }
+}
+
+/// Loads the proof named `proof_name` from `proof_dir`, returning the path it was read from along with the proof.
+///
+/// Hex encoded, gzip compressed and raw binary proofs are supported, with the format being detected from the file
+/// contents.
+pub(crate) fn load_proof_from_dir<P: AsRef<Path>>(
+    proof_name: &str,
+    proof_dir: P,
+) -> Result<(PathBuf, Vec<u8>), FilesystemError> {
+    let compressed_proof_path =
+        proof_dir.as_ref().join(proof_name).with_extension(COMPRESSED_PROOF_EXT);
+    let proof_path = if compressed_proof_path.exists() {
+        compressed_proof_path
+    } else {
+        proof_dir.as_ref().join(proof_name).with_extension(PROOF_EXT)
+    };
+
+    let proof = load_proof_from_file(&proof_path)?;
+    Ok((proof_path, proof))
+}
+
+/// Loads the proof at `proof_path`, which may be in any of the formats supported by [load_proof_from_dir].
+pub(crate) fn load_proof_from_file(proof_path: &Path) -> Result<Vec<u8>, FilesystemError> {
+    let proof_data = std::fs::read(proof_path)
+        .map_err(|_| FilesystemError::PathNotValid(proof_path.to_path_buf()))?;
+
+    let proof = if proof_data.starts_with(&GZIP_MAGIC_BYTES) {
+        let mut proof = Vec::new();
+        GzDecoder::new(proof_data.as_slice()).read_to_end(&mut proof).map_err(|err| {
+            FilesystemError::ProofDecompressionError(proof_path.to_path_buf(), err)
+        })?;
+        proof
+    } else {
+        decode_proof(proof_data)
+            .map_err(|err| FilesystemError::InvalidHexProof(proof_path.to_path_buf(), err))?
+    };
+
+    Ok(proof)
+}
+
+/// Records the hash of the circuit and witness which the proof named `proof_name` was generated from.
+pub(crate) fn proof_hash_file<P: AsRef<Path>>(
+    hash: u64,
+    proof_name: &str,
+    proof_dir: P,
+) -> PendingFile {
+    let path = proof_hash_path(proof_name, proof_dir);
+    PendingFile { path, contents: hash.to_string().into_bytes() }
+}
+
+/// Removes the recorded hash of the proof named `proof_name`, which no longer describes the proof once it has been
+/// replaced by a proof whose hash was not recorded.
+pub(crate) fn remove_proof_hash<P: AsRef<Path>>(proof_name: &str, proof_dir: P) {
+    let _ = std::fs::remove_file(proof_hash_path(proof_name, proof_dir));
+}
+
+fn proof_hash_path<P: AsRef<Path>>(proof_name: &str, proof_dir: P) -> PathBuf {
+    proof_dir.as_ref().join(proof_name).with_extension(PROOF_HASH_EXT)
+}
+
+/// Loads the proof named `proof_name` from `proof_dir` if it was generated from a circuit and witness with hash `hash`.
+pub(crate) fn load_matching_proof_from_dir<P: AsRef<Path>>(
+    hash: u64,
+    proof_name: &str,
+    proof_dir: P,
+) -> Option<(PathBuf, Vec<u8>)> {
+    let hash_path = proof_hash_path(proof_name, &proof_dir);
+    let proof_hash: u64 = std::fs::read_to_string(hash_path).ok()?.trim().parse().ok()?;
+    if proof_hash != hash {
+        return None;
+    }
+    load_proof_from_dir(proof_name, proof_dir).ok()
+}
+
+/// Decodes an uncompressed proof which may have been written either as hex or as raw bytes, e.g. by another tool.
+///
+/// The proof is treated as hex if it consists only of hex digits, ignoring any whitespace such as a trailing
+/// newline or line breaks from the proof being wrapped, and as raw bytes otherwise.
+fn decode_proof(proof_data: Vec<u8>) -> Result<Vec<u8>, hex::FromHexError> {
+    let is_hex =
+        proof_data.iter().all(|byte| byte.is_ascii_hexdigit() || byte.is_ascii_whitespace());
+    if !is_hex {
+        return Ok(proof_data);
+    }
+
+    let hex_digits: Vec<u8> =
+        proof_data.into_iter().filter(|byte| !byte.is_ascii_whitespace()).collect();
+    hex::decode(hex_digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::{
+        decode_proof, load_matching_proof_from_dir, load_proof_from_dir, proof_hash_file,
+        save_proof_to_dir,
+    };
+
+    #[test]
+    fn proofs_round_trip_with_and_without_compression() {
+        let proof_dir = tempdir().unwrap();
+        let proof = vec![0, 1, 2, 3, 255, 254, 253, 0, 0, 0, 0];
+
+        let hex_path = save_proof_to_dir(&proof, "main", proof_dir.path(), false).unwrap();
+        assert_eq!(
+            load_proof_from_dir("main", proof_dir.path()).unwrap(),
+            (hex_path, proof.clone())
+        );
+
+        let compressed_path = save_proof_to_dir(&proof, "main", proof_dir.path(), true).unwrap();
+        assert!(compressed_path.to_string_lossy().ends_with(".proof.gz"));
+        assert_eq!(
+            load_proof_from_dir("main", proof_dir.path()).unwrap(),
+            (compressed_path.clone(), proof.clone())
+        );
+
+        // Switching back to an uncompressed proof should remove the compressed one.
+        save_proof_to_dir(&proof, "main", proof_dir.path(), false).unwrap();
+        assert!(!compressed_path.exists());
+    }
+
+    #[test]
+    fn loads_proofs_written_as_hex_or_raw_bytes() {
+        let proof_dir = tempdir().unwrap();
+        let proof = vec![0, 1, 2, 3, 255, 254, 253, 0, 0, 0, 0];
+        let proof_path = proof_dir.path().join("main.proof");
+
+        std::fs::write(&proof_path, format!("{}\n", hex::encode(&proof))).unwrap();
+        assert_eq!(load_proof_from_dir("main", proof_dir.path()).unwrap().1, proof);
+
+        std::fs::write(&proof_path, &proof).unwrap();
+        assert_eq!(load_proof_from_dir("main", proof_dir.path()).unwrap().1, proof);
+    }
+
+    #[test]
+    fn loads_existing_proofs_only_when_hashes_match() {
+        let proof_dir = tempdir().unwrap();
+        let proof = vec![0, 1, 2, 3];
+        assert_eq!(load_matching_proof_from_dir(1, "main", proof_dir.path()), None);
+
+        let proof_path = save_proof_to_dir(&proof, "main", proof_dir.path(), false).unwrap();
+        assert_eq!(load_matching_proof_from_dir(1, "main", proof_dir.path()), None);
+
+        proof_hash_file(1, "main", proof_dir.path()).write();
+        assert_eq!(
+            load_matching_proof_from_dir(1, "main", proof_dir.path()),
+            Some((proof_path, proof))
+        );
+        assert_eq!(load_matching_proof_from_dir(2, "main", proof_dir.path()), None);
+    }
+
+    #[test]
+    fn decodes_proofs_as_hex_only_when_they_are_hex_digits() {
+        assert_eq!(decode_proof(b"0aFf".to_vec()), Ok(vec![0x0a, 0xff]));
+        assert_eq!(decode_proof(b"0xff".to_vec()), Ok(b"0xff".to_vec()));
+        assert!(decode_proof(b"0aF".to_vec()).is_err());
+    }
 
-    write_to_file(hex::encode(proof).as_bytes(), &proof_path);
+    #[test]
+    fn ignores_whitespace_in_hex_proofs() {
+        let proof_dir = tempdir().unwrap();
+        let proof_path = proof_dir.path().join("main.proof");
 
-    Ok(proof_path)
+        std::fs::write(&proof_path, "  0001\r\n02ff\n\t\n").unwrap();
+        assert_eq!(load_proof_from_dir("main", proof_dir.path()).unwrap().1, vec![0, 1, 2, 255]);
+    }
 }
@@ -1,22 +1,47 @@
 use std::path::{Path, PathBuf};
 
 use acvm::acir::native_types::WitnessMap;
-use nargo::constants::WITNESS_EXT;
+use clap::ValueEnum;
+use nargo::artifacts::witness::{witness_to_csv, witness_to_json};
+use nargo::constants::{WITNESS_CSV_EXT, WITNESS_EXT, WITNESS_JSON_EXT};
 
 use super::{create_named_dir, write_to_file};
 use crate::errors::FilesystemError;
 
+/// The formats in which a solved witness may be saved
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum WitnessFormat {
+    /// Gzip compressed bincode, as consumed by backends
+    Binary,
+    /// JSON mapping each witness index to its value as a decimal string
+    Json,
+    /// CSV with a row for each witness index and its value as a decimal string, sorted by index
+    Csv,
+}
+
 pub(crate) fn save_witness_to_dir<P: AsRef<Path>>(
     witnesses: WitnessMap,
     witness_name: &str,
     witness_dir: P,
+    format: WitnessFormat,
 ) -> Result<PathBuf, FilesystemError> {
     create_named_dir(witness_dir.as_ref(), "witness");
-    let witness_path = witness_dir.as_ref().join(witness_name).with_extension(WITNESS_EXT);
 
-    let buf: Vec<u8> = witnesses.try_into()?;
+    let (buf, extension): (Vec<u8>, _) = match format {
+        WitnessFormat::Binary => (witnesses.try_into()?, WITNESS_EXT),
+        WitnessFormat::Json => (witness_to_json(witnesses).into_bytes(), WITNESS_JSON_EXT),
+        WitnessFormat::Csv => (witness_to_csv(witnesses).into_bytes(), WITNESS_CSV_EXT),
+    };
+    let witness_path = witness_dir.as_ref().join(witness_name).with_extension(extension);
 
     write_to_file(buf.as_slice(), &witness_path);
 
     Ok(witness_path)
 }
+
+/// Reads a witness previously saved in the binary format, i.e. gzip compressed bincode.
+pub(crate) fn read_witness_from_file(witness_path: &Path) -> Result<WitnessMap, FilesystemError> {
+    let bytes = std::fs::read(witness_path)
+        .map_err(|err| FilesystemError::WitnessReadError(witness_path.to_path_buf(), err))?;
+    Ok(WitnessMap::try_from(bytes.as_slice())?)
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use acvm::acir::circuit::ExpressionWidth;
+use acvm::acir::circuit::{ExpressionWidth, Opcode};
 use backend_interface::BackendError;
 use clap::Args;
 use iter_extended::vecmap;
@@ -8,27 +8,28 @@ use nargo::{
     artifacts::debug::DebugArtifact, insert_all_files_for_workspace_into_file_manager,
     package::Package, parse_all,
 };
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::{
-    file_manager_with_stdlib, CompileOptions, CompiledContract, CompiledProgram,
-    NOIR_ARTIFACT_VERSION_STRING,
-};
+use nargo_toml::{get_package_manifest, PackageSelection};
+use noirc_driver::{file_manager_with_stdlib, CompileOptions, CompiledContract, CompiledProgram};
 use noirc_errors::{debug_info::OpCodesCount, Location};
 use noirc_frontend::graph::CrateName;
-use prettytable::{row, table, Row};
+use prettytable::{cell, row, Row, Table};
 use rayon::prelude::*;
 use serde::Serialize;
 
 use crate::backends::Backend;
 use crate::errors::CliError;
 
-use super::{compile_cmd::compile_workspace, NargoConfig};
+use super::{
+    compile_cmd::{apply_profile, compile_workspace},
+    resolve_workspace, NargoConfig,
+};
 
 /// Provides detailed information on a circuit
 ///
 /// Current information provided:
 /// 1. The number of ACIR opcodes
 /// 2. Counts the final number gates in the circuit used by a backend
+/// 3. Optionally, how many of the ACIR opcodes are constrained and how many are unconstrained
 #[derive(Debug, Clone, Args)]
 pub(crate) struct InfoCommand {
     /// The name of the package to detail
@@ -46,6 +47,10 @@ pub(crate) struct InfoCommand {
     #[clap(long, hide = true)]
     profile_info: bool,
 
+    /// Also report how many ACIR opcodes add constraints and how many are unconstrained (directives and Brillig calls)
+    #[clap(long)]
+    include_unconstrained: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -59,26 +64,17 @@ pub(crate) fn run(
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
     let selection = args.package.map_or(default_selection, PackageSelection::Selected);
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
-    )?;
+    let workspace = resolve_workspace(&config, &toml_path, selection)?;
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
 
-    let expression_width = args
-        .compile_options
-        .expression_width
-        .unwrap_or_else(|| backend.get_backend_info_or_default());
-    let (compiled_programs, compiled_contracts) = compile_workspace(
-        &workspace_file_manager,
-        &parsed_files,
-        &workspace,
-        &args.compile_options,
-    )?;
+    let expression_width =
+        compile_options.expression_width.unwrap_or_else(|| backend.get_backend_info_or_default());
+    let (compiled_programs, compiled_contracts) =
+        compile_workspace(&workspace_file_manager, &parsed_files, &workspace, &compile_options)?;
 
     let compiled_programs = vecmap(compiled_programs, |program| {
         nargo::ops::transform_program(program, expression_width)
@@ -109,13 +105,26 @@ pub(crate) fn run(
     let program_info = binary_packages
         .par_bridge()
         .map(|(package, program)| {
-            count_opcodes_and_gates_in_program(backend, program, package, expression_width)
+            count_opcodes_and_gates_in_program(
+                backend,
+                program,
+                package,
+                expression_width,
+                args.include_unconstrained,
+            )
         })
         .collect::<Result<_, _>>()?;
 
     let contract_info = compiled_contracts
         .into_par_iter()
-        .map(|contract| count_opcodes_and_gates_in_contract(backend, contract, expression_width))
+        .map(|contract| {
+            count_opcodes_and_gates_in_contract(
+                backend,
+                contract,
+                expression_width,
+                args.include_unconstrained,
+            )
+        })
         .collect::<Result<_, _>>()?;
 
     let info_report = InfoReport { programs: program_info, contracts: contract_info };
@@ -126,7 +135,11 @@ pub(crate) fn run(
     } else {
         // Otherwise print human-readable table.
         if !info_report.programs.is_empty() {
-            let mut program_table = table!([Fm->"Package", Fm->"Expression Width", Fm->"ACIR Opcodes", Fm->"Backend Circuit Size"]);
+            let mut program_titles = row![Fm->"Package", Fm->"Expression Width", Fm->"ACIR Opcodes", Fm->"Backend Circuit Size"];
+            if args.include_unconstrained {
+                add_opcode_counts_titles(&mut program_titles);
+            }
+            let mut program_table = Table::init(vec![program_titles]);
 
             for program in info_report.programs {
                 program_table.add_row(program.into());
@@ -134,13 +147,17 @@ pub(crate) fn run(
             program_table.printstd();
         }
         if !info_report.contracts.is_empty() {
-            let mut contract_table = table!([
+            let mut contract_titles = row![
                 Fm->"Contract",
                 Fm->"Function",
                 Fm->"Expression Width",
                 Fm->"ACIR Opcodes",
                 Fm->"Backend Circuit Size"
-            ]);
+            ];
+            if args.include_unconstrained {
+                add_opcode_counts_titles(&mut contract_titles);
+            }
+            let mut contract_table = Table::init(vec![contract_titles]);
             for contract_info in info_report.contracts {
                 let contract_rows: Vec<Row> = contract_info.into();
                 for row in contract_rows {
@@ -204,6 +221,36 @@ fn byte_index(string: &str, index: u32) -> usize {
     byte_index
 }
 
+/// The number of ACIR opcodes in a circuit which add constraints and which are unconstrained.
+///
+/// Unconstrained opcodes (directives and Brillig calls) only assign witnesses and so cost little to prove compared to
+/// constrained opcodes, which include black box function calls and memory operations.
+#[derive(Debug, Serialize)]
+struct OpcodeCounts {
+    constrained: usize,
+    unconstrained: usize,
+}
+
+impl OpcodeCounts {
+    fn from_opcodes(opcodes: &[Opcode]) -> Self {
+        let unconstrained = opcodes
+            .iter()
+            .filter(|opcode| matches!(opcode, Opcode::Directive(_) | Opcode::Brillig(_)))
+            .count();
+        OpcodeCounts { constrained: opcodes.len() - unconstrained, unconstrained }
+    }
+
+    fn add_cells(&self, row: &mut Row) {
+        row.add_cell(cell!(Fc->format!("{}", self.constrained)));
+        row.add_cell(cell!(Fc->format!("{}", self.unconstrained)));
+    }
+}
+
+fn add_opcode_counts_titles(row: &mut Row) {
+    row.add_cell(cell!(Fm->"Constrained Opcodes"));
+    row.add_cell(cell!(Fm->"Unconstrained Opcodes"));
+}
+
 #[derive(Debug, Default, Serialize)]
 struct InfoReport {
     programs: Vec<ProgramInfo>,
@@ -217,16 +264,22 @@ struct ProgramInfo {
     expression_width: ExpressionWidth,
     acir_opcodes: usize,
     circuit_size: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opcode_counts: Option<OpcodeCounts>,
 }
 
 impl From<ProgramInfo> for Row {
     fn from(program_info: ProgramInfo) -> Self {
-        row![
+        let mut row = row![
             Fm->format!("{}", program_info.name),
             format!("{:?}", program_info.expression_width),
             Fc->format!("{}", program_info.acir_opcodes),
             Fc->format!("{}", program_info.circuit_size),
-        ]
+        ];
+        if let Some(opcode_counts) = &program_info.opcode_counts {
+            opcode_counts.add_cells(&mut row);
+        }
+        row
     }
 }
 
@@ -243,18 +296,24 @@ struct FunctionInfo {
     name: String,
     acir_opcodes: usize,
     circuit_size: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opcode_counts: Option<OpcodeCounts>,
 }
 
 impl From<ContractInfo> for Vec<Row> {
     fn from(contract_info: ContractInfo) -> Self {
         vecmap(contract_info.functions, |function| {
-            row![
+            let mut row = row![
                 Fm->format!("{}", contract_info.name),
                 Fc->format!("{}", function.name),
                 format!("{:?}", contract_info.expression_width),
                 Fc->format!("{}", function.acir_opcodes),
                 Fc->format!("{}", function.circuit_size),
-            ]
+            ];
+            if let Some(opcode_counts) = &function.opcode_counts {
+                opcode_counts.add_cells(&mut row);
+            }
+            row
         })
     }
 }
@@ -264,12 +323,15 @@ fn count_opcodes_and_gates_in_program(
     compiled_program: CompiledProgram,
     package: &Package,
     expression_width: ExpressionWidth,
+    include_unconstrained: bool,
 ) -> Result<ProgramInfo, CliError> {
     Ok(ProgramInfo {
         name: package.name.to_string(),
         expression_width,
         acir_opcodes: compiled_program.circuit.opcodes.len(),
         circuit_size: backend.get_exact_circuit_size(&compiled_program.circuit)?,
+        opcode_counts: include_unconstrained
+            .then(|| OpcodeCounts::from_opcodes(&compiled_program.circuit.opcodes)),
     })
 }
 
@@ -277,6 +339,7 @@ fn count_opcodes_and_gates_in_contract(
     backend: &Backend,
     contract: CompiledContract,
     expression_width: ExpressionWidth,
+    include_unconstrained: bool,
 ) -> Result<ContractInfo, CliError> {
     let functions = contract
         .functions
@@ -286,9 +349,44 @@ fn count_opcodes_and_gates_in_contract(
                 name: function.name,
                 acir_opcodes: function.bytecode.opcodes.len(),
                 circuit_size: backend.get_exact_circuit_size(&function.bytecode)?,
+                opcode_counts: include_unconstrained
+                    .then(|| OpcodeCounts::from_opcodes(&function.bytecode.opcodes)),
             })
         })
         .collect::<Result<_, _>>()?;
 
     Ok(ContractInfo { name: contract.name, expression_width, functions })
 }
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::{
+        circuit::{brillig::Brillig, directives::Directive, Opcode},
+        native_types::{Expression, Witness},
+    };
+
+    use super::OpcodeCounts;
+
+    #[test]
+    fn counts_directives_and_brillig_as_unconstrained() {
+        let opcodes = vec![
+            Opcode::AssertZero(Expression::default()),
+            Opcode::Directive(Directive::ToLeRadix {
+                a: Expression::default(),
+                b: vec![Witness(1)],
+                radix: 2,
+            }),
+            Opcode::Brillig(Brillig {
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                bytecode: Vec::new(),
+                predicate: None,
+            }),
+            Opcode::AssertZero(Expression::default()),
+        ];
+
+        let opcode_counts = OpcodeCounts::from_opcodes(&opcodes);
+        assert_eq!(opcode_counts.constrained, 2);
+        assert_eq!(opcode_counts.unconstrained, 2);
+    }
+}
@@ -8,6 +8,7 @@ use nargo::package::PackageType;
 use noirc_driver::NOIRC_VERSION;
 use noirc_frontend::graph::CrateName;
 use std::path::PathBuf;
+use std::process::Command;
 
 /// Create a Noir project in the current directory.
 #[derive(Debug, Clone, Args)]
@@ -49,7 +50,7 @@ pub(crate) fn run(args: InitCommand, config: NargoConfig) -> Result<(), CliError
     } else {
         PackageType::Binary
     };
-    initialize_project(config.program_dir, package_name, package_type);
+    initialize_project(config.program_dir, package_name, package_type, git_author(), None);
     Ok(())
 }
 
@@ -58,19 +59,13 @@ pub(crate) fn initialize_project(
     package_dir: PathBuf,
     package_name: CrateName,
     package_type: PackageType,
+    author: Option<String>,
+    license: Option<String>,
 ) {
     let src_dir = package_dir.join(SRC_DIR);
     create_named_dir(&src_dir, "src");
 
-    let toml_contents = format!(
-        r#"[package]
-name = "{package_name}"
-type = "{package_type}"
-authors = [""]
-compiler_version = ">={NOIRC_VERSION}"
-
-[dependencies]"#
-    );
+    let toml_contents = manifest_contents(&package_name, package_type, author, license);
 
     write_to_file(toml_contents.as_bytes(), &package_dir.join(PKG_FILE));
     // This uses the `match` syntax instead of `if` so we get a compile error when we add new package types (which likely need new template files)
@@ -83,3 +78,82 @@ compiler_version = ">={NOIRC_VERSION}"
     };
     println!("Project successfully created! It is located at {}", package_dir.display());
 }
+
+/// Returns the contents of the `Nargo.toml` for a newly created package.
+fn manifest_contents(
+    package_name: &CrateName,
+    package_type: PackageType,
+    author: Option<String>,
+    license: Option<String>,
+) -> String {
+    // Author and license are user provided so we let `toml` handle quoting and escaping them.
+    let authors = toml::Value::Array(vec![author.unwrap_or_default().into()]);
+    let license = license.map(|license| format!("license = {}\n", toml::Value::from(license)));
+    let license = license.unwrap_or_default();
+
+    format!(
+        r#"[package]
+name = "{package_name}"
+type = "{package_type}"
+authors = {authors}
+{license}compiler_version = ">={NOIRC_VERSION}"
+
+[dependencies]"#
+    )
+}
+
+/// Returns the author of a new package as `Name <email>`, based upon the user's git configuration.
+pub(crate) fn git_author() -> Option<String> {
+    let git_config = |key: &str| {
+        let output = Command::new("git").args(["config", "--get", key]).output().ok()?;
+        let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        (output.status.success() && !value.is_empty()).then_some(value)
+    };
+
+    match (git_config("user.name"), git_config("user.email")) {
+        (Some(name), Some(email)) => Some(format!("{name} <{email}>")),
+        (Some(name), None) => Some(name),
+        (None, Some(email)) => Some(format!("<{email}>")),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nargo::package::PackageType;
+    use noirc_driver::NOIRC_VERSION;
+
+    use super::manifest_contents;
+
+    #[test]
+    fn manifest_contains_author_and_license() {
+        let manifest = manifest_contents(
+            &"my_package".parse().unwrap(),
+            PackageType::Binary,
+            Some("Jane \"JD\" Doe <jane@example.com>".to_string()),
+            Some("MIT".to_string()),
+        );
+
+        assert_eq!(
+            manifest,
+            format!(
+                r#"[package]
+name = "my_package"
+type = "bin"
+authors = ["Jane \"JD\" Doe <jane@example.com>"]
+license = "MIT"
+compiler_version = ">={NOIRC_VERSION}"
+
+[dependencies]"#
+            )
+        );
+    }
+
+    #[test]
+    fn manifest_defaults_to_empty_author() {
+        let manifest =
+            manifest_contents(&"my_package".parse().unwrap(), PackageType::Library, None, None);
+
+        assert!(manifest.contains("authors = [\"\"]\ncompiler_version"));
+    }
+}
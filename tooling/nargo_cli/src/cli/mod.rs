@@ -1,21 +1,32 @@
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use const_format::formatcp;
-use nargo_toml::find_package_root;
+use nargo::workspace::Workspace;
+use nargo_toml::{
+    check_dependency_conflicts, find_package_root, get_package_manifest,
+    package_root_from_manifest, read_profile_from_toml, resolve_workspace_from_toml_with_options,
+    PackageSelection, ResolveOptions, ResolvedWorkspace,
+};
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use termcolor::ColorChoice;
 
 use color_eyre::eyre;
 
-use crate::backends::get_active_backend;
+use crate::backends::select_backend;
+use crate::errors::CliError;
 
 mod fs;
 
+mod abi_diff_cmd;
 mod backend_cmd;
 mod check_cmd;
 mod codegen_verifier_cmd;
 mod compile_cmd;
 mod dap_cmd;
 mod debug_cmd;
+mod doc_cmd;
 mod execute_cmd;
 mod export_cmd;
 mod fmt_cmd;
@@ -24,7 +35,9 @@ mod init_cmd;
 mod lsp_cmd;
 mod new_cmd;
 mod prove_cmd;
+mod selftest_cmd;
 mod test_cmd;
+mod tree_cmd;
 mod verify_cmd;
 
 const GIT_HASH: &str = env!("GIT_COMMIT");
@@ -55,6 +68,80 @@ pub(crate) struct NargoConfig {
     // REMINDER: Also change this flag in the LSP test lens if renamed
     #[arg(long, hide = true, global = true, default_value = "./")]
     program_dir: PathBuf,
+
+    /// Path to the `Nargo.toml` of the package to operate on.
+    /// This takes precedence over searching for a manifest from the current directory.
+    #[arg(long, global = true)]
+    manifest_path: Option<PathBuf>,
+
+    /// Whether to use colors when printing output
+    #[arg(long, global = true, value_enum, default_value_t = ColorOption::Auto)]
+    color: ColorOption,
+
+    /// Fail if the same dependency is resolved to conflicting versions across the dependency graph
+    #[arg(long, global = true)]
+    strict_dependencies: bool,
+
+    /// Clone git dependencies again rather than reusing previously cloned copies, replacing the cached copies
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Print additional information, such as the commit which each git dependency resolved to
+    /// and the time taken to generate each proof
+    #[arg(long, global = true)]
+    verbose: bool,
+
+    /// The backend to use, overriding the `NARGO_BACKEND` environment variable and the `Nargo.toml` profile
+    #[arg(long = "backend", value_name = "BACKEND", global = true)]
+    backend_override: Option<String>,
+
+    /// The number of threads used to compile packages and functions in parallel, defaulting to the number of CPUs.
+    /// Use `--jobs 1` to do all work sequentially
+    #[arg(long, short = 'j', value_name = "N", global = true)]
+    jobs: Option<NonZeroUsize>,
+
+    /// Print at most this many warnings when compiling each package, followed by a count of any further warnings
+    #[arg(long, value_name = "N", global = true)]
+    max_warnings: Option<usize>,
+
+    /// The format in which to print an error which causes nargo to fail
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Human)]
+    error_format: ErrorFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ErrorFormat {
+    /// A human-readable message
+    Human,
+    /// A JSON object with the kind of error, its message and any fields specific to that kind of error
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ColorOption {
+    /// Use colors if stderr is a terminal and `NO_COLOR` is not set
+    Auto,
+    /// Always use colors
+    Always,
+    /// Never use colors
+    Never,
+}
+
+impl ColorOption {
+    pub(crate) fn color_choice(self) -> ColorChoice {
+        match self {
+            ColorOption::Auto => {
+                let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                if no_color || !std::io::stderr().is_terminal() {
+                    ColorChoice::Never
+                } else {
+                    ColorChoice::Always
+                }
+            }
+            ColorOption::Always => ColorChoice::Always,
+            ColorOption::Never => ColorChoice::Never,
+        }
+    }
 }
 
 #[non_exhaustive]
@@ -77,14 +164,42 @@ enum NargoCommand {
     Verify(verify_cmd::VerifyCommand),
     Test(test_cmd::TestCommand),
     Info(info_cmd::InfoCommand),
+    Tree(tree_cmd::TreeCommand),
+    Doc(doc_cmd::DocCommand),
+    AbiDiff(abi_diff_cmd::AbiDiffCommand),
     Lsp(lsp_cmd::LspCommand),
+    Selftest(selftest_cmd::SelftestCommand),
     #[command(hide = true)]
     Dap(dap_cmd::DapCommand),
 }
 
 #[cfg(not(feature = "codegen-docs"))]
 pub(crate) fn start_cli() -> eyre::Result<()> {
-    let NargoCli { command, mut config } = NargoCli::parse();
+    let NargoCli { command, config } = NargoCli::parse();
+
+    let error_format = config.error_format;
+    match run_command(command, config) {
+        Err(error) if error_format == ErrorFormat::Json => {
+            eprintln!("{}", error.to_json());
+            std::process::exit(1);
+        }
+        result => Ok(result?),
+    }
+}
+
+#[cfg(not(feature = "codegen-docs"))]
+fn run_command(command: NargoCommand, mut config: NargoConfig) -> Result<(), CliError> {
+    noirc_errors::reporter::set_color_choice(config.color.color_choice());
+    if let Some(max_warnings) = config.max_warnings {
+        noirc_errors::reporter::set_max_warnings(max_warnings);
+    }
+
+    if let Some(jobs) = config.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs.get())
+            .build_global()
+            .map_err(|err| CliError::Generic(err.to_string()))?;
+    }
 
     // If the provided `program_dir` is relative, make it absolute by joining it to the current directory.
     if !config.program_dir.is_absolute() {
@@ -92,6 +207,9 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
     }
 
     // Search through parent directories to find package root if necessary.
+    // Commands given everything they need without a package, such as `nargo execute --file`, are run from wherever
+    // they are.
+    let mut manifest_backend = None;
     if !matches!(
         command,
         NargoCommand::New(_)
@@ -99,12 +217,24 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
             | NargoCommand::Lsp(_)
             | NargoCommand::Backend(_)
             | NargoCommand::Dap(_)
+            | NargoCommand::Selftest(_)
+            | NargoCommand::Execute(execute_cmd::ExecuteCommand { file: Some(_), .. })
+            | NargoCommand::Verify(verify_cmd::VerifyCommand { abi: Some(_), .. })
     ) {
-        config.program_dir = find_package_root(&config.program_dir)?;
+        // An explicit `--manifest-path` takes precedence over searching from `program_dir`.
+        config.program_dir = match &config.manifest_path {
+            Some(manifest_path) => {
+                package_root_from_manifest(&config.program_dir.join(manifest_path))?
+            }
+            None => find_package_root(&config.program_dir)?,
+        };
+
+        manifest_backend =
+            read_profile_from_toml(&get_package_manifest(&config.program_dir)?)?.backend;
     }
 
-    let active_backend = get_active_backend();
-    let backend = crate::backends::Backend::new(active_backend);
+    let backend_name = select_backend(config.backend_override.clone(), manifest_backend);
+    let backend = crate::backends::Backend::new(backend_name);
 
     match command {
         NargoCommand::New(args) => new_cmd::run(&backend, args, config),
@@ -121,11 +251,60 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::CodegenVerifier(args) => codegen_verifier_cmd::run(&backend, args, config),
         NargoCommand::Backend(args) => backend_cmd::run(args),
         NargoCommand::Lsp(args) => lsp_cmd::run(&backend, args, config),
+        NargoCommand::Selftest(args) => selftest_cmd::run(&backend, args),
         NargoCommand::Dap(args) => dap_cmd::run(&backend, args, config),
         NargoCommand::Fmt(args) => fmt_cmd::run(args, config),
-    }?;
+        NargoCommand::Tree(args) => tree_cmd::run(args, config),
+        NargoCommand::Doc(args) => doc_cmd::run(args, config),
+        NargoCommand::AbiDiff(args) => abi_diff_cmd::run(args, config),
+    }
+}
 
-    Ok(())
+/// Resolves the workspace at `toml_path`, applying the global `--no-cache`, `--strict-dependencies`
+/// and `--verbose` flags to the resolution of the selected packages.
+///
+/// Any warnings about the workspace's manifests are printed to stderr.
+pub(crate) fn resolve_workspace(
+    config: &NargoConfig,
+    toml_path: &Path,
+    selection: PackageSelection,
+) -> Result<Workspace, CliError> {
+    Ok(resolve_workspace_with_dependencies(config, toml_path, selection)?.workspace)
+}
+
+/// Resolves the workspace at `toml_path` as in [resolve_workspace], also returning what was learnt about the
+/// dependencies of the selected packages.
+pub(crate) fn resolve_workspace_with_dependencies(
+    config: &NargoConfig,
+    toml_path: &Path,
+    selection: PackageSelection,
+) -> Result<ResolvedWorkspace, CliError> {
+    let options = ResolveOptions { bypass_git_cache: config.no_cache };
+    let resolved = resolve_workspace_from_toml_with_options(
+        toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        options,
+    )?;
+
+    for warning in &resolved.workspace.warnings {
+        eprintln!("Warning: {warning}");
+    }
+
+    if config.strict_dependencies {
+        check_dependency_conflicts(&resolved.workspace)?;
+    }
+
+    if config.verbose {
+        for dependency in &resolved.git_dependencies {
+            eprintln!(
+                "Resolved {} at {} to commit {}",
+                dependency.git, dependency.tag, dependency.commit
+            );
+        }
+    }
+
+    Ok(resolved)
 }
 
 #[cfg(feature = "codegen-docs")]
@@ -1,11 +1,15 @@
 use crate::backends::Backend;
-use crate::errors::CliError;
+use crate::errors::{CliError, FilesystemError};
 
-use super::{init_cmd::initialize_project, NargoConfig};
+use super::{
+    fs::PendingFile,
+    init_cmd::{git_author, initialize_project},
+    NargoConfig,
+};
 use clap::Args;
 use nargo::package::PackageType;
 use noirc_frontend::graph::CrateName;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Create a Noir project in a new directory.
 #[derive(Debug, Clone, Args)]
@@ -28,8 +32,26 @@ pub(crate) struct NewCommand {
     /// Use a contract template
     #[arg(long, conflicts_with = "lib", conflicts_with = "bin")]
     pub(crate) contract: bool,
+
+    /// Author of the package, e.g. "Name <email>" [default: taken from git config]
+    #[clap(long)]
+    author: Option<String>,
+
+    /// License of the package, e.g. MIT
+    #[clap(long)]
+    license: Option<String>,
+
+    /// Copy the contents of this directory into the new project rather than using a built-in template.
+    /// Occurrences of `{{package_name}}` and `{{author}}` in its files are replaced
+    #[clap(long, value_name = "DIR", conflicts_with_all = ["lib", "bin", "contract", "license"])]
+    template: Option<PathBuf>,
 }
 
+/// The token in template files which is replaced with the name of the new package.
+const PACKAGE_NAME_TOKEN: &str = "{{package_name}}";
+/// The token in template files which is replaced with the author of the new package.
+const AUTHOR_TOKEN: &str = "{{author}}";
+
 pub(crate) fn run(
     // Backend is currently unused, but we might want to use it to inform the "new" template in the future
     _backend: &Backend,
@@ -56,6 +78,99 @@ pub(crate) fn run(
     } else {
         PackageType::Binary
     };
-    initialize_project(package_dir, package_name, package_type);
+    let author = args.author.or_else(git_author);
+    if let Some(template_dir) = &args.template {
+        let files = read_template(template_dir, &package_dir, &package_name, author.as_deref())
+            .map_err(|err| FilesystemError::TemplateReadError(template_dir.clone(), err))?;
+        for file in files {
+            file.write();
+        }
+        println!("Project successfully created! It is located at {}", package_dir.display());
+        return Ok(());
+    }
+    initialize_project(package_dir, package_name, package_type, author, args.license);
     Ok(())
 }
+
+/// Reads every file in `template_dir`, returning the files to create at the same relative paths in `package_dir`.
+///
+/// The substitution tokens are only replaced in files which are valid UTF-8, other files are copied unchanged.
+/// Any `.git` directory in the template is skipped.
+fn read_template(
+    template_dir: &Path,
+    package_dir: &Path,
+    package_name: &CrateName,
+    author: Option<&str>,
+) -> std::io::Result<Vec<PendingFile>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(template_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let destination = package_dir.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != ".git" {
+                files.extend(read_template(&path, &destination, package_name, author)?);
+            }
+            continue;
+        }
+
+        let contents = std::fs::read(&path)?;
+        let contents = match String::from_utf8(contents) {
+            Ok(text) => text
+                .replace(PACKAGE_NAME_TOKEN, &package_name.to_string())
+                .replace(AUTHOR_TOKEN, author.unwrap_or_default())
+                .into_bytes(),
+            Err(err) => err.into_bytes(),
+        };
+        files.push(PendingFile { path: destination, contents });
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_template;
+
+    #[test]
+    fn substitutes_tokens_in_template_files() {
+        let template_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(template_dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(template_dir.path().join(".git")).unwrap();
+        std::fs::write(
+            template_dir.path().join("Nargo.toml"),
+            "[package]\nname = \"{{package_name}}\"\nauthors = [\"{{author}}\"]\n",
+        )
+        .unwrap();
+        std::fs::write(template_dir.path().join("src").join("main.nr"), "fn main() {}").unwrap();
+        std::fs::write(template_dir.path().join("logo.bin"), [0xFF, 0xFE]).unwrap();
+        std::fs::write(template_dir.path().join(".git").join("HEAD"), "main").unwrap();
+
+        let package_dir = template_dir.path().join("my_package");
+        let mut files = read_template(
+            template_dir.path(),
+            &package_dir,
+            &"my_package".parse().unwrap(),
+            Some("Jane Doe"),
+        )
+        .unwrap();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let files: Vec<_> = files
+            .into_iter()
+            .map(|file| {
+                (file.path.strip_prefix(&package_dir).unwrap().to_path_buf(), file.contents)
+            })
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                (
+                    "Nargo.toml".into(),
+                    b"[package]\nname = \"my_package\"\nauthors = [\"Jane Doe\"]\n".to_vec()
+                ),
+                ("logo.bin".into(), vec![0xFF, 0xFE]),
+                ("src/main.nr".into(), b"fn main() {}".to_vec()),
+            ]
+        );
+    }
+}
@@ -1,23 +1,34 @@
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use acvm::acir::circuit::Circuit;
 use clap::Args;
+use nargo::artifacts::bundle::ProgramBundle;
 use nargo::constants::{PROVER_INPUT_FILE, VERIFIER_INPUT_FILE};
 use nargo::ops::compile_program;
 use nargo::package::Package;
 use nargo::workspace::Workspace;
 use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use nargo_toml::{get_package_manifest, PackageSelection};
 use noirc_abi::input_parser::Format;
-use noirc_driver::{
-    file_manager_with_stdlib, CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
-};
+use noirc_driver::{file_manager_with_stdlib, CompileOptions, CompiledProgram};
 use noirc_frontend::graph::CrateName;
 
-use super::compile_cmd::report_errors;
+use super::compile_cmd::{apply_profile, report_errors};
 use super::fs::{
-    inputs::{read_inputs_from_file, write_inputs_to_file},
-    proof::save_proof_to_dir,
+    inputs::{inputs_file, read_inputs_from_file},
+    program::load_bundle,
+    proof::{
+        load_matching_proof_from_dir, proof_file, proof_hash_file, remove_proof_hash,
+        save_proof_to_dir,
+    },
+};
+use super::{resolve_workspace, NargoConfig};
+use crate::{
+    backends::Backend,
+    cli::execute_cmd::{execute_program, public_inputs_file},
+    errors::CliError,
 };
-use super::NargoConfig;
-use crate::{backends::Backend, cli::execute_cmd::execute_program, errors::CliError};
 
 /// Create proof for this program. The proof is returned as a hex encoded string.
 #[derive(Debug, Clone, Args)]
@@ -30,10 +41,33 @@ pub(crate) struct ProveCommand {
     #[clap(long, short, default_value = VERIFIER_INPUT_FILE)]
     verifier_name: String,
 
+    /// Write the public inputs and return value to this file rather than to the package's verifier inputs,
+    /// as JSON if it has a `.json` extension and otherwise as TOML
+    #[clap(long, value_name = "FILE", conflicts_with = "workspace")]
+    public_inputs_out: Option<PathBuf>,
+
     /// Verify proof after proving
     #[arg(long)]
     verify: bool,
 
+    /// Write the proof as a gzip compressed `.proof.gz` file rather than as hex
+    #[arg(long)]
+    compress: bool,
+
+    /// Report the files which would be written, and their sizes, without writing them to disk
+    #[arg(long)]
+    dry_run: bool,
+
+    /// The directory to write proofs to, relative to the workspace root.
+    /// Overrides `proofs-dir` in the `[profile]` section of Nargo.toml.
+    #[arg(long)]
+    proofs_dir: Option<PathBuf>,
+
+    /// Reuse an existing proof rather than generating a new one if it was generated from the same circuit and inputs,
+    /// by the same version of the backend
+    #[arg(long)]
+    keep_existing: bool,
+
     /// The name of the package to prove
     #[clap(long, conflicts_with = "workspace")]
     package: Option<CrateName>,
@@ -42,6 +76,14 @@ pub(crate) struct ProveCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// Prove the program in this bundle, written by `nargo compile --bundle`, rather than compiling the package
+    #[clap(long, value_name = "FILE", conflicts_with = "workspace")]
+    bundle: Option<PathBuf>,
+
+    /// Use the bundle even if it was compiled for a backend other than the one in use
+    #[clap(long, requires = "bundle")]
+    allow_backend_mismatch: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 
@@ -59,54 +101,110 @@ pub(crate) fn run(
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
     let selection = args.package.map_or(default_selection, PackageSelection::Selected);
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
-    )?;
+    let mut workspace = resolve_workspace(&config, &toml_path, selection)?;
+    if let Some(proofs_dir) = args.proofs_dir {
+        workspace.profile.proofs_dir = Some(proofs_dir);
+    }
+    let binary_packages: Vec<_> =
+        workspace.into_iter().filter(|package| package.is_binary()).collect();
+
+    let prove = |package: &Package, compiled_program: CompiledProgram| {
+        prove_package(
+            backend,
+            &workspace,
+            package,
+            compiled_program,
+            &args.prover_name,
+            &args.verifier_name,
+            args.public_inputs_out.as_deref(),
+            args.verify,
+            args.compress,
+            args.dry_run,
+            config.verbose,
+            args.keep_existing,
+            args.oracle_resolver.as_deref(),
+        )
+    };
+
+    if let Some(bundle_path) = &args.bundle {
+        let package = single_binary_package(&binary_packages, "A bundle")?;
+        let bundle = load_bundle_for_backend(bundle_path, backend, args.allow_backend_mismatch)?;
+        return prove(package, bundle.into());
+    }
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
 
-    let expression_width = args
-        .compile_options
-        .expression_width
-        .unwrap_or_else(|| backend.get_backend_info_or_default());
-    let binary_packages = workspace.into_iter().filter(|package| package.is_binary());
+    let expression_width =
+        compile_options.expression_width.unwrap_or_else(|| backend.get_backend_info_or_default());
     for package in binary_packages {
         let compilation_result = compile_program(
             &workspace_file_manager,
             &parsed_files,
             package,
-            &args.compile_options,
+            &compile_options,
             None,
         );
 
         let compiled_program = report_errors(
             compilation_result,
             &workspace_file_manager,
-            args.compile_options.deny_warnings,
-            args.compile_options.silence_warnings,
+            compile_options.deny_warnings,
+            compile_options.silence_warnings,
         )?;
 
         let compiled_program = nargo::ops::transform_program(compiled_program, expression_width);
-
-        prove_package(
-            backend,
-            &workspace,
-            package,
-            compiled_program,
-            &args.prover_name,
-            &args.verifier_name,
-            args.verify,
-            args.oracle_resolver.as_deref(),
-        )?;
+        prove(package, compiled_program)?;
     }
 
     Ok(())
 }
 
+/// Returns the only binary package selected, for inputs such as a bundle which describe a single program.
+///
+/// `input` names what the package is used with in the error reported when several packages, or none, were selected.
+pub(crate) fn single_binary_package<'a>(
+    binary_packages: &[&'a Package],
+    input: &str,
+) -> Result<&'a Package, CliError> {
+    match binary_packages {
+        [package] => Ok(package),
+        _ => Err(CliError::Generic(format!(
+            "{input} holds the program of a single package but {} binary packages were selected. \
+             Select one with `--package`",
+            binary_packages.len()
+        ))),
+    }
+}
+
+/// Loads the bundle at `bundle_path`, failing if it was compiled for a backend other than `backend` unless
+/// `allow_backend_mismatch` is set.
+pub(crate) fn load_bundle_for_backend(
+    bundle_path: &Path,
+    backend: &Backend,
+    allow_backend_mismatch: bool,
+) -> Result<ProgramBundle, CliError> {
+    let bundle = load_bundle(bundle_path)?;
+    if bundle.backend != backend.name() {
+        if !allow_backend_mismatch {
+            return Err(CliError::BundleBackendMismatch {
+                path: bundle_path.to_path_buf(),
+                bundle_backend: bundle.backend,
+                backend: backend.name().to_string(),
+            });
+        }
+        eprintln!(
+            "Warning: {} was compiled for the backend `{}` but is being used with `{}`",
+            bundle_path.display(),
+            bundle.backend,
+            backend.name()
+        );
+    }
+    Ok(bundle)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn prove_package(
     backend: &Backend,
@@ -115,30 +213,77 @@ pub(crate) fn prove_package(
     compiled_program: CompiledProgram,
     prover_name: &str,
     verifier_name: &str,
+    public_inputs_out: Option<&Path>,
     check_proof: bool,
+    compress_proof: bool,
+    dry_run: bool,
+    verbose: bool,
+    keep_existing: bool,
     foreign_call_resolver_url: Option<&str>,
 ) -> Result<(), CliError> {
+    // A proof of a program without public inputs doesn't commit to any values which the verifier can check.
+    let abi = &compiled_program.abi;
+    if !abi.parameters.is_empty() && !abi.has_public_inputs() {
+        eprintln!(
+            "Warning: [{}] program has no public parameters or return value so its proofs do not commit to any public values",
+            package.name
+        );
+    }
+
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &compiled_program.abi)?;
 
     let solved_witness =
-        execute_program(&compiled_program, &inputs_map, foreign_call_resolver_url)?;
+        execute_program(&compiled_program, &inputs_map, foreign_call_resolver_url, false, None)?;
 
-    // Write public inputs into Verifier.toml
+    // Serialize public inputs for Verifier.toml, this is only written once the proof has been generated
     let public_abi = compiled_program.abi.public_abi();
     let (public_inputs, return_value) = public_abi.decode(&solved_witness)?;
 
-    write_inputs_to_file(
-        &public_inputs,
-        &return_value,
-        &public_abi,
-        &package.root_dir,
-        verifier_name,
-        Format::Toml,
-    )?;
+    let verifier_inputs_file = match public_inputs_out {
+        Some(path) => public_inputs_file(&public_abi, &solved_witness, path)?,
+        None => inputs_file(
+            &public_inputs,
+            &return_value,
+            &public_abi,
+            &package.root_dir,
+            verifier_name,
+            Format::Toml,
+        )?,
+    };
+
+    let proof_name = String::from(&package.name);
+    let proof_dir = workspace.proofs_directory_path();
+    // Proofs are only interchangeable if they were made by the same version of the same backend.
+    let proof_hash = if keep_existing {
+        let circuit = Circuit::serialize_circuit(&compiled_program.circuit);
+        Some(fxhash::hash64(&(backend.name(), backend.version()?, circuit, &solved_witness)))
+    } else {
+        None
+    };
+    let existing_proof =
+        proof_hash.and_then(|hash| load_matching_proof_from_dir(hash, &proof_name, &proof_dir));
 
-    let proof = backend.prove(&compiled_program.circuit, solved_witness)?;
+    let proof = match &existing_proof {
+        Some((proof_path, proof)) => {
+            println!("[{}] Reusing existing proof at {}", package.name, proof_path.display());
+            proof.clone()
+        }
+        None => {
+            let proving_start = Instant::now();
+            let proof = backend.prove(&compiled_program.circuit, solved_witness)?;
+            if verbose {
+                let proving_time = proving_start.elapsed();
+                println!(
+                    "[{}] Proof generated in {proving_time:?} ({} bytes)",
+                    package.name,
+                    proof.len()
+                );
+            }
+            proof
+        }
+    };
 
     if check_proof {
         let public_inputs = public_abi.encode(&public_inputs, return_value)?;
@@ -149,7 +294,28 @@ pub(crate) fn prove_package(
         }
     }
 
-    save_proof_to_dir(&proof, &String::from(&package.name), workspace.proofs_directory_path())?;
+    // The hash is only recorded when proofs are being reused.
+    let hash_file = proof_hash.map(|hash| proof_hash_file(hash, &proof_name, &proof_dir));
+    if dry_run {
+        verifier_inputs_file.report();
+        if existing_proof.is_none() {
+            proof_file(&proof, &proof_name, &proof_dir, compress_proof).report();
+            if let Some(hash_file) = &hash_file {
+                hash_file.report();
+            }
+        }
+    } else {
+        verifier_inputs_file.write();
+        if existing_proof.is_none() {
+            save_proof_to_dir(&proof, &proof_name, &proof_dir, compress_proof)?;
+            match &hash_file {
+                Some(hash_file) => {
+                    hash_file.write();
+                }
+                None => remove_proof_hash(&proof_name, &proof_dir),
+            }
+        }
+    }
 
     Ok(())
 }
@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use acvm::FieldElement;
+use clap::Args;
+use nargo::ops::compile_program;
+use nargo::package::{Package, PackageType};
+use nargo::parse_all;
+use noirc_abi::input_parser::InputValue;
+use noirc_abi::InputMap;
+use noirc_driver::{file_manager_with_stdlib, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+
+use super::compile_cmd::report_errors;
+use super::execute_cmd::execute_program;
+use crate::backends::Backend;
+use crate::errors::CliError;
+
+/// Compile, prove and verify a small built-in program to check that nargo and its backend are working
+#[derive(Debug, Clone, Args)]
+pub(crate) struct SelftestCommand;
+
+/// The program which is proven by the self-test. It has both a private and a public input so that the public
+/// inputs are passed to the backend when verifying.
+const SELFTEST_SOURCE: &str = "fn main(x: Field, y: pub Field) {
+    assert(x != y);
+}
+";
+
+pub(crate) fn run(backend: &Backend, _args: SelftestCommand) -> Result<(), CliError> {
+    // The program only exists in the file manager so no files are written to disk.
+    let root_dir = PathBuf::from("selftest");
+    let entry_path = Path::new("src").join("main.nr");
+    let package = Package {
+        version: None,
+        compiler_required_version: Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        root_dir: root_dir.clone(),
+        package_type: PackageType::Binary,
+        entry_path: root_dir.join(&entry_path),
+        name: "selftest".parse().expect("selftest should be a valid crate name"),
+        dependencies: BTreeMap::new(),
+        include: Vec::new(),
+        exclude: Vec::new(),
+    };
+    let mut file_manager = file_manager_with_stdlib(&root_dir);
+    file_manager.add_file_with_source(&entry_path, SELFTEST_SOURCE.to_string());
+
+    let compiled_program = run_stage("compile", || {
+        let parsed_files = parse_all(&file_manager);
+        let compile_options = CompileOptions::default();
+        let compilation_result =
+            compile_program(&file_manager, &parsed_files, &package, &compile_options, None);
+        let compiled_program = report_errors(compilation_result, &file_manager, false, false)?;
+        Ok(nargo::ops::transform_program(compiled_program, backend.get_backend_info_or_default()))
+    })?;
+
+    let inputs_map: InputMap = BTreeMap::from([
+        ("x".to_string(), InputValue::Field(FieldElement::from(1_u128))),
+        ("y".to_string(), InputValue::Field(FieldElement::from(2_u128))),
+    ]);
+    let (solved_witness, public_inputs) = run_stage("solve", || {
+        let solved_witness = execute_program(&compiled_program, &inputs_map, None, false, None)?;
+        let public_abi = compiled_program.abi.clone().public_abi();
+        let (public_inputs, return_value) = public_abi.decode(&solved_witness)?;
+        let public_inputs = public_abi.encode(&public_inputs, return_value)?;
+        Ok((solved_witness, public_inputs))
+    })?;
+
+    let proof =
+        run_stage("prove", || Ok(backend.prove(&compiled_program.circuit, solved_witness)?))?;
+
+    run_stage("verify", || {
+        if backend.verify(&proof, public_inputs, &compiled_program.circuit)? {
+            Ok(())
+        } else {
+            Err(CliError::InvalidProof("".into()))
+        }
+    })?;
+
+    println!("Self-test passed");
+    Ok(())
+}
+
+/// Runs a single stage of the self-test, reporting whether it succeeded.
+fn run_stage<T>(
+    stage: &'static str,
+    f: impl FnOnce() -> Result<T, CliError>,
+) -> Result<T, CliError> {
+    match f() {
+        Ok(result) => {
+            println!("[selftest] {stage}: ok");
+            Ok(result)
+        }
+        Err(error) => Err(CliError::SelftestFailed { stage, source: Box::new(error) }),
+    }
+}
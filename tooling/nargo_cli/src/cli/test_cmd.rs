@@ -8,19 +8,20 @@ use nargo::{
     insert_all_files_for_workspace_into_file_manager,
     ops::{run_test, TestStatus},
     package::Package,
-    parse_all, prepare_package,
+    parse_all, prepare_package_with_features,
 };
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::{file_manager_with_stdlib, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use nargo_toml::{get_package_manifest, PackageSelection};
+use noirc_driver::{file_manager_with_stdlib, CompileOptions};
 use noirc_frontend::{
     graph::CrateName,
     hir::{FunctionNameMatch, ParsedFiles},
 };
-use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use termcolor::{Color, ColorSpec, StandardStream, WriteColor};
 
 use crate::{backends::Backend, cli::check_cmd::check_crate_and_report_errors, errors::CliError};
 
-use super::NargoConfig;
+use super::compile_cmd::apply_profile;
+use super::{resolve_workspace, NargoConfig};
 
 /// Run the tests for this program
 #[derive(Debug, Clone, Args)]
@@ -36,6 +37,10 @@ pub(crate) struct TestCommand {
     #[clap(long)]
     exact: bool,
 
+    /// Stop running tests after the first failure rather than running every test and reporting all failures
+    #[arg(long)]
+    fail_fast: bool,
+
     /// The name of the package to test
     #[clap(long, conflicts_with = "workspace")]
     package: Option<CrateName>,
@@ -61,15 +66,12 @@ pub(crate) fn run(
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
     let selection = args.package.map_or(default_selection, PackageSelection::Selected);
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
-    )?;
+    let workspace = resolve_workspace(&config, &toml_path, selection)?;
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
 
     let pattern = match &args.test_name {
         Some(name) => {
@@ -84,22 +86,27 @@ pub(crate) fn run(
 
     let blackbox_solver = Bn254BlackBoxSolver::new();
 
-    let test_reports: Vec<Vec<(String, TestStatus)>> = workspace
-        .into_iter()
-        .map(|package| {
-            run_tests(
-                &workspace_file_manager,
-                &parsed_files,
-                &blackbox_solver,
-                package,
-                pattern,
-                args.show_output,
-                args.oracle_resolver.as_deref(),
-                &args.compile_options,
-            )
-        })
-        .collect::<Result<_, _>>()?;
-    let test_report: Vec<(String, TestStatus)> = test_reports.into_iter().flatten().collect();
+    let mut test_report: Vec<(String, TestStatus)> = Vec::new();
+    let mut packages_tested = 0;
+    for package in &workspace {
+        let package_report = run_tests(
+            &workspace_file_manager,
+            &parsed_files,
+            &blackbox_solver,
+            package,
+            pattern,
+            args.show_output,
+            args.fail_fast,
+            args.oracle_resolver.as_deref(),
+            &compile_options,
+        )?;
+        test_report.extend(package_report);
+        packages_tested += 1;
+
+        if args.fail_fast && test_report.iter().any(|(_, status)| status.failed()) {
+            break;
+        }
+    }
 
     if test_report.is_empty() {
         match &pattern {
@@ -116,7 +123,13 @@ pub(crate) fn run(
         };
     }
 
-    if test_report.iter().any(|(_, status)| !matches!(status, TestStatus::Fail { .. })) {
+    let count_failed = test_report.iter().filter(|(_, status)| status.failed()).count();
+    if packages_tested > 1 {
+        let count_passed = test_report.len() - count_failed;
+        eprintln!("Test result: {count_passed} passed, {count_failed} failed");
+    }
+
+    if count_failed == 0 {
         Ok(())
     } else {
         Err(CliError::Generic(String::new()))
@@ -131,10 +144,16 @@ fn run_tests<S: BlackBoxFunctionSolver>(
     package: &Package,
     fn_name: FunctionNameMatch,
     show_output: bool,
+    fail_fast: bool,
     foreign_call_resolver_url: Option<&str>,
     compile_options: &CompileOptions,
 ) -> Result<Vec<(String, TestStatus)>, CliError> {
-    let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
+    let (mut context, crate_id) = prepare_package_with_features(
+        file_manager,
+        parsed_files,
+        package,
+        &compile_options.features,
+    );
     check_crate_and_report_errors(
         &mut context,
         crate_id,
@@ -149,7 +168,7 @@ fn run_tests<S: BlackBoxFunctionSolver>(
     let plural = if count_all == 1 { "" } else { "s" };
     println!("[{}] Running {count_all} test function{plural}", package.name);
 
-    let writer = StandardStream::stderr(ColorChoice::Always);
+    let writer = StandardStream::stderr(noirc_errors::reporter::color_choice());
     let mut writer = writer.lock();
 
     let mut test_report: Vec<(String, TestStatus)> = Vec::new();
@@ -198,22 +217,27 @@ fn run_tests<S: BlackBoxFunctionSolver>(
             }
         }
 
+        let failed = test_status.failed();
         test_report.push((test_name, test_status));
 
         writer.reset().expect("Failed to reset writer");
+
+        if fail_fast && failed {
+            break;
+        }
     }
 
     write!(writer, "[{}] ", package.name).expect("Failed to write to stderr");
 
-    let count_failed =
-        test_report.iter().filter(|(_, status)| !matches!(status, TestStatus::Pass)).count();
+    let count_failed = test_report.iter().filter(|(_, status)| status.failed()).count();
+    let count_skipped = count_all - test_report.len();
     if count_failed == 0 {
         writer.set_color(ColorSpec::new().set_fg(Some(Color::Green))).expect("Failed to set color");
         write!(writer, "{count_all} test{plural} passed").expect("Failed to write to stderr");
         writer.reset().expect("Failed to reset writer");
         writeln!(writer).expect("Failed to write to stderr");
     } else {
-        let count_passed = count_all - count_failed;
+        let count_passed = test_report.len() - count_failed;
         let plural_failed = if count_failed == 1 { "" } else { "s" };
         let plural_passed = if count_passed == 1 { "" } else { "s" };
 
@@ -226,9 +250,14 @@ fn run_tests<S: BlackBoxFunctionSolver>(
         }
 
         writer.set_color(ColorSpec::new().set_fg(Some(Color::Red))).expect("Failed to set color");
-        writeln!(writer, "{count_failed} test{plural_failed} failed")
+        write!(writer, "{count_failed} test{plural_failed} failed")
             .expect("Failed to write to stderr");
         writer.reset().expect("Failed to reset writer");
+
+        if count_skipped != 0 {
+            write!(writer, ", {count_skipped} skipped").expect("Failed to write to stderr");
+        }
+        writeln!(writer).expect("Failed to write to stderr");
     }
 
     Ok(test_report)
@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use clap::Args;
+use nargo_toml::{
+    get_package_manifest, DependencyGraph, DependencySource, GraphPackage, PackageSelection,
+};
+use noirc_frontend::graph::CrateName;
+
+use super::{resolve_workspace_with_dependencies, NargoConfig};
+use crate::errors::CliError;
+
+/// Display the dependency graph of packages in the workspace
+#[derive(Debug, Clone, Args)]
+pub(crate) struct TreeCommand {
+    /// The name of the package to display the dependencies of
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Display the dependencies of all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// Output the dependency graph in Graphviz DOT format
+    #[clap(long)]
+    dot: bool,
+}
+
+pub(crate) fn run(args: TreeCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let graph =
+        resolve_workspace_with_dependencies(&config, &toml_path, selection)?.dependency_graph;
+
+    if args.dot {
+        print!("{}", dependency_graph_dot(&graph));
+    } else {
+        for (index, package) in graph.packages.iter().enumerate() {
+            if package.is_workspace_member {
+                print!("{}", dependency_tree(&graph, index));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Describes where a dependency was requested from, e.g. `git: https://github.com/noir-lang/my_lib @ v1.0.0`.
+fn source_label(source: &DependencySource) -> String {
+    match source {
+        DependencySource::Git { git, tag, directory: Some(directory), .. } => {
+            format!("git: {git} @ {tag}, directory: {directory}")
+        }
+        DependencySource::Git { git, tag, directory: None, .. } => format!("git: {git} @ {tag}"),
+        DependencySource::Path { path } => format!("path: {path}"),
+        DependencySource::Registry { version } => format!("registry: {version}"),
+    }
+}
+
+fn package_label(package: &GraphPackage, source: &str) -> String {
+    match &package.version {
+        Some(version) => format!("{} v{version} ({source})", package.name),
+        None => format!("{} ({source})", package.name),
+    }
+}
+
+/// Renders the dependencies of the package at `index` in `graph` as an indented tree.
+///
+/// The dependencies of each package are only shown where it first appears. Later appearances of a package
+/// which has dependencies are marked with `(*)`.
+fn dependency_tree(graph: &DependencyGraph, index: usize) -> String {
+    let package = &graph.packages[index];
+    let root_source = format!("root: {}", package.root_dir.display());
+    let mut output = format!("{}\n", package_label(package, &root_source));
+    let mut shown = BTreeSet::from([index]);
+    write_dependency_tree(graph, index, "", &mut shown, &mut output);
+    output
+}
+
+fn write_dependency_tree(
+    graph: &DependencyGraph,
+    index: usize,
+    prefix: &str,
+    shown: &mut BTreeSet<usize>,
+    output: &mut String,
+) {
+    let edges: Vec<_> = graph.edges.iter().filter(|edge| edge.from == index).collect();
+    for (position, edge) in edges.iter().enumerate() {
+        let is_last = position + 1 == edges.len();
+        let (branch, indent) =
+            if is_last { ("└── ", "    ") } else { ("├── ", "│   ") };
+        let label = package_label(&graph.packages[edge.to], &source_label(&edge.source));
+
+        let is_repeated = !shown.insert(edge.to);
+        let has_dependencies = graph.edges.iter().any(|other| other.from == edge.to);
+        let marker = if is_repeated && has_dependencies { " (*)" } else { "" };
+        writeln!(output, "{prefix}{branch}{label}{marker}")
+            .expect("writing to a string should not fail");
+        if !is_repeated {
+            write_dependency_tree(graph, edge.to, &format!("{prefix}{indent}"), shown, output);
+        }
+    }
+}
+
+/// Renders `graph` in Graphviz DOT format.
+///
+/// Each resolved package is a node, identified by its root directory, with an edge to each of its dependencies
+/// labelled with where the dependency was requested from.
+fn dependency_graph_dot(graph: &DependencyGraph) -> String {
+    let node_id = |index: usize| graph.packages[index].root_dir.display().to_string();
+
+    let mut nodes = BTreeMap::new();
+    for (index, package) in graph.packages.iter().enumerate() {
+        let label = match &package.version {
+            Some(version) => format!("{} v{version}", package.name),
+            None => package.name.clone(),
+        };
+        nodes.insert(node_id(index), label);
+    }
+    let edges: BTreeSet<_> = graph
+        .edges
+        .iter()
+        .map(|edge| (node_id(edge.from), node_id(edge.to), source_label(&edge.source)))
+        .collect();
+
+    let mut output = String::from("digraph dependencies {\n");
+    for (id, label) in nodes {
+        writeln!(output, "    \"{}\" [label=\"{}\"];", escape_dot(&id), escape_dot(&label))
+            .expect("writing to a string should not fail");
+    }
+    for (from, to, label) in edges {
+        writeln!(
+            output,
+            "    \"{}\" -> \"{}\" [label=\"{}\"];",
+            escape_dot(&from),
+            escape_dot(&to),
+            escape_dot(&label)
+        )
+        .expect("writing to a string should not fail");
+    }
+    output.push_str("}\n");
+    output
+}
+
+fn escape_dot(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use nargo_toml::{DependencyEdge, DependencyGraph, DependencySource, GraphPackage};
+
+    use super::{dependency_graph_dot, dependency_tree};
+
+    fn package(name: &str, is_workspace_member: bool) -> GraphPackage {
+        GraphPackage {
+            name: name.to_string(),
+            version: None,
+            package_type: "lib".to_string(),
+            root_dir: PathBuf::from(format!("/{name}")),
+            is_workspace_member,
+        }
+    }
+
+    fn edge(from: usize, to: usize, name: &str, source: DependencySource) -> DependencyEdge {
+        DependencyEdge { from, to, name: name.to_string(), source }
+    }
+
+    fn dependency_graph() -> DependencyGraph {
+        let git = || DependencySource::Git {
+            git: "https://github.com/noir-lang/shared".to_string(),
+            tag: "v1.0.0".to_string(),
+            directory: None,
+            commit: "0123abc".to_string(),
+        };
+        DependencyGraph {
+            packages: vec![
+                package("bin", true),
+                package("local", false),
+                package("shared", false),
+                package("util", false),
+            ],
+            edges: vec![
+                edge(2, 3, "util", DependencySource::Path { path: "../util".to_string() }),
+                edge(1, 2, "shared", git()),
+                edge(0, 1, "local", DependencySource::Path { path: "../local".to_string() }),
+                edge(0, 2, "shared", git()),
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_dependency_tree() {
+        let expected = "\
+bin (root: /bin)
+├── local (path: ../local)
+│   └── shared (git: https://github.com/noir-lang/shared @ v1.0.0)
+│       └── util (path: ../util)
+└── shared (git: https://github.com/noir-lang/shared @ v1.0.0) (*)
+";
+        assert_eq!(dependency_tree(&dependency_graph(), 0), expected);
+    }
+
+    #[test]
+    fn renders_dependency_graph_as_dot() {
+        let expected = r#"digraph dependencies {
+    "/bin" [label="bin"];
+    "/local" [label="local"];
+    "/shared" [label="shared"];
+    "/util" [label="util"];
+    "/bin" -> "/local" [label="path: ../local"];
+    "/bin" -> "/shared" [label="git: https://github.com/noir-lang/shared @ v1.0.0"];
+    "/local" -> "/shared" [label="git: https://github.com/noir-lang/shared @ v1.0.0"];
+    "/shared" -> "/util" [label="path: ../util"];
+}
+"#;
+        assert_eq!(dependency_graph_dot(&dependency_graph()), expected);
+    }
+}
@@ -1,19 +1,30 @@
-use super::compile_cmd::report_errors;
-use super::fs::{inputs::read_inputs_from_file, load_hex_data};
-use super::NargoConfig;
-use crate::{backends::Backend, errors::CliError};
+use std::path::{Path, PathBuf};
 
+use super::abi_diff_cmd::read_abi_from_file;
+use super::compile_cmd::{apply_profile, report_errors};
+use super::fs::{
+    inputs::{read_inputs_from_file, read_inputs_from_path},
+    program::read_program_from_file,
+    proof::{load_proof_from_dir, load_proof_from_file},
+};
+use super::prove_cmd::{load_bundle_for_backend, single_binary_package};
+use super::{resolve_workspace, NargoConfig};
+use crate::{
+    backends::Backend,
+    errors::{CliError, FilesystemError},
+};
+
+use acvm::acir::native_types::WitnessMap;
 use clap::Args;
-use nargo::constants::{PROOF_EXT, VERIFIER_INPUT_FILE};
+use nargo::constants::{COMPRESSED_PROOF_EXT, PROOF_EXT, VERIFIER_INPUT_FILE};
 use nargo::ops::compile_program;
 use nargo::package::Package;
 use nargo::workspace::Workspace;
 use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use nargo_toml::{get_package_manifest, PackageSelection};
 use noirc_abi::input_parser::Format;
-use noirc_driver::{
-    file_manager_with_stdlib, CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
-};
+use noirc_abi::Abi;
+use noirc_driver::{file_manager_with_stdlib, CompileOptions, CompiledProgram};
 use noirc_frontend::graph::CrateName;
 
 /// Given a proof and a program, verify whether the proof is valid
@@ -23,6 +34,11 @@ pub(crate) struct VerifyCommand {
     #[clap(long, short, default_value = VERIFIER_INPUT_FILE)]
     verifier_name: String,
 
+    /// Read the public inputs from this file rather than from the package's verifier inputs.
+    /// The file is parsed as JSON if it has a .json extension and otherwise as TOML.
+    #[clap(long, value_name = "FILE", conflicts_with = "batch")]
+    public_inputs: Option<PathBuf>,
+
     /// The name of the package verify
     #[clap(long, conflicts_with = "workspace")]
     package: Option<CrateName>,
@@ -31,6 +47,50 @@ pub(crate) struct VerifyCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// Verify every proof in this directory rather than the package's proof.
+    ///
+    /// The public inputs for `<name>.proof` are read from `<name>.toml` in the same directory if it exists,
+    /// otherwise the package's verifier inputs are used. Fails if the directory contains no proofs.
+    #[clap(long)]
+    batch: Option<PathBuf>,
+
+    /// Verify against this verification key, as written by the backend, rather than recompiling the circuit to
+    /// derive one. The package's ABI is read from its compiled artifact.
+    #[clap(long, value_name = "FILE", conflicts_with_all = ["batch", "workspace"])]
+    key: Option<PathBuf>,
+
+    /// Verify without a package, reading the ABI used to encode the public inputs from this file. The file may
+    /// contain the ABI itself or a program artifact.
+    ///
+    /// The verification key, proof and public inputs must be given with `--key`, `--proof` and `--public-inputs`.
+    #[clap(
+        long,
+        value_name = "FILE",
+        requires = "key",
+        requires = "proof",
+        requires = "public_inputs",
+        conflicts_with_all = ["package", "workspace", "bundle", "proofs_dir"]
+    )]
+    pub(crate) abi: Option<PathBuf>,
+
+    /// The proof to verify when verifying without a package using `--abi`
+    #[clap(long, value_name = "FILE", requires = "abi")]
+    proof: Option<PathBuf>,
+
+    /// Verify against the program in this bundle, written by `nargo compile --bundle`, rather than compiling the
+    /// package
+    #[clap(long, value_name = "FILE", conflicts_with_all = ["workspace", "key"])]
+    bundle: Option<PathBuf>,
+
+    /// Use the bundle even if it was compiled for a backend other than the one in use
+    #[clap(long, requires = "bundle")]
+    allow_backend_mismatch: bool,
+
+    /// The directory to read proofs from, relative to the workspace root.
+    /// Overrides `proofs-dir` in the `[profile]` section of Nargo.toml.
+    #[clap(long)]
+    proofs_dir: Option<PathBuf>,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -40,44 +100,89 @@ pub(crate) fn run(
     args: VerifyCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
+    if let (Some(abi_path), Some(key_path), Some(proof_path), Some(public_inputs_path)) =
+        (&args.abi, &args.key, &args.proof, &args.public_inputs)
+    {
+        return verify_proof_with_abi(backend, key_path, abi_path, proof_path, public_inputs_path);
+    }
+
     let toml_path = get_package_manifest(&config.program_dir)?;
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
     let selection = args.package.map_or(default_selection, PackageSelection::Selected);
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
-    )?;
+    let mut workspace = resolve_workspace(&config, &toml_path, selection)?;
+    if let Some(proofs_dir) = args.proofs_dir {
+        workspace.profile.proofs_dir = Some(proofs_dir);
+    }
+    let binary_packages: Vec<_> =
+        workspace.into_iter().filter(|package| package.is_binary()).collect();
+
+    if let Some(key_path) = &args.key {
+        // A verification key belongs to a single circuit, so it can't be used to verify several packages.
+        let [package] = binary_packages.as_slice() else {
+            return Err(CliError::Generic(format!(
+                "A verification key can only be used to verify a single binary package but {} were selected. \
+                 Select one with `--package`",
+                binary_packages.len()
+            )));
+        };
+
+        let vk = read_verification_key(key_path)?;
+        return verify_package_with_key(
+            backend,
+            &workspace,
+            package,
+            &vk,
+            &args.verifier_name,
+            args.public_inputs.as_deref(),
+        );
+    }
+
+    let verify = |package: &Package, compiled_program: CompiledProgram| match &args.batch {
+        Some(batch_dir) => {
+            verify_batch(backend, package, compiled_program, batch_dir, &args.verifier_name)
+        }
+        None => verify_package(
+            backend,
+            &workspace,
+            package,
+            compiled_program,
+            &args.verifier_name,
+            args.public_inputs.as_deref(),
+        ),
+    };
+
+    if let Some(bundle_path) = &args.bundle {
+        let package = single_binary_package(&binary_packages, "A bundle")?;
+        let bundle = load_bundle_for_backend(bundle_path, backend, args.allow_backend_mismatch)?;
+        return verify(package, bundle.into());
+    }
 
     let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
     let parsed_files = parse_all(&workspace_file_manager);
+    let compile_options = apply_profile(&args.compile_options, &workspace.profile);
 
-    let expression_width = args
-        .compile_options
-        .expression_width
-        .unwrap_or_else(|| backend.get_backend_info_or_default());
-    let binary_packages = workspace.into_iter().filter(|package| package.is_binary());
+    let expression_width =
+        compile_options.expression_width.unwrap_or_else(|| backend.get_backend_info_or_default());
     for package in binary_packages {
         let compilation_result = compile_program(
             &workspace_file_manager,
             &parsed_files,
             package,
-            &args.compile_options,
+            &compile_options,
             None,
         );
 
         let compiled_program = report_errors(
             compilation_result,
             &workspace_file_manager,
-            args.compile_options.deny_warnings,
-            args.compile_options.silence_warnings,
+            compile_options.deny_warnings,
+            compile_options.silence_warnings,
         )?;
 
         let compiled_program = nargo::ops::transform_program(compiled_program, expression_width);
-
-        verify_package(backend, &workspace, package, compiled_program, &args.verifier_name)?;
+        verify(package, compiled_program)?;
     }
 
     Ok(())
@@ -89,18 +194,13 @@ fn verify_package(
     package: &Package,
     compiled_program: CompiledProgram,
     verifier_name: &str,
+    public_inputs_path: Option<&Path>,
 ) -> Result<(), CliError> {
-    // Load public inputs (if any) from `verifier_name`.
-    let public_abi = compiled_program.abi.public_abi();
-    let (public_inputs_map, return_value) =
-        read_inputs_from_file(&package.root_dir, verifier_name, Format::Toml, &public_abi)?;
-
-    let public_inputs = public_abi.encode(&public_inputs_map, return_value)?;
+    let public_inputs =
+        read_public_inputs(package, compiled_program.abi, verifier_name, public_inputs_path)?;
 
-    let proof_path =
-        workspace.proofs_directory_path().join(package.name.to_string()).with_extension(PROOF_EXT);
-
-    let proof = load_hex_data(&proof_path)?;
+    let (proof_path, proof) =
+        load_proof_from_dir(&package.name.to_string(), workspace.proofs_directory_path())?;
 
     let valid_proof = backend.verify(&proof, public_inputs, &compiled_program.circuit)?;
 
@@ -110,3 +210,193 @@ fn verify_package(
         Err(CliError::InvalidProof(proof_path))
     }
 }
+
+/// Verifies the package's proof against the verification key `vk` without compiling the package.
+///
+/// The public inputs are encoded using the ABI of the package's compiled artifact.
+fn verify_package_with_key(
+    backend: &Backend,
+    workspace: &Workspace,
+    package: &Package,
+    vk: &[u8],
+    verifier_name: &str,
+    public_inputs_path: Option<&Path>,
+) -> Result<(), CliError> {
+    let abi = read_program_from_file(workspace.package_build_path(package))?.abi;
+    let public_inputs = read_public_inputs(package, abi, verifier_name, public_inputs_path)?;
+
+    let (proof_path, proof) =
+        load_proof_from_dir(&package.name.to_string(), workspace.proofs_directory_path())?;
+
+    if backend.verify_with_vk(&proof, public_inputs, vk)? {
+        Ok(())
+    } else {
+        Err(CliError::InvalidProof(proof_path))
+    }
+}
+
+/// Verifies the proof at `proof_path` against the verification key at `key_path`, without a package.
+///
+/// The public inputs at `public_inputs_path` are encoded using the ABI read from `abi_path`.
+fn verify_proof_with_abi(
+    backend: &Backend,
+    key_path: &Path,
+    abi_path: &Path,
+    proof_path: &Path,
+    public_inputs_path: &Path,
+) -> Result<(), CliError> {
+    let vk = read_verification_key(key_path)?;
+    let public_abi = read_abi_from_file(abi_path)?.public_abi();
+    let (public_inputs_map, return_value) = read_inputs_from_path(public_inputs_path, &public_abi)?;
+    let public_inputs = public_abi.encode(&public_inputs_map, return_value)?;
+    let proof = load_proof_from_file(proof_path)?;
+
+    if backend.verify_with_vk(&proof, public_inputs, &vk)? {
+        Ok(())
+    } else {
+        Err(CliError::InvalidProof(proof_path.to_path_buf()))
+    }
+}
+
+fn read_verification_key(key_path: &Path) -> Result<Vec<u8>, FilesystemError> {
+    std::fs::read(key_path)
+        .map_err(|err| FilesystemError::VerificationKeyReadError(key_path.to_path_buf(), err))
+}
+
+/// Loads the public inputs (if any) from `public_inputs_path` if given, otherwise from `verifier_name`, and encodes
+/// them according to `abi`.
+fn read_public_inputs(
+    package: &Package,
+    abi: Abi,
+    verifier_name: &str,
+    public_inputs_path: Option<&Path>,
+) -> Result<WitnessMap, CliError> {
+    let public_abi = abi.public_abi();
+    let (public_inputs_map, return_value) = match public_inputs_path {
+        Some(path) => read_inputs_from_path(path, &public_abi)?,
+        None => read_inputs_from_file(&package.root_dir, verifier_name, Format::Toml, &public_abi)?,
+    };
+
+    Ok(public_abi.encode(&public_inputs_map, return_value)?)
+}
+
+/// Verifies every proof in `batch_dir` against `compiled_program`, printing a summary of the results.
+///
+/// Returns an error if any of the proofs fail to verify.
+fn verify_batch(
+    backend: &Backend,
+    package: &Package,
+    compiled_program: CompiledProgram,
+    batch_dir: &Path,
+    verifier_name: &str,
+) -> Result<(), CliError> {
+    let proof_names = batch_proof_names(batch_dir)?;
+    if proof_names.is_empty() {
+        return Err(FilesystemError::NoProofsFound(batch_dir.to_path_buf()).into());
+    }
+    let public_abi = compiled_program.abi.clone().public_abi();
+
+    let mut failed = 0;
+    for proof_name in &proof_names {
+        let result = verify_batch_proof(
+            backend,
+            &compiled_program,
+            &public_abi,
+            batch_dir,
+            proof_name,
+            &package.root_dir,
+            verifier_name,
+        );
+
+        match result {
+            Ok(true) => println!("[{}] {proof_name} ... ok", package.name),
+            Ok(false) => {
+                failed += 1;
+                println!("[{}] {proof_name} ... FAILED", package.name);
+            }
+            Err(err) => {
+                failed += 1;
+                println!("[{}] {proof_name} ... FAILED ({err})", package.name);
+            }
+        }
+    }
+
+    let total = proof_names.len();
+    println!("[{}] {} of {total} proofs verified successfully", package.name, total - failed);
+
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(CliError::BatchVerificationFailed { failed, total })
+    }
+}
+
+/// Verifies the proof named `proof_name` in `batch_dir`.
+///
+/// The public inputs are read from `<proof_name>.toml` in `batch_dir` if it exists, otherwise from the verifier
+/// inputs named `verifier_name` in `package_dir`.
+fn verify_batch_proof(
+    backend: &Backend,
+    compiled_program: &CompiledProgram,
+    public_abi: &Abi,
+    batch_dir: &Path,
+    proof_name: &str,
+    package_dir: &Path,
+    verifier_name: &str,
+) -> Result<bool, CliError> {
+    // Proof names may contain dots, so the extensions are appended rather than set with `Path::with_extension`.
+    let proof_inputs_path = batch_dir.join(format!("{proof_name}.toml"));
+    let (public_inputs_map, return_value) = if proof_inputs_path.exists() {
+        read_inputs_from_path(&proof_inputs_path, public_abi)?
+    } else {
+        read_inputs_from_file(package_dir, verifier_name, Format::Toml, public_abi)?
+    };
+    let public_inputs = public_abi.encode(&public_inputs_map, return_value)?;
+
+    let compressed_proof_path = batch_dir.join(format!("{proof_name}.{COMPRESSED_PROOF_EXT}"));
+    let proof_path = if compressed_proof_path.exists() {
+        compressed_proof_path
+    } else {
+        batch_dir.join(format!("{proof_name}.{PROOF_EXT}"))
+    };
+    let proof = load_proof_from_file(&proof_path)?;
+
+    Ok(backend.verify(&proof, public_inputs, &compiled_program.circuit)?)
+}
+
+/// Returns the names of all proofs in `batch_dir`, sorted alphabetically.
+fn batch_proof_names(batch_dir: &Path) -> Result<Vec<String>, CliError> {
+    let entries = std::fs::read_dir(batch_dir)
+        .map_err(|_| FilesystemError::PathNotValid(batch_dir.to_path_buf()))?;
+
+    let mut proof_names: Vec<String> = entries
+        .filter_map(|entry| {
+            let file_name = entry.ok()?.file_name().into_string().ok()?;
+            let proof_name = file_name
+                .strip_suffix(&format!(".{COMPRESSED_PROOF_EXT}"))
+                .or_else(|| file_name.strip_suffix(&format!(".{PROOF_EXT}")))?;
+            Some(proof_name.to_string())
+        })
+        .collect();
+    proof_names.sort();
+    proof_names.dedup();
+
+    Ok(proof_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::batch_proof_names;
+
+    #[test]
+    fn finds_all_proofs_in_batch_directory() {
+        let batch_dir = tempdir().unwrap();
+        for file_name in ["b.proof", "a.proof.gz", "a.toml", "c.proof", "c.proof.gz", "notes.txt"] {
+            std::fs::write(batch_dir.path().join(file_name), "").unwrap();
+        }
+
+        assert_eq!(batch_proof_names(batch_dir.path()).unwrap(), vec!["a", "b", "c"]);
+    }
+}
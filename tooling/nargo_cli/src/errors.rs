@@ -1,22 +1,46 @@
 use acvm::acir::native_types::WitnessMapError;
-use hex::FromHexError;
 use nargo::{errors::CompileError, NargoError};
 use nargo_toml::ManifestError;
 use noir_debugger::errors::DapError;
 use noirc_abi::errors::{AbiError, InputParserError};
-use std::path::PathBuf;
+use serde_json::json;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub(crate) enum FilesystemError {
     #[error("Error: {} is not a valid path\nRun either `nargo compile` to generate missing build artifacts or `nargo prove` to construct a proof", .0.display())]
     PathNotValid(PathBuf),
-    #[error("Error: could not parse hex build artifact (proof, proving and/or verification keys, ACIR checksum) ({0})")]
-    HexArtifactNotValid(FromHexError),
+    #[error("Error: could not decompress proof {}: {1}", .0.display())]
+    ProofDecompressionError(PathBuf, std::io::Error),
+    #[error("Error: could not decode the hex proof {}: {1}", .0.display())]
+    InvalidHexProof(PathBuf, hex::FromHexError),
     #[error(
         " Error: cannot find {0}.toml file.\n Expected location: {1:?} \n Please generate this file at the expected location."
     )]
     MissingTomlFile(String, PathBuf),
+    #[error("Error: cannot find the inputs file {}", .0.display())]
+    MissingInputFile(PathBuf),
+    #[error("Error: no inputs were provided, expected values for the parameters {}", .0.join(", "))]
+    NoInputsProvided(Vec<String>),
+    #[error("Error: expected inputs to be piped into stdin")]
+    MissingStdinInputs,
+    #[error("Error: could not read inputs from stdin: {0}")]
+    StdinReadError(std::io::Error),
+    #[error("Error: could not read an ABI from {}: {1}", .0.display())]
+    InvalidAbiFile(PathBuf, serde_json::Error),
+    #[error("Error: could not write the solver trace to {}: {1}", .0.display())]
+    TraceWriteError(PathBuf, std::io::Error),
+    #[error("Error: could not read the witness {}: {1}", .0.display())]
+    WitnessReadError(PathBuf, std::io::Error),
+    #[error("Error: no proofs were found in {}", .0.display())]
+    NoProofsFound(PathBuf),
+    #[error("Error: could not read the verification key {}: {1}", .0.display())]
+    VerificationKeyReadError(PathBuf, std::io::Error),
+    #[error("Error: could not read the project template {}: {1}", .0.display())]
+    TemplateReadError(PathBuf, std::io::Error),
+    #[error("Error: {} is not a version {} bundle. Recompile it with `nargo compile --bundle`", .0.display(), nargo::artifacts::bundle::BUNDLE_FORMAT_VERSION)]
+    UnsupportedBundleVersion(PathBuf, Option<u64>),
 
     /// Input parsing error
     #[error(transparent)]
@@ -30,6 +54,32 @@ pub(crate) enum FilesystemError {
     ProgramSerializationError(String),
 }
 
+impl FilesystemError {
+    /// The path of the file which caused this error, if there is one.
+    fn path(&self) -> Option<&Path> {
+        match self {
+            FilesystemError::PathNotValid(path)
+            | FilesystemError::ProofDecompressionError(path, _)
+            | FilesystemError::InvalidHexProof(path, _)
+            | FilesystemError::MissingTomlFile(_, path)
+            | FilesystemError::MissingInputFile(path)
+            | FilesystemError::InvalidAbiFile(path, _)
+            | FilesystemError::TraceWriteError(path, _)
+            | FilesystemError::WitnessReadError(path, _)
+            | FilesystemError::NoProofsFound(path)
+            | FilesystemError::VerificationKeyReadError(path, _)
+            | FilesystemError::TemplateReadError(path, _)
+            | FilesystemError::UnsupportedBundleVersion(path, _) => Some(path),
+            FilesystemError::NoInputsProvided(_)
+            | FilesystemError::MissingStdinInputs
+            | FilesystemError::StdinReadError(_)
+            | FilesystemError::InputParserError(_)
+            | FilesystemError::WitnessMapSerialization(_)
+            | FilesystemError::ProgramSerializationError(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub(crate) enum CliError {
     #[error("{0}")]
@@ -40,9 +90,30 @@ pub(crate) enum CliError {
     #[error("Failed to verify proof {}", .0.display())]
     InvalidProof(PathBuf),
 
+    #[error("{failed} of {total} proofs failed to verify")]
+    BatchVerificationFailed { failed: usize, total: usize },
+
+    #[error("Circuit output does not match the expected output\n{0}")]
+    UnexpectedOutput(String),
+
+    #[error("[{package}] ABI has {count} breaking change{}", if *.count == 1 { "" } else { "s" })]
+    BreakingAbiChanges { package: String, count: usize },
+
+    #[error("[{package}] Solving the circuit is estimated to need {estimated_mb} MB of memory, which exceeds the limit of {max_memory_mb} MB set by `--max-memory`")]
+    MemoryLimitExceeded { package: String, estimated_mb: u64, max_memory_mb: u64 },
+
+    #[error("[{circuit}] Circuit has {opcode_count} opcodes, which exceeds the budget of {max_gates} set by `--max-gates`")]
+    GateBudgetExceeded { circuit: String, opcode_count: usize, max_gates: usize },
+
+    #[error("Error: {} was compiled for the backend `{bundle_backend}` but is being used with `{backend}`. Pass `--allow-backend-mismatch` to use it anyway", .path.display())]
+    BundleBackendMismatch { path: PathBuf, bundle_backend: String, backend: String },
+
     #[error("Invalid package name {0}. Did you mean to use `--name`?")]
     InvalidPackageName(String),
 
+    #[error("Self-test failed at the {stage} stage: {source}")]
+    SelftestFailed { stage: &'static str, source: Box<CliError> },
+
     /// ABI encoding/decoding error
     #[error(transparent)]
     AbiError(#[from] AbiError),
@@ -78,6 +149,67 @@ pub(crate) enum CliError {
     BackendCommunicationError(#[from] backend_interface::BackendError),
 }
 
+impl CliError {
+    /// Describes this error as a JSON object, for tools which run nargo to report failures without parsing its
+    /// human-readable output.
+    ///
+    /// The object always contains the `kind` of error and its `message`, along with any fields specific to that
+    /// kind of error.
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        let (kind, fields) = match self {
+            CliError::Generic(_) => ("Generic", json!({})),
+            CliError::DestinationAlreadyExists(path) => {
+                ("DestinationAlreadyExists", json!({ "path": path }))
+            }
+            CliError::InvalidProof(path) => ("InvalidProof", json!({ "path": path })),
+            CliError::BatchVerificationFailed { failed, total } => {
+                ("BatchVerificationFailed", json!({ "failed": failed, "total": total }))
+            }
+            CliError::UnexpectedOutput(diff) => ("UnexpectedOutput", json!({ "diff": diff })),
+            CliError::BreakingAbiChanges { package, count } => {
+                ("BreakingAbiChanges", json!({ "package": package, "count": count }))
+            }
+            CliError::MemoryLimitExceeded { package, estimated_mb, max_memory_mb } => (
+                "MemoryLimitExceeded",
+                json!({
+                    "package": package,
+                    "estimated_mb": estimated_mb,
+                    "max_memory_mb": max_memory_mb,
+                }),
+            ),
+            CliError::GateBudgetExceeded { circuit, opcode_count, max_gates } => (
+                "GateBudgetExceeded",
+                json!({ "circuit": circuit, "opcode_count": opcode_count, "max_gates": max_gates }),
+            ),
+            CliError::BundleBackendMismatch { path, bundle_backend, backend } => (
+                "BundleBackendMismatch",
+                json!({ "path": path, "bundle_backend": bundle_backend, "backend": backend }),
+            ),
+            CliError::InvalidPackageName(name) => ("InvalidPackageName", json!({ "name": name })),
+            CliError::SelftestFailed { stage, source } => {
+                ("SelftestFailed", json!({ "stage": stage, "source": source.to_json() }))
+            }
+            CliError::AbiError(_) => ("AbiError", json!({})),
+            CliError::FilesystemError(error) => {
+                ("FilesystemError", error.path().map_or(json!({}), |path| json!({ "path": path })))
+            }
+            CliError::LspError(_) => ("LspError", json!({})),
+            CliError::DapError(_) => ("DapError", json!({})),
+            CliError::NargoError(_) => ("NargoError", json!({})),
+            CliError::ManifestError(_) => ("ManifestError", json!({})),
+            CliError::CompileError(_) => ("CompileError", json!({})),
+            CliError::BackendError(_) => ("BackendError", json!({})),
+            CliError::BackendCommunicationError(_) => ("BackendCommunicationError", json!({})),
+        };
+
+        let mut error = json!({ "kind": kind, "message": self.to_string() });
+        if let (Some(error), serde_json::Value::Object(fields)) = (error.as_object_mut(), fields) {
+            error.extend(fields);
+        }
+        error
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum BackendError {
     #[error("No backend is installed with the name {0}")]
@@ -89,3 +221,34 @@ pub(crate) enum BackendError {
     #[error("Backend installation failed: {0}")]
     InstallationError(#[from] std::io::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::json;
+
+    use super::{CliError, FilesystemError};
+
+    #[test]
+    fn describes_errors_as_json() {
+        let error = CliError::BatchVerificationFailed { failed: 1, total: 3 };
+        assert_eq!(
+            error.to_json(),
+            json!({
+                "kind": "BatchVerificationFailed",
+                "message": "1 of 3 proofs failed to verify",
+                "failed": 1,
+                "total": 3,
+            })
+        );
+
+        let error = CliError::FilesystemError(FilesystemError::MissingInputFile(PathBuf::from(
+            "Prover.toml",
+        )));
+        let json = error.to_json();
+        assert_eq!(json["kind"], "FilesystemError");
+        assert_eq!(json["message"], error.to_string());
+        assert_eq!(json["path"], "Prover.toml");
+    }
+}
@@ -5,7 +5,7 @@ use assert_cmd::prelude::*;
 use predicates::prelude::*;
 use std::process::Command;
 
-use assert_fs::prelude::{PathAssert, PathChild};
+use assert_fs::prelude::{FileWriteStr, PathAssert, PathChild};
 
 #[test]
 fn simple_verifier_codegen() {
@@ -35,3 +35,41 @@ fn simple_verifier_codegen() {
         .child("plonk_vk.sol")
         .assert(predicate::path::is_file());
 }
+
+#[test]
+fn verifier_codegen_applies_the_manifest_profile() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    std::env::set_current_dir(&test_dir).unwrap();
+
+    let project_name = "profiled";
+    let project_dir = test_dir.child(project_name);
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.arg("new").arg(project_name);
+    cmd.assert().success();
+
+    // An unused variable is only a warning, which the profile turns into an error.
+    project_dir
+        .child("src")
+        .child("main.nr")
+        .write_str(
+            "fn main(x: Field, y: pub Field) {\n    let unused = x;\n    assert(x != y);\n}\n",
+        )
+        .unwrap();
+
+    std::env::set_current_dir(&project_dir).unwrap();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.arg("codegen-verifier");
+    cmd.assert().success();
+
+    let manifest = std::fs::read_to_string(project_dir.child("Nargo.toml").path()).unwrap();
+    project_dir
+        .child("Nargo.toml")
+        .write_str(&format!("{manifest}\n[profile]\nallow-warnings = false\n"))
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.arg("codegen-verifier");
+    cmd.assert().failure().stderr(predicate::str::contains("unused variable"));
+}
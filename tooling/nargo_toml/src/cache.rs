@@ -0,0 +1,228 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use nargo::package::{Dependency, Package};
+use noirc_frontend::graph::CrateName;
+
+use crate::{DependencyConfig, DependencySource, ManifestError};
+
+/// The environment variable which overrides the maximum depth of the dependency graph.
+const MAX_DEPENDENCY_DEPTH_ENV: &str = "NARGO_MAX_DEPENDENCY_DEPTH";
+
+const DEFAULT_MAX_DEPENDENCY_DEPTH: usize = 64;
+
+/// Reads the maximum depth of the dependency graph from [MAX_DEPENDENCY_DEPTH_ENV], falling back to
+/// [DEFAULT_MAX_DEPENDENCY_DEPTH] if it is not set.
+///
+/// Returns an error if the variable is set to something other than a whole number.
+pub(crate) fn max_dependency_depth_from_env() -> Result<usize, ManifestError> {
+    parse_max_dependency_depth(std::env::var(MAX_DEPENDENCY_DEPTH_ENV).ok())
+}
+
+fn parse_max_dependency_depth(value: Option<String>) -> Result<usize, ManifestError> {
+    let Some(value) = value else {
+        return Ok(DEFAULT_MAX_DEPENDENCY_DEPTH);
+    };
+    value.trim().parse().map_err(|_| ManifestError::InvalidMaxDependencyDepth {
+        name: MAX_DEPENDENCY_DEPTH_ENV,
+        value,
+    })
+}
+
+/// Packages which have already been resolved as dependencies.
+///
+/// These are keyed by both their root directory and a hash of their contents so that a library which is reached
+/// through multiple sources (e.g. a git dependency and a path dependency) resolves to a single crate.
+pub(crate) struct CachedPackages {
+    content_hashes: HashMap<PathBuf, u64>,
+    packages: HashMap<u64, Package>,
+    /// The git clones which have been fetched again during this resolution, if cloned git dependencies are not
+    /// being reused.
+    pub(crate) refreshed_git_clones: Option<HashSet<PathBuf>>,
+    /// The maximum number of manifests which may be resolved within one another.
+    ///
+    /// Manifests are resolved recursively so this guards against dependency chains deep enough to overflow the stack.
+    pub(crate) max_dependency_depth: usize,
+    /// How long fetching a git dependency may take before it is aborted, if there is a limit.
+    pub(crate) network_timeout: Option<Duration>,
+    /// The commit which each git dependency resolved to, keyed by the location of its clone.
+    pub(crate) git_commits: BTreeMap<PathBuf, ResolvedGitDependency>,
+    /// The sources from the root manifest's `[patch]` table which replace every dependency of the same name.
+    pub(crate) patches: BTreeMap<CrateName, DependencyConfig>,
+    /// Problems found in the manifests resolved so far which do not prevent their resolution.
+    pub(crate) warnings: Vec<String>,
+    /// The source which each dependency was resolved from, keyed by the root directory of the package which
+    /// declares it and the name by which it is imported.
+    pub(crate) dependency_sources: DependencySources,
+}
+
+pub(crate) type DependencySources = BTreeMap<(PathBuf, CrateName), DependencySource>;
+
+/// A git dependency along with the commit which its tag resolved to when it was fetched.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ResolvedGitDependency {
+    pub git: String,
+    pub tag: String,
+    /// The short hash of the commit.
+    pub commit: String,
+}
+
+impl Default for CachedPackages {
+    fn default() -> Self {
+        CachedPackages {
+            content_hashes: HashMap::new(),
+            packages: HashMap::new(),
+            refreshed_git_clones: None,
+            max_dependency_depth: DEFAULT_MAX_DEPENDENCY_DEPTH,
+            network_timeout: None,
+            git_commits: BTreeMap::new(),
+            patches: BTreeMap::new(),
+            warnings: Vec::new(),
+            dependency_sources: BTreeMap::new(),
+        }
+    }
+}
+
+impl CachedPackages {
+    /// Creates a cache which re-clones every git dependency rather than reusing any previous clone.
+    pub(crate) fn bypassing_git_cache() -> Self {
+        CachedPackages { refreshed_git_clones: Some(HashSet::new()), ..Default::default() }
+    }
+
+    /// Returns the package which was resolved from `root_dir` if it has already been cached.
+    pub(crate) fn get(&self, root_dir: &Path) -> Option<Package> {
+        let content_hash = self.content_hashes.get(root_dir)?;
+        self.packages.get(content_hash).cloned()
+    }
+
+    /// Records the commit which the git dependency `git` at `tag`, cloned into `clone_dir`, resolved to.
+    pub(crate) fn record_git_commit(
+        &mut self,
+        clone_dir: PathBuf,
+        git: &str,
+        tag: &str,
+        commit: String,
+    ) {
+        let dependency =
+            ResolvedGitDependency { git: git.to_string(), tag: tag.to_string(), commit };
+        self.git_commits.insert(clone_dir, dependency);
+    }
+
+    /// Returns the git dependencies which `packages` depend upon, whether directly or transitively.
+    pub(crate) fn git_dependencies_of<'a>(
+        &self,
+        packages: impl IntoIterator<Item = &'a Package>,
+    ) -> Vec<ResolvedGitDependency> {
+        fn collect_root_dirs(package: &Package, root_dirs: &mut HashSet<PathBuf>) {
+            for dependency in package.dependencies.values() {
+                let (Dependency::Local { package } | Dependency::Remote { package }) = dependency;
+                if root_dirs.insert(package.root_dir.clone()) {
+                    collect_root_dirs(package, root_dirs);
+                }
+            }
+        }
+
+        let mut root_dirs = HashSet::new();
+        for package in packages {
+            collect_root_dirs(package, &mut root_dirs);
+        }
+
+        let mut dependencies: Vec<_> = self
+            .git_commits
+            .iter()
+            .filter(|(clone_dir, _)| {
+                root_dirs.iter().any(|root_dir| root_dir.starts_with(clone_dir))
+            })
+            .map(|(_, dependency)| dependency.clone())
+            .collect();
+        dependencies.sort();
+        dependencies.dedup();
+        dependencies
+    }
+}
+
+/// Caches a newly resolved dependency, returning the previously cached package if one exists with identical contents.
+pub(crate) fn cache_dep(
+    package: Package,
+    cached_packages: &mut CachedPackages,
+) -> Result<Package, ManifestError> {
+    let content_hash = hash_package_contents(&package, cached_packages)?;
+    cached_packages.content_hashes.insert(package.root_dir.clone(), content_hash);
+
+    let cached_package = cached_packages.packages.entry(content_hash).or_insert(package);
+    Ok(cached_package.clone())
+}
+
+// Hashes the manifest and source files of a package along with the contents of its dependencies.
+//
+// The package's dependencies are expected to have already been cached.
+fn hash_package_contents(
+    package: &Package,
+    cached_packages: &CachedPackages,
+) -> Result<u64, ManifestError> {
+    let source_dir = package.entry_path.parent().expect("entry path should have a parent");
+
+    let mut files = vec![package.root_dir.join("Nargo.toml")];
+    collect_files(source_dir, &mut files)
+        .map_err(|_| ManifestError::ReadFailed(source_dir.to_path_buf()))?;
+    files.sort();
+    files.dedup();
+
+    let mut hasher = DefaultHasher::new();
+    for file in files {
+        let contents =
+            std::fs::read(&file).map_err(|_| ManifestError::ReadFailed(file.to_path_buf()))?;
+        file.strip_prefix(&package.root_dir).unwrap_or(&file).hash(&mut hasher);
+        contents.hash(&mut hasher);
+    }
+
+    // Two packages with identical sources may still depend upon different libraries.
+    for (name, dep) in &package.dependencies {
+        match dep {
+            Dependency::Local { package } | Dependency::Remote { package } => {
+                name.hash(&mut hasher);
+                cached_packages.content_hashes.get(&package.root_dir).hash(&mut hasher);
+            }
+        }
+    }
+
+    Ok(hasher.finish())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ManifestError;
+
+    use super::{parse_max_dependency_depth, DEFAULT_MAX_DEPENDENCY_DEPTH};
+
+    #[test]
+    fn rejects_invalid_max_dependency_depths() {
+        assert_eq!(parse_max_dependency_depth(None).unwrap(), DEFAULT_MAX_DEPENDENCY_DEPTH);
+        assert_eq!(parse_max_dependency_depth(Some(" 8 ".to_string())).unwrap(), 8);
+
+        for value in ["abc", "-1", ""] {
+            let Err(ManifestError::InvalidMaxDependencyDepth { value: rejected, .. }) =
+                parse_max_dependency_depth(Some(value.to_string()))
+            else {
+                panic!("expected `{value}` to be rejected");
+            };
+            assert_eq!(rejected, value);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+use crate::{errors::DependencyResolutionError, overridden_included_dependencies, ManifestError};
+use nargo::{
+    package::{Dependency, Package},
+    workspace::Workspace,
+};
+use noirc_frontend::graph::CrateName;
+
+/// For each dependency, the locations it was resolved to along with the packages which requested each location.
+type Resolutions = BTreeMap<CrateName, BTreeMap<PathBuf, BTreeSet<String>>>;
+
+// Check that each package in the dependency graph of the selected packages is only resolved to a single
+// location
+pub(crate) fn check_workspace_dependency_conflicts(
+    workspace: &Workspace,
+) -> Result<(), ManifestError> {
+    let mut resolutions = Resolutions::new();
+    for package in workspace {
+        collect_resolutions(package, &mut resolutions);
+    }
+
+    for (dependency, locations) in resolutions {
+        if locations.len() > 1 {
+            let requesters = locations
+                .into_iter()
+                .flat_map(|(location, requesters)| {
+                    requesters.into_iter().map(move |requester| {
+                        format!("  `{requester}` requires {}", location.display())
+                    })
+                })
+                .collect();
+
+            return Err(ManifestError::DependencyResolutionError(
+                DependencyResolutionError::VersionConflict { dependency, requesters },
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// Check that no package in the dependency graph of the selected packages overrides a dependency from its
+// included file
+pub(crate) fn check_workspace_include_conflicts(
+    workspace: &Workspace,
+) -> Result<(), ManifestError> {
+    let mut resolutions = Resolutions::new();
+    for package in workspace {
+        collect_resolutions(package, &mut resolutions);
+    }
+
+    // Every package in the graph, whether a selected package or a dependency, may include shared dependencies.
+    let package_dirs: BTreeSet<PathBuf> = workspace
+        .into_iter()
+        .map(|package| package.root_dir.clone())
+        .chain(resolutions.into_values().flat_map(BTreeMap::into_keys))
+        .collect();
+
+    for package_dir in package_dirs {
+        let manifest = package_dir.join("Nargo.toml");
+        let dependencies = overridden_included_dependencies(&manifest)?;
+        if !dependencies.is_empty() {
+            return Err(ManifestError::DependencyResolutionError(
+                DependencyResolutionError::IncludeConflict { manifest, dependencies },
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// Record where each of the package's dependencies were resolved to, recursing into the dependencies' dependencies
+fn collect_resolutions(package: &Package, resolutions: &mut Resolutions) {
+    for dep in package.dependencies.values() {
+        match dep {
+            Dependency::Local { package: dependency }
+            | Dependency::Remote { package: dependency } => {
+                let requesters = resolutions
+                    .entry(dependency.name.clone())
+                    .or_default()
+                    .entry(dependency.root_dir.clone())
+                    .or_default();
+                // Stop if this requester has already been recorded, we've walked this part of the graph before.
+                if requesters.insert(package.name.to_string()) {
+                    collect_resolutions(dependency, resolutions);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use nargo::package::PackageType;
+
+    use super::*;
+
+    fn library(name: &str, root_dir: &str, dependencies: Vec<Package>) -> Package {
+        Package {
+            version: None,
+            compiler_required_version: None,
+            root_dir: PathBuf::from(root_dir),
+            package_type: PackageType::Library,
+            entry_path: PathBuf::new(),
+            name: CrateName::from_str(name).unwrap(),
+            dependencies: dependencies
+                .into_iter()
+                .map(|package| (package.name.clone(), Dependency::Remote { package }))
+                .collect(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    fn workspace(members: Vec<Package>) -> Workspace {
+        Workspace {
+            root_dir: PathBuf::new(),
+            members,
+            selected_package_index: None,
+            is_assumed: false,
+            profile: Default::default(),
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn allows_shared_dependency_at_same_version() {
+        let shared = library("shared", "shared-v1", Vec::new());
+        let a = library("a", "a", vec![shared.clone()]);
+        let b = library("b", "b", vec![shared]);
+        let root = library("root", "root", vec![a, b]);
+
+        assert!(check_workspace_dependency_conflicts(&workspace(vec![root])).is_ok());
+    }
+
+    #[test]
+    fn rejects_shared_dependency_at_conflicting_versions() {
+        let a = library("a", "a", vec![library("shared", "shared-v1", Vec::new())]);
+        let b = library("b", "b", vec![library("shared", "shared-v2", Vec::new())]);
+        let root = library("root", "root", vec![a, b]);
+
+        let err = check_workspace_dependency_conflicts(&workspace(vec![root])).unwrap_err();
+        let ManifestError::DependencyResolutionError(err) = err else {
+            panic!("expected a dependency resolution error, got {err:?}");
+        };
+        assert_eq!(
+            err,
+            DependencyResolutionError::VersionConflict {
+                dependency: CrateName::from_str("shared").unwrap(),
+                requesters: vec![
+                    "  `a` requires shared-v1".to_string(),
+                    "  `b` requires shared-v2".to_string(),
+                ],
+            }
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use nargo::package::PackageType;
+use nargo::package::{PackageType, SourceGlobError};
 use noirc_frontend::graph::CrateName;
 use thiserror::Error;
 
@@ -12,9 +12,15 @@ pub enum ManifestError {
     #[error("cannot find a Nargo.toml for {0}")]
     MissingFile(PathBuf),
 
+    #[error("Manifest path {0} must point to a Nargo.toml file")]
+    InvalidManifestPath(PathBuf),
+
     #[error("Cannot read file {0} - does it exist?")]
     ReadFailed(PathBuf),
 
+    #[error("Cannot read {0} as it is not valid UTF-8")]
+    InvalidEncoding(PathBuf),
+
     #[error("Nargo.toml is missing a parent directory")]
     MissingParent,
 
@@ -45,6 +51,12 @@ pub enum ManifestError {
     #[error("{} found in {toml}", if name.is_empty() { "Empty dependency name".into() } else { format!("Invalid dependency name `{name}`") })]
     InvalidDependencyName { toml: PathBuf, name: String },
 
+    #[error("Multiple dependencies in {toml} are imported as `{name}`. Set a distinct `alias` on each of them")]
+    DuplicateDependencyName { toml: PathBuf, name: String },
+
+    #[error("Unknown feature `{feature}` enabled in {toml}. Known features are: {}", nargo::KNOWN_FEATURES.join(", "))]
+    UnknownFeature { toml: PathBuf, feature: String },
+
     #[error("Invalid directory path {directory} in {toml}: It must point to a subdirectory")]
     InvalidDirectory { toml: PathBuf, directory: PathBuf },
 
@@ -52,6 +64,12 @@ pub enum ManifestError {
     #[error("{0}")]
     GitError(String),
 
+    #[error("Invalid value `{value}` for `{name}`: expected a whole number of seconds")]
+    InvalidNetworkTimeout { name: &'static str, value: String },
+
+    #[error("Invalid value `{value}` for `{name}`: expected a whole number of manifests")]
+    InvalidMaxDependencyDepth { name: &'static str, value: String },
+
     #[error("Selected package `{0}` was not found")]
     MissingSelectedPackage(CrateName),
 
@@ -61,6 +79,9 @@ pub enum ManifestError {
     #[error("Package `{0}` has type `bin` but you cannot depend on binary packages")]
     BinaryDependency(CrateName),
 
+    #[error("Package `{0}` has type `contract` but you cannot depend on contract packages. Move the code to be shared into a `lib` package")]
+    ContractDependency(CrateName),
+
     #[error("Missing `name` field in {toml}")]
     MissingNameField { toml: PathBuf },
 
@@ -70,8 +91,29 @@ pub enum ManifestError {
     #[error(transparent)]
     SemverError(SemverError),
 
+    #[error(transparent)]
+    DependencyResolutionError(DependencyResolutionError),
+
+    #[error("Invalid `include` or `exclude` globs in {toml}: {error}")]
+    InvalidSourceGlobs { toml: PathBuf, error: SourceGlobError },
+
+    #[error("Invalid `include` of {include} in {toml}: it must be the path to a file containing a `[dependencies]` table")]
+    InvalidInclude { toml: PathBuf, include: String },
+
+    #[error("Dependency `{dependency}` is requested from the package registry but `NARGO_REGISTRY` is not set to the path of a registry index")]
+    MissingRegistry { dependency: String },
+
+    #[error("Version {version} of `{dependency}` was not found in the registry index at {}", index.display())]
+    MissingRegistryEntry { dependency: String, version: String, index: PathBuf },
+
     #[error("Cyclic package dependency found when processing {cycle}")]
     CyclicDependency { cycle: String },
+
+    #[error("{}", manifests.iter().map(|(toml, issues)| format!("{} is invalid:\n{}", toml.display(), issues.iter().map(|issue| format!("  {issue}")).collect::<Vec<_>>().join("\n"))).collect::<Vec<_>>().join("\n"))]
+    InvalidManifests { manifests: Vec<(PathBuf, Vec<ManifestIssue>)> },
+
+    #[error("Package `{package}` is not ready to be published:\n{}", errors.iter().map(|error| format!("  {error}")).collect::<Vec<_>>().join("\n"))]
+    NotPublishable { package: CrateName, errors: Vec<PublishError> },
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -88,3 +130,56 @@ pub enum SemverError {
     #[error("Could not parse the package version for package {package_name} in Nargo.toml. Error: {error}")]
     CouldNotParsePackageVersion { package_name: String, error: String },
 }
+
+/// Errors covering situations where the dependency graph of a workspace cannot be resolved unambiguously.
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum DependencyResolutionError {
+    #[error("Dependency `{dependency}` is resolved to conflicting versions:\n{}", requesters.join("\n"))]
+    VersionConflict { dependency: CrateName, requesters: Vec<String> },
+
+    #[error("Dependencies {} in {} override dependencies of the same name from its included file", dependencies.join(", "), manifest.display())]
+    IncludeConflict { manifest: PathBuf, dependencies: Vec<String> },
+
+    #[error("Path dependency `{dependency}` points to {}, which {}", path.display(), if *is_dir { "does not contain a Nargo.toml" } else { "is not a directory" })]
+    MissingPathDependency { dependency: CrateName, path: PathBuf, is_dir: bool },
+
+    #[error("Dependency {} is nested more than {max_depth} dependencies deep. The limit can be raised by setting `NARGO_MAX_DEPENDENCY_DEPTH`", manifest.display())]
+    DependencyTooDeep { manifest: PathBuf, max_depth: usize },
+}
+
+/// Conditions which prevent a package from being published as a library.
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum PublishError {
+    #[error("Package has type `bin` but binary packages cannot be published")]
+    BinaryPackage,
+
+    #[error("Missing `compiler_version` field")]
+    MissingCompilerVersion,
+
+    #[error("Could not parse the `compiler_version` field. Error: {error}")]
+    InvalidCompilerVersion { error: String },
+
+    #[error("Missing `license` field")]
+    MissingLicense,
+
+    #[error("Dependency `{dependency}` is specified by a local `path`")]
+    LocalDependency { dependency: CrateName },
+}
+
+/// Semantic problems in a manifest which parses successfully but cannot be resolved as intended.
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum ManifestIssue {
+    #[error(
+        "Dependency `{dependency}` is imported under the name of the package which depends on it"
+    )]
+    DependencyNamedAfterPackage { dependency: String },
+
+    #[error("Git dependency `{dependency}` has an empty `git` repository")]
+    EmptyGitRepository { dependency: String },
+
+    #[error("Git dependency `{dependency}` has an empty `tag`")]
+    EmptyGitTag { dependency: String },
+
+    #[error("Dependency `{dependency}` has an empty `{key}` path")]
+    EmptyPath { dependency: String, key: &'static str },
+}
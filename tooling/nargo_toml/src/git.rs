@@ -1,4 +1,32 @@
-use std::path::PathBuf;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+    time::{Duration, Instant},
+};
+
+use crate::ManifestError;
+
+/// The environment variable which sets the number of seconds after which fetching a git dependency is aborted.
+pub(crate) const NETWORK_TIMEOUT_ENV: &str = "NARGO_NETWORK_TIMEOUT";
+
+/// Reads the timeout for fetching git dependencies from [NETWORK_TIMEOUT_ENV]. By default there is no timeout.
+///
+/// Returns an error if the variable is set to something other than a whole number of seconds.
+pub(crate) fn network_timeout_from_env() -> Result<Option<Duration>, ManifestError> {
+    let Ok(value) = std::env::var(NETWORK_TIMEOUT_ENV) else {
+        return Ok(None);
+    };
+    match value.trim().parse() {
+        Ok(seconds) => Ok(Some(Duration::from_secs(seconds))),
+        Err(_) => Err(ManifestError::InvalidNetworkTimeout { name: NETWORK_TIMEOUT_ENV, value }),
+    }
+}
+
+/// The file written into a clone once it has been fully checked out, recording the commit which was checked out.
+///
+/// A clone without this marker was interrupted, e.g. by the build being killed, so is fetched again rather than used.
+const COMPLETION_MARKER: &str = ".nargo-clone-complete";
 
 /// Creates a unique folder name for a GitHub repo
 /// by using its URL and tag
@@ -25,20 +53,116 @@ fn git_dep_location(base: &url::Url, tag: &str) -> PathBuf {
 /// github-rs looks promising, however it seems to require an API token
 ///
 /// One advantage of using "git clone" is that there is effectively no rate limit
-pub(crate) fn clone_git_repo(url: &str, tag: &str) -> Result<PathBuf, String> {
-    use std::process::Command;
-
+///
+/// If `refreshed_clones` is provided then any existing clone is ignored and the repository is cloned again,
+/// replacing the cached clone. Each location is only refreshed once, as recorded in `refreshed_clones`.
+///
+/// If cloning the repository takes longer than `timeout` then it is aborted and an error is returned.
+///
+/// Returns the location of the clone along with the short hash of the commit which `tag` resolved to.
+pub(crate) fn clone_git_repo(
+    url: &str,
+    tag: &str,
+    timeout: Option<Duration>,
+    refreshed_clones: Option<&mut HashSet<PathBuf>>,
+) -> Result<(PathBuf, String), String> {
     let base = match url::Url::parse(url) {
         Ok(base) => base,
         Err(err) => return Err(err.to_string()),
     };
 
     let loc = git_dep_location(&base, tag);
+    let refresh = refreshed_clones.is_some_and(|refreshed| refreshed.insert(loc.clone()));
+    fetch_git_repo_into(base.as_str(), tag, &loc, timeout, refresh)?;
+    let commit =
+        head_commit(&loc).ok_or_else(|| format!("Failed to read the commit of {url} at {tag}"))?;
+
+    Ok((loc, commit))
+}
+
+/// Returns the short hash of the commit checked out in the clone at `loc`.
+fn head_commit(loc: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(loc)
+        .arg("rev-parse")
+        .arg("--short")
+        .arg("HEAD")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
+/// Makes sure that `loc` holds a complete clone of the repository at `url`, reusing an existing clone unless
+/// `refresh` is set.
+///
+/// An existing clone which has no completion marker is discarded and cloned again.
+fn fetch_git_repo_into(
+    url: &str,
+    tag: &str,
+    loc: &Path,
+    timeout: Option<Duration>,
+    refresh: bool,
+) -> Result<(), String> {
     if loc.exists() {
-        return Ok(loc);
+        if is_complete_clone(loc) {
+            if refresh {
+                reclone_git_repo_into(url, tag, loc, timeout)?;
+            }
+            return Ok(());
+        }
+        std::fs::remove_dir_all(loc).map_err(|err| {
+            format!("Failed to remove the incomplete clone of {url} at {tag}: {err}")
+        })?;
     }
 
-    Command::new("git")
+    clone_git_repo_into(url, tag, loc, timeout)
+}
+
+/// Clones the repository at `url` again and replaces the existing clone at `loc` with it.
+///
+/// The existing clone is only removed once the new clone has succeeded, so a failed fetch leaves it untouched.
+fn reclone_git_repo_into(
+    url: &str,
+    tag: &str,
+    loc: &Path,
+    timeout: Option<Duration>,
+) -> Result<(), String> {
+    let mut fresh_loc = loc.as_os_str().to_owned();
+    fresh_loc.push(".fresh");
+    let fresh_loc = PathBuf::from(fresh_loc);
+    let _ = std::fs::remove_dir_all(&fresh_loc);
+
+    clone_git_repo_into(url, tag, &fresh_loc, timeout)?;
+    std::fs::remove_dir_all(loc)
+        .and_then(|_| std::fs::rename(&fresh_loc, loc))
+        .map_err(|err| format!("Failed to replace the cached clone of {url} at {tag}: {err}"))
+}
+
+/// Clones the repository at `url` into `loc`, checking out `tag` along with any submodules the repository uses.
+///
+/// If the clone fails or does not finish within `timeout` then `loc` is removed, so that a partial checkout is not
+/// mistaken for the dependency. The completion marker is only written once everything has been checked out.
+fn clone_git_repo_into(
+    url: &str,
+    tag: &str,
+    loc: &Path,
+    timeout: Option<Duration>,
+) -> Result<(), String> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let timed_out = || {
+        let _ = std::fs::remove_dir_all(loc);
+        format!(
+            "Timed out after {} seconds while cloning {url} at {tag}. The timeout can be changed by setting `{NETWORK_TIMEOUT_ENV}`",
+            timeout.unwrap_or_default().as_secs()
+        )
+    };
+
+    let mut clone_command = Command::new("git");
+    clone_command
         .arg("-c")
         .arg("advice.detachedHead=false")
         .arg("clone")
@@ -46,10 +170,214 @@ pub(crate) fn clone_git_repo(url: &str, tag: &str) -> Result<PathBuf, String> {
         .arg("1")
         .arg("--branch")
         .arg(tag)
-        .arg(base.as_str())
-        .arg(&loc)
-        .status()
-        .expect("git clone command failed to start");
+        .arg(url)
+        .arg(loc);
+    let clone_status = run_until(&mut clone_command, deadline).ok_or_else(timed_out)?;
+    if !clone_status.success() {
+        let _ = std::fs::remove_dir_all(loc);
+        return Err(format!("Failed to clone {url} at {tag}"));
+    }
+
+    // Dependencies may keep some of their sources in submodules, without which the package would be incomplete.
+    if loc.join(".gitmodules").exists() {
+        let mut submodule_command = Command::new("git");
+        submodule_command
+            .arg("-C")
+            .arg(loc)
+            .arg("submodule")
+            .arg("update")
+            .arg("--init")
+            .arg("--recursive")
+            .arg("--depth")
+            .arg("1");
+        let submodule_status = run_until(&mut submodule_command, deadline).ok_or_else(timed_out)?;
+        if !submodule_status.success() {
+            let _ = std::fs::remove_dir_all(loc);
+            return Err(format!("Failed to fetch the submodules of {url} at {tag}"));
+        }
+    }
+
+    let commit =
+        head_commit(loc).ok_or_else(|| format!("Failed to read the commit of {url} at {tag}"))?;
+    std::fs::write(loc.join(COMPLETION_MARKER), commit)
+        .map_err(|err| format!("Failed to record the clone of {url} at {tag}: {err}"))
+}
+
+/// Returns whether `loc` holds a clone which finished checking out.
+///
+/// Clones made by earlier versions of nargo have no completion marker. These are kept if git reports that their
+/// checkout is clean, and the marker is written so that they are not checked again.
+fn is_complete_clone(loc: &Path) -> bool {
+    let marker = loc.join(COMPLETION_MARKER);
+    if marker.is_file() {
+        return true;
+    }
+    if !is_clean_checkout(loc) {
+        return false;
+    }
+    head_commit(loc).is_some_and(|commit| std::fs::write(marker, commit).is_ok())
+}
+
+/// Returns whether `loc` is a git checkout whose files all match its `HEAD` commit.
+fn is_clean_checkout(loc: &Path) -> bool {
+    // The repository is given explicitly so that git does not search the parent directories for one.
+    let output = Command::new("git")
+        .arg("--git-dir")
+        .arg(loc.join(".git"))
+        .arg("--work-tree")
+        .arg(loc)
+        .arg("status")
+        .arg("--porcelain")
+        .output();
+    output.is_ok_and(|output| output.status.success() && output.stdout.is_empty())
+}
+
+/// Runs `command` to completion, killing it if it is still running at `deadline`.
+///
+/// Returns `None` if the command was killed.
+fn run_until(command: &mut Command, deadline: Option<Instant>) -> Option<ExitStatus> {
+    let mut child =
+        command.spawn().unwrap_or_else(|err| panic!("{command:?} failed to start: {err}"));
+    let Some(deadline) = deadline else {
+        return Some(child.wait().expect("failed to wait on git command"));
+    };
+
+    loop {
+        if let Some(status) = child.try_wait().expect("failed to wait on git command") {
+            return Some(status);
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::Path,
+        process::Command,
+        time::{Duration, Instant},
+    };
+
+    use super::{
+        clone_git_repo_into, fetch_git_repo_into, head_commit, reclone_git_repo_into, run_until,
+        COMPLETION_MARKER,
+    };
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=nargo", "-c", "user.email=nargo@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn create_repo(dir: &Path, file: &str, contents: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(file), contents).unwrap();
+        git(dir, &["init", "--quiet"]);
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "--quiet", "-m", "initial commit"]);
+    }
+
+    #[test]
+    fn clones_submodules() {
+        // The submodule is fetched from the local filesystem, which newer versions of git refuse unless configured.
+        // Every git command spawned by this process, including those run by `clone_git_repo_into`, picks this up.
+        std::env::set_var("GIT_CONFIG_COUNT", "1");
+        std::env::set_var("GIT_CONFIG_KEY_0", "protocol.file.allow");
+        std::env::set_var("GIT_CONFIG_VALUE_0", "always");
 
-    Ok(loc)
+        let fixtures = tempfile::tempdir().unwrap();
+        let lib_repo = fixtures.path().join("lib");
+        create_repo(&lib_repo, "lib.nr", "fn foo() {}");
+
+        let dependency_repo = fixtures.path().join("dependency");
+        create_repo(&dependency_repo, "Nargo.toml", "");
+        git(&dependency_repo, &["submodule", "--quiet", "add", lib_repo.to_str().unwrap(), "src"]);
+        git(&dependency_repo, &["commit", "--quiet", "-m", "add submodule"]);
+        git(&dependency_repo, &["tag", "v0.1.0"]);
+
+        let loc = fixtures.path().join("clone");
+        let url = format!("file://{}", dependency_repo.display());
+        clone_git_repo_into(&url, "v0.1.0", &loc, None).unwrap();
+        assert_eq!(std::fs::read_to_string(loc.join("src/lib.nr")).unwrap(), "fn foo() {}");
+
+        let missing_tag = fixtures.path().join("missing_tag");
+        assert!(clone_git_repo_into(&url, "v0.2.0", &missing_tag, None).is_err());
+        assert!(!missing_tag.exists());
+    }
+
+    #[test]
+    fn reclones_moved_tags() {
+        let fixtures = tempfile::tempdir().unwrap();
+        let dependency_repo = fixtures.path().join("dependency");
+        create_repo(&dependency_repo, "lib.nr", "fn foo() {}");
+        git(&dependency_repo, &["tag", "v0.1.0"]);
+
+        let loc = fixtures.path().join("clone");
+        let url = format!("file://{}", dependency_repo.display());
+        clone_git_repo_into(&url, "v0.1.0", &loc, None).unwrap();
+
+        std::fs::write(dependency_repo.join("lib.nr"), "fn bar() {}").unwrap();
+        git(&dependency_repo, &["commit", "--quiet", "-am", "move tag"]);
+        git(&dependency_repo, &["tag", "--force", "v0.1.0"]);
+
+        let original_commit = head_commit(&loc).unwrap();
+        reclone_git_repo_into(&url, "v0.1.0", &loc, None).unwrap();
+        assert_eq!(std::fs::read_to_string(loc.join("lib.nr")).unwrap(), "fn bar() {}");
+        assert_ne!(head_commit(&loc).unwrap(), original_commit);
+        assert_eq!(head_commit(&loc), head_commit(&dependency_repo));
+
+        // A failed fetch leaves the existing clone in place.
+        assert!(reclone_git_repo_into(&url, "v0.2.0", &loc, None).is_err());
+        assert_eq!(std::fs::read_to_string(loc.join("lib.nr")).unwrap(), "fn bar() {}");
+    }
+
+    #[test]
+    fn replaces_incomplete_clones() {
+        let fixtures = tempfile::tempdir().unwrap();
+        let dependency_repo = fixtures.path().join("dependency");
+        create_repo(&dependency_repo, "lib.nr", "fn foo() {}");
+        git(&dependency_repo, &["tag", "v0.1.0"]);
+        let url = format!("file://{}", dependency_repo.display());
+
+        // A clone which was interrupted before it was checked out.
+        let loc = fixtures.path().join("clone");
+        std::fs::create_dir_all(loc.join(".git")).unwrap();
+        fetch_git_repo_into(&url, "v0.1.0", &loc, None, false).unwrap();
+        assert_eq!(std::fs::read_to_string(loc.join("lib.nr")).unwrap(), "fn foo() {}");
+        assert!(loc.join(COMPLETION_MARKER).is_file());
+
+        // A complete clone is reused as-is.
+        std::fs::write(loc.join(".git").join("reused"), "").unwrap();
+        fetch_git_repo_into(&url, "v0.1.0", &loc, None, false).unwrap();
+        assert!(loc.join(".git").join("reused").exists());
+
+        // A clone made before completion markers were written is kept if its checkout is clean.
+        std::fs::remove_file(loc.join(COMPLETION_MARKER)).unwrap();
+        fetch_git_repo_into(&url, "v0.1.0", &loc, None, false).unwrap();
+        assert!(loc.join(".git").join("reused").exists());
+        assert_eq!(
+            std::fs::read_to_string(loc.join(COMPLETION_MARKER)).unwrap(),
+            head_commit(&loc).unwrap()
+        );
+    }
+
+    #[test]
+    fn kills_commands_which_run_past_the_deadline() {
+        let deadline = Instant::now() + Duration::from_millis(100);
+        assert!(run_until(Command::new("sleep").arg("10"), Some(deadline)).is_none());
+        assert!(Instant::now() < deadline + Duration::from_secs(5));
+
+        let status = run_until(&mut Command::new("true"), Some(deadline + Duration::from_secs(10)));
+        assert!(status.is_some_and(|status| status.success()));
+    }
 }
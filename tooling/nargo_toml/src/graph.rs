@@ -0,0 +1,173 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use nargo::{
+    package::{Dependency, Package},
+    workspace::Workspace,
+};
+use serde::Serialize;
+
+use crate::cache::DependencySources;
+
+/// The resolved dependency graph of a workspace, as plain data which can be inspected or serialized without
+/// compiling any of its packages.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyGraph {
+    /// Every package in the graph. Packages which are depended upon from several places appear once.
+    pub packages: Vec<GraphPackage>,
+    /// An edge for each entry in the `[dependencies]` of each package.
+    pub edges: Vec<DependencyEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GraphPackage {
+    pub name: String,
+    pub version: Option<String>,
+    pub package_type: String,
+    /// The directory which the package was resolved to, e.g. the checkout of a git dependency.
+    pub root_dir: PathBuf,
+    pub is_workspace_member: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyEdge {
+    /// The index in [DependencyGraph::packages] of the package which declares the dependency.
+    pub from: usize,
+    /// The index in [DependencyGraph::packages] of the package which the dependency resolved to.
+    pub to: usize,
+    /// The name by which the dependency is imported.
+    pub name: String,
+    pub source: DependencySource,
+}
+
+/// Where a dependency was resolved from, as written in the manifest which declares it or in the root manifest's
+/// `[patch]` table if the dependency was patched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DependencySource {
+    Git {
+        git: String,
+        tag: String,
+        directory: Option<String>,
+        /// The short hash of the commit which `tag` resolved to.
+        commit: String,
+    },
+    Path {
+        path: String,
+    },
+    Registry {
+        version: String,
+    },
+}
+
+impl DependencyGraph {
+    pub(crate) fn from_workspace(
+        workspace: &Workspace,
+        sources: &DependencySources,
+    ) -> DependencyGraph {
+        let mut graph = DependencyGraph::default();
+        let mut indices = BTreeMap::new();
+        for package in workspace {
+            graph.add_package(package, true, sources, &mut indices);
+        }
+        graph
+    }
+
+    // Add the package and everything it depends upon to the graph, returning the package's index.
+    fn add_package(
+        &mut self,
+        package: &Package,
+        is_workspace_member: bool,
+        sources: &DependencySources,
+        indices: &mut BTreeMap<PathBuf, usize>,
+    ) -> usize {
+        if let Some(&index) = indices.get(&package.root_dir) {
+            return index;
+        }
+        let index = self.packages.len();
+        indices.insert(package.root_dir.clone(), index);
+        self.packages.push(GraphPackage {
+            name: package.name.to_string(),
+            version: package.version.clone(),
+            package_type: package.package_type.to_string(),
+            root_dir: package.root_dir.clone(),
+            is_workspace_member,
+        });
+
+        for (name, dependency) in &package.dependencies {
+            let (Dependency::Local { package: dependency }
+            | Dependency::Remote { package: dependency }) = dependency;
+            let source = sources
+                .get(&(package.root_dir.clone(), name.clone()))
+                .expect("the source of each resolved dependency should be recorded")
+                .clone();
+            let to = self.add_package(dependency, false, sources, indices);
+            self.edges.push(DependencyEdge { from: index, to, name: name.to_string(), source });
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{resolve_dependency_graph, PackageSelection};
+
+    use super::DependencySource;
+
+    #[test]
+    fn shared_dependencies_appear_once() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let write_package = |dir: &str, manifest: &str, entry: &str| {
+            let package_dir = temp_dir.path().join(dir);
+            std::fs::create_dir_all(package_dir.join("src")).unwrap();
+            std::fs::write(package_dir.join("Nargo.toml"), manifest).unwrap();
+            std::fs::write(package_dir.join("src").join(entry), "").unwrap();
+        };
+        write_package(
+            "common",
+            r#"
+            [package]
+            name = "common"
+            type = "lib"
+        "#,
+            "lib.nr",
+        );
+        write_package(
+            "helper",
+            r#"
+            [package]
+            name = "helper"
+            type = "lib"
+
+            [dependencies]
+            common = { path = "../common" }
+        "#,
+            "lib.nr",
+        );
+        write_package(
+            "root",
+            r#"
+            [package]
+            name = "root"
+            type = "bin"
+
+            [dependencies]
+            helper = { path = "../helper" }
+            common_alias = { path = "../common", alias = "shared" }
+        "#,
+            "main.nr",
+        );
+
+        let root_toml = temp_dir.path().join("root").join("Nargo.toml");
+        let graph = resolve_dependency_graph(&root_toml, PackageSelection::All).unwrap();
+
+        let names: Vec<_> = graph.packages.iter().map(|package| package.name.as_str()).collect();
+        assert_eq!(names, vec!["root", "helper", "common"]);
+        assert!(graph.packages[0].is_workspace_member);
+        assert!(!graph.packages[1].is_workspace_member);
+
+        let edges: Vec<_> =
+            graph.edges.iter().map(|edge| (edge.from, edge.to, edge.name.as_str())).collect();
+        assert_eq!(edges, vec![(1, 2, "common"), (0, 1, "helper"), (0, 2, "shared")]);
+        assert_eq!(graph.edges[2].source, DependencySource::Path { path: "../common".to_string() });
+    }
+}
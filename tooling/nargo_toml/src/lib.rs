@@ -12,17 +12,27 @@ use errors::SemverError;
 use fm::{NormalizePath, FILE_EXTENSION};
 use nargo::{
     package::{Dependency, Package, PackageType},
-    workspace::Workspace,
+    workspace::{Profile, Workspace},
+    KNOWN_FEATURES,
 };
 use noirc_frontend::graph::CrateName;
 use serde::Deserialize;
 
+mod cache;
+mod conflicts;
 mod errors;
 mod git;
+mod graph;
+mod publish;
+mod registry;
 mod semver;
 
-pub use errors::ManifestError;
+pub use cache::ResolvedGitDependency;
+use cache::{cache_dep, CachedPackages};
+pub use errors::{DependencyResolutionError, ManifestError, ManifestIssue, PublishError};
 use git::clone_git_repo;
+pub use graph::{DependencyEdge, DependencyGraph, DependencySource, GraphPackage};
+use registry::{lookup_registry_entry, registry_index_dir, RegistryEntry};
 
 /// Searches for a `Nargo.toml` file in the current directory and all parent directories.
 /// For example, if the current directory is `/workspace/package/src`, then this function
@@ -112,11 +122,29 @@ pub fn get_package_manifest(current_path: &Path) -> Result<PathBuf, ManifestErro
     }
 }
 
+/// Returns the [PathBuf] of the directory containing the `Nargo.toml` file at `manifest_path`.
+///
+/// Returns a [ManifestError] if `manifest_path` does not point to an existing `Nargo.toml` file.
+pub fn package_root_from_manifest(manifest_path: &Path) -> Result<PathBuf, ManifestError> {
+    if manifest_path.file_name() != Some("Nargo.toml".as_ref()) {
+        return Err(ManifestError::InvalidManifestPath(manifest_path.to_path_buf()));
+    }
+    if !manifest_path.is_file() {
+        return Err(ManifestError::ReadFailed(manifest_path.to_path_buf()));
+    }
+
+    manifest_path.parent().map(Path::to_path_buf).ok_or(ManifestError::MissingParent)
+}
+
 #[derive(Debug, Deserialize, Clone)]
 struct PackageConfig {
     package: PackageMetadata,
     #[serde(default)]
-    dependencies: BTreeMap<String, DependencyConfig>,
+    dependencies: BTreeMap<String, DependencyEntry>,
+    #[serde(default)]
+    profile: ProfileConfig,
+    #[serde(default)]
+    patch: BTreeMap<String, DependencyConfig>,
 }
 
 impl PackageConfig {
@@ -124,6 +152,7 @@ impl PackageConfig {
         &self,
         root_dir: &Path,
         processed: &mut Vec<String>,
+        cached_packages: &mut CachedPackages,
     ) -> Result<Package, ManifestError> {
         let name: CrateName = if let Some(name) = &self.package.name {
             name.parse().map_err(|_| ManifestError::InvalidPackageName {
@@ -134,16 +163,41 @@ impl PackageConfig {
             return Err(ManifestError::MissingNameField { toml: root_dir.join("Nargo.toml") });
         };
 
-        let mut dependencies: BTreeMap<CrateName, Dependency> = BTreeMap::new();
-        for (name, dep_config) in self.dependencies.iter() {
-            let name = name.parse().map_err(|_| ManifestError::InvalidDependencyName {
+        let parse_dependency_name = |name: &str| {
+            name.parse::<CrateName>().map_err(|_| ManifestError::InvalidDependencyName {
                 toml: root_dir.join("Nargo.toml"),
                 name: name.into(),
-            })?;
-            let resolved_dep = dep_config.resolve_to_dependency(root_dir, processed)?;
+            })
+        };
+        let mut dependencies: BTreeMap<CrateName, Dependency> = BTreeMap::new();
+        for (name, dep_entry) in self.dependencies.iter() {
+            let name = parse_dependency_name(name)?;
+            // The dependency is imported by its alias if it has one, otherwise by its key in `[dependencies]`.
+            let import_name = match &dep_entry.alias {
+                Some(alias) => parse_dependency_name(alias)?,
+                None => name.clone(),
+            };
+            if dependencies.contains_key(&import_name) {
+                return Err(ManifestError::DuplicateDependencyName {
+                    toml: root_dir.join("Nargo.toml"),
+                    name: import_name.to_string(),
+                });
+            }
 
-            dependencies.insert(name, resolved_dep);
+            let (resolved_dep, source) = dep_entry.source.resolve_to_dependency(
+                &name,
+                root_dir,
+                processed,
+                cached_packages,
+            )?;
+            cached_packages
+                .dependency_sources
+                .insert((root_dir.to_path_buf(), import_name.clone()), source);
+            dependencies.insert(import_name, resolved_dep);
         }
+        let duplicate_name_warnings =
+            duplicate_crate_name_warnings(&root_dir.join("Nargo.toml"), &dependencies);
+        cached_packages.warnings.extend(duplicate_name_warnings);
 
         let package_type = match self.package.package_type.as_deref() {
             Some("lib") => PackageType::Library,
@@ -199,7 +253,7 @@ impl PackageConfig {
             })?;
         }
 
-        Ok(Package {
+        let package = Package {
             version: self.package.version.clone(),
             compiler_required_version: self.package.compiler_version.clone(),
             root_dir: root_dir.to_path_buf(),
@@ -207,10 +261,53 @@ impl PackageConfig {
             package_type,
             name,
             dependencies,
-        })
+            include: self.package.include.clone(),
+            exclude: self.package.exclude.clone(),
+        };
+        package.check_source_globs().map_err(|error| ManifestError::InvalidSourceGlobs {
+            toml: root_dir.join("Nargo.toml"),
+            error,
+        })?;
+
+        Ok(package)
     }
 }
 
+/// Returns a warning for each crate name which is declared by more than one of the distinct packages in
+/// `dependencies`, as declared by the manifest at `toml`.
+///
+/// Such packages can only be told apart by the names they are imported under, which is easily confused.
+/// `--strict-dependencies` rejects them as a [DependencyResolutionError::VersionConflict].
+fn duplicate_crate_name_warnings(
+    toml: &Path,
+    dependencies: &BTreeMap<CrateName, Dependency>,
+) -> Vec<String> {
+    let mut packages_by_name: BTreeMap<&CrateName, BTreeMap<&Path, Vec<String>>> = BTreeMap::new();
+    for (import_name, dependency) in dependencies {
+        let (Dependency::Local { package } | Dependency::Remote { package }) = dependency;
+        packages_by_name
+            .entry(&package.name)
+            .or_default()
+            .entry(package.root_dir.as_path())
+            .or_default()
+            .push(format!("`{import_name}`"));
+    }
+
+    packages_by_name
+        .into_iter()
+        .filter(|(_, locations)| locations.len() > 1)
+        .map(|(crate_name, locations)| {
+            let import_names: Vec<_> = locations.into_values().flatten().collect();
+            format!(
+                "Dependencies {} in {} are different packages which are all named `{crate_name}`. \
+                Set an `alias` on each of them which makes clear which package it imports",
+                import_names.join(", "),
+                toml.display()
+            )
+        })
+        .collect()
+}
+
 /// Contains all the information about a package, as loaded from a `Nargo.toml`.
 #[derive(Debug, Deserialize, Clone)]
 #[serde(untagged)]
@@ -224,14 +321,44 @@ enum Config {
     Workspace {
         #[serde(alias = "workspace")]
         workspace_config: WorkspaceConfig,
+        #[serde(default)]
+        profile: ProfileConfig,
+        #[serde(default)]
+        patch: BTreeMap<String, DependencyConfig>,
     },
 }
 
+impl Config {
+    /// Checks the semantic rules which the manifest must follow beyond being well formed, returning every issue found.
+    fn validate(&self) -> Vec<ManifestIssue> {
+        let mut issues = Vec::new();
+        let patch = match self {
+            Config::Package { package_config } => {
+                for (key, entry) in &package_config.dependencies {
+                    let import_name = entry.alias.as_ref().unwrap_or(key);
+                    if package_config.package.name.as_ref() == Some(import_name) {
+                        issues.push(ManifestIssue::DependencyNamedAfterPackage {
+                            dependency: key.clone(),
+                        });
+                    }
+                    entry.source.validate(key, &mut issues);
+                }
+                &package_config.patch
+            }
+            Config::Workspace { patch, .. } => patch,
+        };
+        for (name, source) in patch {
+            source.validate(name, &mut issues);
+        }
+        issues
+    }
+}
+
 impl TryFrom<String> for Config {
     type Error = toml::de::Error;
 
     fn try_from(toml: String) -> Result<Self, Self::Error> {
-        toml::from_str(&toml)
+        Config::try_from(toml.as_str())
     }
 }
 
@@ -239,14 +366,129 @@ impl TryFrom<&str> for Config {
     type Error = toml::de::Error;
 
     fn try_from(toml: &str) -> Result<Self, Self::Error> {
-        toml::from_str(toml)
+        let toml: toml::Value = toml::from_str(toml)?;
+        Config::try_from(toml)
+    }
+}
+
+/// Deprecated keys are accepted, but the warnings about them are discarded.
+/// Use [read_toml] to find out which deprecated keys a manifest uses.
+impl TryFrom<toml::Value> for Config {
+    type Error = toml::de::Error;
+
+    fn try_from(mut toml: toml::Value) -> Result<Self, Self::Error> {
+        normalize_deprecated_keys(&mut toml);
+        toml.try_into()
     }
 }
 
+/// Keys of the `[package]` table which have been renamed, mapped to their current name.
+///
+/// Manifests using these keys are still accepted, but a deprecation warning is emitted.
+const DEPRECATED_PACKAGE_KEYS: &[(&str, &str)] = &[("compiler-version", "compiler_version")];
+
+/// Renames any deprecated keys in the manifest to their current name.
+///
+/// Returns a warning message for each deprecated key which was found.
+fn normalize_deprecated_keys(toml: &mut toml::Value) -> Vec<String> {
+    let Some(package) = toml.get_mut("package").and_then(toml::Value::as_table_mut) else {
+        return Vec::new();
+    };
+
+    let mut warnings = Vec::new();
+    for (deprecated_key, current_key) in DEPRECATED_PACKAGE_KEYS {
+        let Some(value) = package.remove(*deprecated_key) else {
+            continue;
+        };
+
+        if package.contains_key(*current_key) {
+            warnings.push(format!(
+                "`{deprecated_key}` is deprecated and has been ignored as `{current_key}` is also set"
+            ));
+        } else {
+            warnings.push(format!("`{deprecated_key}` is deprecated, use `{current_key}` instead"));
+            package.insert(current_key.to_string(), value);
+        }
+    }
+    warnings
+}
+
+/// Top-level key of a `Nargo.toml` naming a file whose `[dependencies]` are shared with this manifest.
+const INCLUDE_KEY: &str = "include";
+
+/// Merges the `[dependencies]` table of the file named by the manifest's `include` key into the manifest.
+///
+/// The include path, as well as any `path` dependencies within the included file, are resolved relative to the
+/// file in which they are written. Dependencies declared in the manifest itself take precedence over included ones.
+///
+/// Returns the names of any included dependencies which were overridden by the manifest.
+fn merge_included_dependencies(
+    toml: &mut toml::Value,
+    toml_path: &Path,
+) -> Result<Vec<String>, ManifestError> {
+    let Some(include) = toml.get(INCLUDE_KEY) else {
+        return Ok(Vec::new());
+    };
+    let invalid_include = || ManifestError::InvalidInclude {
+        toml: toml_path.to_path_buf(),
+        include: include.to_string(),
+    };
+    let include = include.as_str().ok_or_else(invalid_include)?;
+
+    let root_dir = toml_path.parent().ok_or(ManifestError::MissingParent)?;
+    let include_path = root_dir.join(include).normalize();
+    let include_dir = include_path.parent().ok_or(ManifestError::MissingParent)?;
+    let include_bytes = std::fs::read(&include_path)
+        .map_err(|_| ManifestError::ReadFailed(include_path.to_path_buf()))?;
+    let include_string = decode_manifest(include_bytes)
+        .ok_or_else(|| ManifestError::InvalidEncoding(include_path.to_path_buf()))?;
+    let mut included: toml::Table = toml::from_str(&include_string)?;
+    let included_dependencies = match included.remove("dependencies") {
+        Some(toml::Value::Table(dependencies)) => dependencies,
+        Some(_) => return Err(invalid_include()),
+        None => return Ok(Vec::new()),
+    };
+
+    let Some(dependencies) = toml
+        .as_table_mut()
+        .map(|toml| {
+            toml.entry("dependencies").or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        })
+        .and_then(toml::Value::as_table_mut)
+    else {
+        // The manifest's own `[dependencies]` is malformed, this is reported when deserializing the manifest.
+        return Ok(Vec::new());
+    };
+
+    let mut overridden = Vec::new();
+    for (name, mut dependency) in included_dependencies {
+        if dependencies.contains_key(&name) {
+            overridden.push(name);
+            continue;
+        }
+        if let Some(toml::Value::String(path)) = dependency.get_mut("path") {
+            *path = include_dir.join(&*path).to_string_lossy().into_owned();
+        }
+        dependencies.insert(name, dependency);
+    }
+    Ok(overridden)
+}
+
+/// Returns the names of the dependencies in the manifest at `toml_path` which override a dependency of the same name
+/// from its included file.
+pub(crate) fn overridden_included_dependencies(
+    toml_path: &Path,
+) -> Result<Vec<String>, ManifestError> {
+    let mut toml = read_toml_value(toml_path)?;
+    merge_included_dependencies(&mut toml, toml_path)
+}
+
 /// Tracks the root_dir of a `Nargo.toml` and the contents inside the file.
 struct NargoToml {
     root_dir: PathBuf,
     config: Config,
+    /// Warnings about deprecated keys used in the file.
+    warnings: Vec<String>,
 }
 
 #[derive(Default, Debug, Deserialize, Clone)]
@@ -258,6 +500,44 @@ struct WorkspaceConfig {
     default_member: Option<PathBuf>,
 }
 
+/// The `[profile]` section of a `Nargo.toml`, holding defaults for command line options.
+#[derive(Default, Debug, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+struct ProfileConfig {
+    allow_warnings: Option<bool>,
+    #[serde(alias = "proofs_dir")]
+    proofs_dir: Option<PathBuf>,
+    backend: Option<String>,
+    #[serde(default)]
+    features: Vec<String>,
+}
+
+impl ProfileConfig {
+    /// Checks that every feature enabled by the profile of the manifest at `toml` is known to the compiler.
+    fn validate(&self, toml: &Path) -> Result<(), ManifestError> {
+        let unknown_feature =
+            self.features.iter().find(|feature| !KNOWN_FEATURES.contains(&feature.as_str()));
+        match unknown_feature {
+            Some(feature) => Err(ManifestError::UnknownFeature {
+                toml: toml.to_path_buf(),
+                feature: feature.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<ProfileConfig> for Profile {
+    fn from(profile: ProfileConfig) -> Self {
+        Profile {
+            allow_warnings: profile.allow_warnings,
+            proofs_dir: profile.proofs_dir,
+            backend: profile.backend,
+            features: profile.features,
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Default, Debug, Deserialize, Clone)]
 struct PackageMetadata {
@@ -275,26 +555,97 @@ struct PackageMetadata {
     // so you will not need to supply an ACIR and compiler version
     compiler_version: Option<String>,
     license: Option<String>,
+    /// Globs, relative to the package root, of the source files to compile. Every source file is compiled if empty.
+    #[serde(default)]
+    include: Vec<String>,
+    /// Globs, relative to the package root, of source files which should not be compiled.
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+/// An entry in the `[dependencies]` table of a manifest.
+#[derive(Debug, Deserialize, Clone)]
+struct DependencyEntry {
+    #[serde(flatten)]
+    source: DependencyConfig,
+    /// The name by which the dependency is imported, if it should differ from its key in `[dependencies]`.
+    ///
+    /// This allows depending on two packages with the same name, e.g. two versions of the same library.
+    alias: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(untagged)]
 /// Enum representing the different types of ways to
-/// supply a source for the dependency
+/// supply a source for the dependency.
+///
+/// `Registry` dependencies are looked up by name and version in the registry index configured through `NARGO_REGISTRY`.
 enum DependencyConfig {
     Github { git: String, tag: String, directory: Option<String> },
     Path { path: String },
+    Registry { version: String },
 }
 
 impl DependencyConfig {
+    /// Records an issue for each required string of the source `dependency` which is empty.
+    fn validate(&self, dependency: &str, issues: &mut Vec<ManifestIssue>) {
+        let dependency = dependency.to_string();
+        match self {
+            Self::Github { git, tag, directory } => {
+                if git.is_empty() {
+                    issues
+                        .push(ManifestIssue::EmptyGitRepository { dependency: dependency.clone() });
+                }
+                if tag.is_empty() {
+                    issues.push(ManifestIssue::EmptyGitTag { dependency: dependency.clone() });
+                }
+                if directory.as_ref().is_some_and(String::is_empty) {
+                    issues.push(ManifestIssue::EmptyPath { dependency, key: "directory" });
+                }
+            }
+            Self::Path { path } => {
+                if path.is_empty() {
+                    issues.push(ManifestIssue::EmptyPath { dependency, key: "path" });
+                }
+            }
+            Self::Registry { .. } => {}
+        }
+    }
+
+    /// Resolves the dependency from its `[patch]` if it has one, otherwise from this source.
+    ///
+    /// Returns the dependency along with the source which it was actually resolved from.
     fn resolve_to_dependency(
         &self,
+        name: &CrateName,
+        pkg_root: &Path,
+        processed: &mut Vec<String>,
+        cached_packages: &mut CachedPackages,
+    ) -> Result<(Dependency, DependencySource), ManifestError> {
+        match cached_packages.patches.get(name).cloned() {
+            Some(patch) => patch.resolve_source(name, pkg_root, processed, cached_packages),
+            None => self.resolve_source(name, pkg_root, processed, cached_packages),
+        }
+    }
+
+    /// Resolves the dependency from this source, ignoring any `[patch]` of the dependency.
+    fn resolve_source(
+        &self,
+        name: &CrateName,
         pkg_root: &Path,
         processed: &mut Vec<String>,
-    ) -> Result<Dependency, ManifestError> {
-        let dep = match self {
+        cached_packages: &mut CachedPackages,
+    ) -> Result<(Dependency, DependencySource), ManifestError> {
+        let (dep, source) = match self {
             Self::Github { git, tag, directory } => {
-                let dir_path = clone_git_repo(git, tag).map_err(ManifestError::GitError)?;
+                let (dir_path, commit) = clone_git_repo(
+                    git,
+                    tag,
+                    cached_packages.network_timeout,
+                    cached_packages.refreshed_git_clones.as_mut(),
+                )
+                .map_err(ManifestError::GitError)?;
+                cached_packages.record_git_commit(dir_path.clone(), git, tag, commit.clone());
                 let project_path = if let Some(directory) = directory {
                     let internal_path = dir_path.join(directory).normalize();
                     if !internal_path.starts_with(&dir_path) {
@@ -308,35 +659,95 @@ impl DependencyConfig {
                     dir_path
                 };
                 let toml_path = project_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
-                Dependency::Remote { package }
+                let package = resolve_dependency_from_toml(&toml_path, processed, cached_packages)?;
+                let source = DependencySource::Git {
+                    git: git.clone(),
+                    tag: tag.clone(),
+                    directory: directory.clone(),
+                    commit,
+                };
+                (Dependency::Remote { package }, source)
             }
             Self::Path { path } => {
                 let dir_path = pkg_root.join(path);
                 let toml_path = dir_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
-                Dependency::Local { package }
+                if !toml_path.is_file() {
+                    return Err(ManifestError::DependencyResolutionError(
+                        DependencyResolutionError::MissingPathDependency {
+                            dependency: name.clone(),
+                            path: dir_path.normalize(),
+                            is_dir: dir_path.is_dir(),
+                        },
+                    ));
+                }
+                let package = resolve_dependency_from_toml(&toml_path, processed, cached_packages)?;
+                (Dependency::Local { package }, DependencySource::Path { path: path.clone() })
+            }
+            Self::Registry { version } => {
+                // Registry packages are published as git repositories so are fetched like any other git dependency.
+                let index_dir = registry_index_dir(&name.to_string())?;
+                let RegistryEntry { git, tag, directory } =
+                    lookup_registry_entry(&index_dir, &name.to_string(), version)?;
+                let git_config = Self::Github { git, tag, directory };
+                let (dep, _) =
+                    git_config.resolve_source(name, pkg_root, processed, cached_packages)?;
+                return Ok((dep, DependencySource::Registry { version: version.clone() }));
             }
         };
 
-        // Cannot depend on a binary
-        // TODO: Can we depend upon contracts?
-        if dep.is_binary() {
-            Err(ManifestError::BinaryDependency(dep.package_name().clone()))
-        } else {
-            Ok(dep)
+        // Only libraries can be depended upon. Binaries and contracts are compiled from their entrypoints,
+        // which are only compiled for the root crate and so would be silently ignored in a dependency.
+        match dep.package_type() {
+            PackageType::Library => Ok((dep, source)),
+            PackageType::Binary => Err(ManifestError::BinaryDependency(dep.package_name().clone())),
+            PackageType::Contract => {
+                Err(ManifestError::ContractDependency(dep.package_name().clone()))
+            }
         }
     }
 }
 
+/// Records the `[patch]` table of the root manifest in `root_dir`, which replaces the source of every dependency of
+/// the same name throughout the workspace, including transitive ones. The `[patch]` tables of dependencies are ignored.
+///
+/// Patches with a `path` are resolved relative to `root_dir`.
+fn record_patches(
+    patch: &BTreeMap<String, DependencyConfig>,
+    root_dir: &Path,
+    cached_packages: &mut CachedPackages,
+) -> Result<(), ManifestError> {
+    for (name, source) in patch {
+        let name = name.parse::<CrateName>().map_err(|_| ManifestError::InvalidDependencyName {
+            toml: root_dir.join("Nargo.toml"),
+            name: name.clone(),
+        })?;
+        let source = match source {
+            DependencyConfig::Path { path } => {
+                DependencyConfig::Path { path: root_dir.join(path).to_string_lossy().into_owned() }
+            }
+            source => source.clone(),
+        };
+        cached_packages.patches.insert(name, source);
+    }
+    Ok(())
+}
+
 fn toml_to_workspace(
     nargo_toml: NargoToml,
     package_selection: PackageSelection,
+    cached_packages: &mut CachedPackages,
 ) -> Result<Workspace, ManifestError> {
+    cached_packages.warnings.extend(nargo_toml.warnings);
     let mut resolved = Vec::new();
-    let workspace = match nargo_toml.config {
+    let mut workspace = match nargo_toml.config {
         Config::Package { package_config } => {
-            let member = package_config.resolve_to_package(&nargo_toml.root_dir, &mut resolved)?;
+            package_config.profile.validate(&nargo_toml.root_dir.join("Nargo.toml"))?;
+            record_patches(&package_config.patch, &nargo_toml.root_dir, cached_packages)?;
+            let member = package_config.resolve_to_package(
+                &nargo_toml.root_dir,
+                &mut resolved,
+                cached_packages,
+            )?;
             match &package_selection {
                 PackageSelection::Selected(selected_name) if selected_name != &member.name => {
                     return Err(ManifestError::MissingSelectedPackage(member.name))
@@ -346,16 +757,21 @@ fn toml_to_workspace(
                     selected_package_index: Some(0),
                     members: vec![member],
                     is_assumed: false,
+                    profile: package_config.profile.into(),
+                    warnings: Vec::new(),
                 },
             }
         }
-        Config::Workspace { workspace_config } => {
+        Config::Workspace { workspace_config, profile, patch } => {
+            profile.validate(&nargo_toml.root_dir.join("Nargo.toml"))?;
+            record_patches(&patch, &nargo_toml.root_dir, cached_packages)?;
             let mut members = Vec::new();
             let mut selected_package_index = None;
             for (index, member_path) in workspace_config.members.into_iter().enumerate() {
                 let package_root_dir = nargo_toml.root_dir.join(&member_path);
                 let package_toml_path = package_root_dir.join("Nargo.toml");
-                let member = resolve_package_from_toml(&package_toml_path, &mut resolved)?;
+                let member =
+                    resolve_package_from_toml(&package_toml_path, &mut resolved, cached_packages)?;
 
                 match &package_selection {
                     PackageSelection::Selected(selected_name) => {
@@ -398,20 +814,54 @@ fn toml_to_workspace(
                 members,
                 selected_package_index,
                 is_assumed: false,
+                profile: profile.into(),
+                warnings: Vec::new(),
             }
         }
     };
 
+    // The same manifest may be read more than once, so each warning is only reported once.
+    let mut warnings = Vec::new();
+    for warning in std::mem::take(&mut cached_packages.warnings) {
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+    workspace.warnings = warnings;
+
     Ok(workspace)
 }
 
+/// Decodes the contents of a manifest file, stripping any UTF-8 byte order mark and normalizing line endings.
+///
+/// Returns `None` if the manifest is not valid UTF-8.
+fn decode_manifest(bytes: Vec<u8>) -> Option<String> {
+    const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(&bytes);
+    let contents = std::str::from_utf8(bytes).ok()?;
+    Some(contents.replace("\r\n", "\n"))
+}
+
+fn read_toml_value(toml_path: &Path) -> Result<toml::Value, ManifestError> {
+    let toml_bytes =
+        std::fs::read(toml_path).map_err(|_| ManifestError::ReadFailed(toml_path.to_path_buf()))?;
+    let toml_as_string = decode_manifest(toml_bytes)
+        .ok_or_else(|| ManifestError::InvalidEncoding(toml_path.to_path_buf()))?;
+    Ok(toml::from_str(&toml_as_string)?)
+}
+
 fn read_toml(toml_path: &Path) -> Result<NargoToml, ManifestError> {
     let toml_path = toml_path.normalize();
-    let toml_as_string = std::fs::read_to_string(&toml_path)
-        .map_err(|_| ManifestError::ReadFailed(toml_path.to_path_buf()))?;
+    let mut toml = read_toml_value(&toml_path)?;
+    merge_included_dependencies(&mut toml, &toml_path)?;
     let root_dir = toml_path.parent().ok_or(ManifestError::MissingParent)?;
+    let warnings = normalize_deprecated_keys(&mut toml)
+        .into_iter()
+        .map(|warning| format!("{warning} in {}", toml_path.display()))
+        .collect();
     let nargo_toml =
-        NargoToml { root_dir: root_dir.to_path_buf(), config: toml_as_string.try_into()? };
+        NargoToml { root_dir: root_dir.to_path_buf(), config: toml.try_into()?, warnings };
 
     Ok(nargo_toml)
 }
@@ -420,6 +870,7 @@ fn read_toml(toml_path: &Path) -> Result<NargoToml, ManifestError> {
 fn resolve_package_from_toml(
     toml_path: &Path,
     processed: &mut Vec<String>,
+    cached_packages: &mut CachedPackages,
 ) -> Result<Package, ManifestError> {
     // Checks for cyclic dependencies
     let str_path = toml_path.to_str().expect("ICE - path is empty");
@@ -435,16 +886,25 @@ fn resolve_package_from_toml(
         message += str_path;
         return Err(ManifestError::CyclicDependency { cycle: message });
     }
+    if processed.len() >= cached_packages.max_dependency_depth {
+        return Err(ManifestError::DependencyResolutionError(
+            DependencyResolutionError::DependencyTooDeep {
+                manifest: toml_path.to_path_buf(),
+                max_depth: cached_packages.max_dependency_depth,
+            },
+        ));
+    }
     // Adds the package to the set of resolved packages
     if let Some(str) = toml_path.to_str() {
         processed.push(str.to_string());
     }
 
     let nargo_toml = read_toml(toml_path)?;
+    cached_packages.warnings.extend(nargo_toml.warnings);
 
     let result = match nargo_toml.config {
         Config::Package { package_config } => {
-            package_config.resolve_to_package(&nargo_toml.root_dir, processed)
+            package_config.resolve_to_package(&nargo_toml.root_dir, processed, cached_packages)
         }
         Config::Workspace { .. } => {
             Err(ManifestError::UnexpectedWorkspace(toml_path.to_path_buf()))
@@ -456,6 +916,23 @@ fn resolve_package_from_toml(
     result
 }
 
+/// Resolves a dependency's Nargo.toml file into a `Package`, reusing any previously resolved package which has the
+/// same location or contents.
+fn resolve_dependency_from_toml(
+    toml_path: &Path,
+    processed: &mut Vec<String>,
+    cached_packages: &mut CachedPackages,
+) -> Result<Package, ManifestError> {
+    let root_dir =
+        toml_path.normalize().parent().ok_or(ManifestError::MissingParent)?.to_path_buf();
+    if let Some(package) = cached_packages.get(&root_dir) {
+        return Ok(package);
+    }
+
+    let package = resolve_package_from_toml(toml_path, processed, cached_packages)?;
+    cache_dep(package, cached_packages)
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum PackageSelection {
     Selected(CrateName),
@@ -463,18 +940,207 @@ pub enum PackageSelection {
     All,
 }
 
+/// Settings which control how the dependencies of a workspace are resolved.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResolveOptions {
+    /// Clone every git dependency again rather than reusing the clones cached by previous resolutions.
+    ///
+    /// The fresh clones replace the cached ones, so subsequent resolutions of the workspace will use them.
+    pub bypass_git_cache: bool,
+}
+
+/// A workspace along with what was learnt about its dependencies while resolving it.
+#[derive(Clone)]
+pub struct ResolvedWorkspace {
+    pub workspace: Workspace,
+    /// The commit which each git dependency of the selected packages resolved to, sorted by repository and tag.
+    pub git_dependencies: Vec<ResolvedGitDependency>,
+    /// The dependency graph of the selected packages.
+    pub dependency_graph: DependencyGraph,
+}
+
 /// Resolves a Nargo.toml file into a `Workspace` struct as defined by our `nargo` core.
 pub fn resolve_workspace_from_toml(
     toml_path: &Path,
     package_selection: PackageSelection,
     current_compiler_version: Option<String>,
 ) -> Result<Workspace, ManifestError> {
+    let resolved = resolve_workspace_from_toml_with_options(
+        toml_path,
+        package_selection,
+        current_compiler_version,
+        ResolveOptions::default(),
+    )?;
+    Ok(resolved.workspace)
+}
+
+/// Resolves a Nargo.toml file into a `Workspace` according to `options`, also returning what was learnt about the
+/// workspace's dependencies while resolving them.
+pub fn resolve_workspace_from_toml_with_options(
+    toml_path: &Path,
+    package_selection: PackageSelection,
+    current_compiler_version: Option<String>,
+    options: ResolveOptions,
+) -> Result<ResolvedWorkspace, ManifestError> {
     let nargo_toml = read_toml(toml_path)?;
-    let workspace = toml_to_workspace(nargo_toml, package_selection)?;
+    let mut cached_packages = if options.bypass_git_cache {
+        CachedPackages::bypassing_git_cache()
+    } else {
+        CachedPackages::default()
+    };
+    cached_packages.max_dependency_depth = cache::max_dependency_depth_from_env()?;
+    cached_packages.network_timeout = git::network_timeout_from_env()?;
+    let workspace = toml_to_workspace(nargo_toml, package_selection, &mut cached_packages)?;
     if let Some(current_compiler_version) = current_compiler_version {
         semver::semver_check_workspace(&workspace, current_compiler_version)?;
     }
-    Ok(workspace)
+    let git_dependencies = cached_packages.git_dependencies_of(&workspace);
+    let dependency_graph =
+        DependencyGraph::from_workspace(&workspace, &cached_packages.dependency_sources);
+    Ok(ResolvedWorkspace { workspace, git_dependencies, dependency_graph })
+}
+
+/// Resolves a standalone Noir source file, which has no `Nargo.toml`, into a `Workspace` containing a single binary
+/// package whose entrypoint is that file.
+///
+/// The package is named after the file and has no dependencies other than the standard library. Only the file itself
+/// is included in the package, not any other source files in its directory.
+pub fn resolve_workspace_from_entry_file(entry_path: &Path) -> Result<Workspace, ManifestError> {
+    let entry_path = entry_path
+        .canonicalize()
+        .map_err(|_| ManifestError::ReadFailed(entry_path.to_path_buf()))?;
+    let root_dir = entry_path.parent().ok_or(ManifestError::MissingParent)?.to_path_buf();
+    let file_stem = entry_path.file_stem().unwrap_or_default().to_string_lossy().replace('-', "_");
+    let name = file_stem.parse().map_err(|_| ManifestError::InvalidPackageName {
+        toml: entry_path.clone(),
+        name: file_stem.clone(),
+    })?;
+    // Escape the file name so that it is matched literally as a glob.
+    let include_glob: String = entry_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .chars()
+        .map(|c| if matches!(c, '?' | '*' | '[' | ']') { format!("[{c}]") } else { c.to_string() })
+        .collect();
+
+    let package = Package {
+        version: None,
+        compiler_required_version: None,
+        root_dir: root_dir.clone(),
+        package_type: PackageType::Binary,
+        entry_path,
+        name,
+        dependencies: BTreeMap::new(),
+        include: vec![include_glob],
+        exclude: Vec::new(),
+    };
+    Ok(Workspace {
+        root_dir,
+        members: vec![package],
+        selected_package_index: Some(0),
+        is_assumed: true,
+        profile: Profile::default(),
+        warnings: Vec::new(),
+    })
+}
+
+/// Resolves a Nargo.toml file and returns the paths of every source file belonging to the selected packages and their
+/// dependencies, without compiling them.
+///
+/// This allows editor tooling to index sources across dependency boundaries.
+pub fn resolve_workspace_files_from_toml(
+    toml_path: &Path,
+    package_selection: PackageSelection,
+) -> Result<Vec<PathBuf>, ManifestError> {
+    let workspace = resolve_workspace_from_toml(toml_path, package_selection, None)?;
+    Ok(nargo::get_all_files_in_workspace(&workspace))
+}
+
+/// Resolves the full dependency graph of a Nargo.toml file into plain data, without preparing any of its packages
+/// for compilation.
+///
+/// This allows tooling such as dependency auditors to inspect where each dependency was requested from and
+/// which package it resolved to.
+pub fn resolve_dependency_graph(
+    toml_path: &Path,
+    package_selection: PackageSelection,
+) -> Result<DependencyGraph, ManifestError> {
+    let resolved = resolve_workspace_from_toml_with_options(
+        toml_path,
+        package_selection,
+        None,
+        ResolveOptions::default(),
+    )?;
+    Ok(resolved.dependency_graph)
+}
+
+/// Reads the `[profile]` section of a Nargo.toml file, without resolving the workspace's dependencies.
+pub fn read_profile_from_toml(toml_path: &Path) -> Result<Profile, ManifestError> {
+    let nargo_toml = read_toml(toml_path)?;
+    let profile = match nargo_toml.config {
+        Config::Package { package_config } => package_config.profile,
+        Config::Workspace { profile, .. } => profile,
+    };
+    profile.validate(toml_path)?;
+    Ok(profile.into())
+}
+
+/// Checks that the Nargo.toml file, and the manifest of each member if it is a workspace, follow the rules which
+/// cannot be expressed by its format, without resolving any dependencies.
+///
+/// The issues found in every invalid manifest are reported together in a [ManifestError::InvalidManifests].
+pub fn validate_manifest(toml_path: &Path) -> Result<(), ManifestError> {
+    let mut manifests = Vec::new();
+    collect_manifest_issues(toml_path, &mut manifests)?;
+    if manifests.is_empty() {
+        Ok(())
+    } else {
+        Err(ManifestError::InvalidManifests { manifests })
+    }
+}
+
+/// Appends the issues found in the Nargo.toml file at `toml_path`, and in the manifests of its members if it is a
+/// workspace, to `manifests`.
+fn collect_manifest_issues(
+    toml_path: &Path,
+    manifests: &mut Vec<(PathBuf, Vec<ManifestIssue>)>,
+) -> Result<(), ManifestError> {
+    let nargo_toml = read_toml(toml_path)?;
+    let issues = nargo_toml.config.validate();
+    if !issues.is_empty() {
+        manifests.push((toml_path.to_path_buf(), issues));
+    }
+
+    if let Config::Workspace { workspace_config, .. } = nargo_toml.config {
+        for member in workspace_config.members {
+            collect_manifest_issues(
+                &nargo_toml.root_dir.join(member).join("Nargo.toml"),
+                manifests,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every package in the dependency graph of the workspace's selected packages resolves to a single
+/// location.
+///
+/// Transitive dependencies which request the same library at different refs result in a
+/// [DependencyResolutionError::VersionConflict] naming the packages which requested each ref.
+/// Manifests which override a dependency from their `include`d file result in a
+/// [DependencyResolutionError::IncludeConflict].
+pub fn check_dependency_conflicts(workspace: &Workspace) -> Result<(), ManifestError> {
+    conflicts::check_workspace_dependency_conflicts(workspace)?;
+    conflicts::check_workspace_include_conflicts(workspace)
+}
+
+/// Checks that each selected package in the workspace is ready to be published as a library.
+///
+/// A publishable package has no binary entrypoint, a valid `compiler_version`, a `license` and no dependencies
+/// specified by a local `path`. Every failing condition of a package is reported together in a [ManifestError::NotPublishable].
+pub fn check_publishable(workspace: &Workspace) -> Result<(), ManifestError> {
+    publish::check_workspace_publishable(workspace)
 }
 
 #[test]
@@ -531,3 +1197,676 @@ fn parse_workspace_default_member_toml() {
     assert!(Config::try_from(String::from(src)).is_ok());
     assert!(Config::try_from(src).is_ok());
 }
+
+#[test]
+fn parse_package_toml_with_deprecated_keys() {
+    let src = r#"
+        [package]
+        name = "test"
+        compiler-version = ">=0.1.0"
+    "#;
+
+    let mut toml: toml::Value = toml::from_str(src).unwrap();
+    let warnings = normalize_deprecated_keys(&mut toml);
+    assert_eq!(warnings, vec!["`compiler-version` is deprecated, use `compiler_version` instead"]);
+
+    let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.package.compiler_version.as_deref(), Some(">=0.1.0"));
+}
+
+#[test]
+fn parse_package_toml_prefers_current_key_over_deprecated_key() {
+    let src = r#"
+        [package]
+        name = "test"
+        compiler-version = ">=0.1.0"
+        compiler_version = ">=0.2.0"
+    "#;
+
+    let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.package.compiler_version.as_deref(), Some(">=0.2.0"));
+}
+
+#[test]
+fn resolution_returns_deprecation_warnings() {
+    let root = tempfile::tempdir().unwrap();
+    let toml_path = root.path().join("Nargo.toml");
+    std::fs::create_dir(root.path().join("src")).unwrap();
+    std::fs::write(root.path().join("src").join("lib.nr"), "pub fn foo() {}").unwrap();
+    std::fs::write(
+        &toml_path,
+        r#"
+        [package]
+        name = "test"
+        type = "lib"
+        compiler-version = ">=0.1.0"
+    "#,
+    )
+    .unwrap();
+
+    let workspace = resolve_workspace_from_toml(&toml_path, PackageSelection::All, None).unwrap();
+    assert_eq!(workspace.warnings.len(), 1);
+    assert!(workspace.warnings[0].starts_with("`compiler-version` is deprecated"));
+}
+
+#[test]
+fn validation_reports_every_invalid_member() {
+    let root = tempfile::tempdir().unwrap();
+    std::fs::write(
+        root.path().join("Nargo.toml"),
+        r#"
+        [workspace]
+        members = ["a", "b"]
+    "#,
+    )
+    .unwrap();
+    for member in ["a", "b"] {
+        std::fs::create_dir(root.path().join(member)).unwrap();
+        std::fs::write(
+            root.path().join(member).join("Nargo.toml"),
+            format!(
+                r#"
+                [package]
+                name = "{member}"
+                type = "lib"
+
+                [dependencies]
+                dep = {{ git = "", tag = "v1.0.0" }}
+            "#
+            ),
+        )
+        .unwrap();
+    }
+
+    let Err(ManifestError::InvalidManifests { manifests }) =
+        validate_manifest(&root.path().join("Nargo.toml"))
+    else {
+        panic!("both members should be reported as invalid");
+    };
+    assert_eq!(manifests.len(), 2);
+}
+
+#[test]
+fn decode_manifest_with_bom_and_crlf() {
+    let src = "\u{feff}[package]\r\nname = \"test\"\r\ntype = \"bin\"\r\n";
+
+    let decoded = decode_manifest(src.as_bytes().to_vec()).unwrap();
+    assert_eq!(decoded, "[package]\nname = \"test\"\ntype = \"bin\"\n");
+    assert!(Config::try_from(decoded).is_ok());
+}
+
+#[test]
+fn decode_manifest_rejects_invalid_utf8() {
+    assert!(decode_manifest(vec![b'[', 0xFF, 0xFE, b']']).is_none());
+}
+
+#[test]
+fn identical_dependencies_resolve_to_the_same_package() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let write_package = |dir: &str, manifest: &str, entry: &str| {
+        let package_dir = temp_dir.path().join(dir);
+        std::fs::create_dir_all(package_dir.join("src")).unwrap();
+        std::fs::write(package_dir.join("Nargo.toml"), manifest).unwrap();
+        std::fs::write(package_dir.join("src").join(entry), "pub fn foo() {}").unwrap();
+    };
+
+    let foo_manifest = r#"
+        [package]
+        name = "foo"
+        type = "lib"
+    "#;
+    // `root` reaches `foo` through a copy while `bar` reaches the original.
+    write_package("foo", foo_manifest, "lib.nr");
+    write_package("foo_copy", foo_manifest, "lib.nr");
+    write_package(
+        "bar",
+        r#"
+        [package]
+        name = "bar"
+        type = "lib"
+
+        [dependencies]
+        foo = { path = "../foo" }
+    "#,
+        "lib.nr",
+    );
+    write_package(
+        "root",
+        r#"
+        [package]
+        name = "root"
+        type = "bin"
+
+        [dependencies]
+        foo = { path = "../foo_copy" }
+        bar = { path = "../bar" }
+    "#,
+        "main.nr",
+    );
+
+    let workspace = resolve_workspace_from_toml(
+        &temp_dir.path().join("root").join("Nargo.toml"),
+        PackageSelection::All,
+        None,
+    )
+    .unwrap();
+
+    let dependency = |package: &Package, name: &str| match &package.dependencies
+        [&name.parse::<CrateName>().unwrap()]
+    {
+        Dependency::Local { package } | Dependency::Remote { package } => package.clone(),
+    };
+    let root = &workspace.members[0];
+    let foo_from_root = dependency(root, "foo");
+    let foo_from_bar = dependency(&dependency(root, "bar"), "foo");
+    assert_eq!(foo_from_root.root_dir, foo_from_bar.root_dir);
+}
+
+#[test]
+fn aliased_dependencies_are_imported_by_their_alias() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let write_package = |dir: &str, manifest: &str, entry: &str| {
+        let package_dir = temp_dir.path().join(dir);
+        std::fs::create_dir_all(package_dir.join("src")).unwrap();
+        std::fs::write(package_dir.join("Nargo.toml"), manifest).unwrap();
+        std::fs::write(package_dir.join("src").join(entry), "pub fn foo() {}").unwrap();
+    };
+
+    let foo_manifest = r#"
+        [package]
+        name = "foo"
+        type = "lib"
+    "#;
+    write_package("foo_v1", foo_manifest, "lib.nr");
+    write_package("foo_v2", foo_manifest, "lib.nr");
+    write_package(
+        "root",
+        r#"
+        [package]
+        name = "root"
+        type = "bin"
+
+        [dependencies]
+        foo = { path = "../foo_v1" }
+        foo_v2 = { path = "../foo_v2", alias = "new_foo" }
+    "#,
+        "main.nr",
+    );
+
+    let root_toml = temp_dir.path().join("root").join("Nargo.toml");
+    let workspace = resolve_workspace_from_toml(&root_toml, PackageSelection::All, None).unwrap();
+    let dependency_names: Vec<_> =
+        workspace.members[0].dependencies.keys().map(ToString::to_string).collect();
+    assert_eq!(dependency_names, vec!["foo", "new_foo"]);
+
+    let manifest = std::fs::read_to_string(&root_toml).unwrap();
+    std::fs::write(&root_toml, manifest.replace(r#"alias = "new_foo""#, r#"alias = "foo""#))
+        .unwrap();
+    assert!(matches!(
+        resolve_workspace_from_toml(&root_toml, PackageSelection::All, None),
+        Err(ManifestError::DuplicateDependencyName { name, .. }) if name == "foo"
+    ));
+}
+
+#[test]
+fn rejects_dependency_chains_deeper_than_the_limit() {
+    // `package_0` depends on `package_1`, which depends on `package_2` and so on.
+    let temp_dir = tempfile::tempdir().unwrap();
+    for index in 0..4 {
+        let package_dir = temp_dir.path().join(format!("package_{index}"));
+        std::fs::create_dir_all(package_dir.join("src")).unwrap();
+        let dependency = if index < 3 {
+            format!("package_{} = {{ path = \"../package_{}\" }}", index + 1, index + 1)
+        } else {
+            String::new()
+        };
+        let manifest = format!(
+            "[package]\nname = \"package_{index}\"\ntype = \"lib\"\n\n[dependencies]\n{dependency}\n"
+        );
+        std::fs::write(package_dir.join("Nargo.toml"), manifest).unwrap();
+        std::fs::write(package_dir.join("src").join("lib.nr"), "").unwrap();
+    }
+
+    let resolve = |max_dependency_depth| {
+        let nargo_toml = read_toml(&temp_dir.path().join("package_0").join("Nargo.toml")).unwrap();
+        let mut cached_packages = CachedPackages::default();
+        cached_packages.max_dependency_depth = max_dependency_depth;
+        toml_to_workspace(nargo_toml, PackageSelection::All, &mut cached_packages)
+    };
+    assert!(resolve(3).is_ok());
+    let Err(ManifestError::DependencyResolutionError(
+        DependencyResolutionError::DependencyTooDeep { manifest, max_depth: 2 },
+    )) = resolve(2)
+    else {
+        panic!("expected the dependency chain to be too deep");
+    };
+    assert!(manifest.ends_with("package_3/Nargo.toml"));
+}
+
+#[test]
+fn resolves_a_standalone_file_into_a_binary_package() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let entry_path = temp_dir.path().join("hello-world.nr");
+    std::fs::write(&entry_path, "fn main() {}").unwrap();
+
+    let workspace = resolve_workspace_from_entry_file(&entry_path).unwrap();
+    let package = workspace.into_iter().next().unwrap();
+    assert_eq!(package.name.to_string(), "hello_world");
+    assert!(package.is_binary());
+    assert_eq!(package.entry_path, entry_path.canonicalize().unwrap());
+    assert_eq!(package.root_dir, temp_dir.path().canonicalize().unwrap());
+
+    // Other source files next to the entry file are not part of the package.
+    let sibling_path = temp_dir.path().join("other.nr");
+    std::fs::write(&sibling_path, "fn foo() {}").unwrap();
+    let sibling_path = sibling_path.canonicalize().unwrap();
+    assert!(package.is_source_file_included(&package.entry_path));
+    assert!(!package.is_source_file_included(&sibling_path));
+
+    assert!(resolve_workspace_from_entry_file(&temp_dir.path().join("missing.nr")).is_err());
+}
+
+#[test]
+fn patches_replace_transitive_dependencies() {
+    // `root` depends on `middle`, which depends on `leaf`. The root manifest patches `leaf` to a local copy.
+    let temp_dir = tempfile::tempdir().unwrap();
+    let write_package = |dir: &str, package_type: &str, extra: &str| {
+        let package_dir = temp_dir.path().join(dir);
+        let entry = if package_type == "bin" { "main.nr" } else { "lib.nr" };
+        std::fs::create_dir_all(package_dir.join("src")).unwrap();
+        std::fs::write(package_dir.join("src").join(entry), "").unwrap();
+        let manifest = format!("[package]\nname = \"{dir}\"\ntype = \"{package_type}\"\n\n{extra}");
+        std::fs::write(package_dir.join("Nargo.toml"), manifest).unwrap();
+    };
+    write_package("leaf", "lib", "");
+    write_package("patched_leaf", "lib", "");
+    write_package("middle", "lib", "[dependencies]\nleaf = { path = \"../leaf\" }\n");
+    write_package(
+        "root",
+        "bin",
+        "[dependencies]\nmiddle = { path = \"../middle\" }\n\n[patch]\nleaf = { path = \"../patched_leaf\" }\n",
+    );
+
+    let workspace = resolve_workspace_from_toml(
+        &temp_dir.path().join("root").join("Nargo.toml"),
+        PackageSelection::All,
+        None,
+    )
+    .unwrap();
+    let root = workspace.into_iter().next().unwrap();
+    let Some(Dependency::Local { package: middle }) = root.dependencies.get(&"middle".parse().unwrap())
+    else {
+        panic!("expected `root` to depend on `middle`");
+    };
+    let Some(Dependency::Local { package: leaf }) = middle.dependencies.get(&"leaf".parse().unwrap())
+    else {
+        panic!("expected `middle` to depend on `leaf`");
+    };
+    assert_eq!(leaf.name.to_string(), "patched_leaf");
+    assert_eq!(leaf.root_dir, temp_dir.path().join("patched_leaf").normalize());
+}
+
+#[test]
+fn warns_about_distinct_dependencies_with_the_same_crate_name() {
+    let library = |name: &str, root_dir: &str| Package {
+        version: None,
+        compiler_required_version: None,
+        root_dir: PathBuf::from(root_dir),
+        package_type: PackageType::Library,
+        entry_path: PathBuf::new(),
+        name: name.parse().unwrap(),
+        dependencies: BTreeMap::new(),
+        include: Vec::new(),
+        exclude: Vec::new(),
+    };
+    let dependencies = |packages: Vec<(&str, Package)>| -> BTreeMap<CrateName, Dependency> {
+        packages
+            .into_iter()
+            .map(|(import_name, package)| {
+                (import_name.parse().unwrap(), Dependency::Local { package })
+            })
+            .collect()
+    };
+    let toml = Path::new("Nargo.toml");
+
+    // The same package imported under two names is unambiguous.
+    let shared = dependencies(vec![("a", library("lib", "lib")), ("b", library("lib", "lib"))]);
+    assert!(duplicate_crate_name_warnings(toml, &shared).is_empty());
+
+    let conflicting =
+        dependencies(vec![("a", library("lib", "lib-v1")), ("b", library("lib", "lib-v2"))]);
+    let warnings = duplicate_crate_name_warnings(toml, &conflicting);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("Dependencies `a`, `b` in Nargo.toml"));
+    assert!(warnings[0].contains("named `lib`"));
+}
+
+#[test]
+fn rejects_path_dependencies_without_a_manifest() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let package_dir = temp_dir.path().join("package");
+    std::fs::create_dir_all(package_dir.join("src")).unwrap();
+    std::fs::create_dir_all(temp_dir.path().join("empty")).unwrap();
+    std::fs::write(package_dir.join("src").join("lib.nr"), "").unwrap();
+
+    for dependency_dir in ["missing", "empty"] {
+        let manifest = format!(
+            "[package]\nname = \"package\"\ntype = \"lib\"\n\n[dependencies]\ndep = {{ path = \"../{dependency_dir}\" }}\n"
+        );
+        std::fs::write(package_dir.join("Nargo.toml"), manifest).unwrap();
+
+        let nargo_toml = read_toml(&package_dir.join("Nargo.toml")).unwrap();
+        let result =
+            toml_to_workspace(nargo_toml, PackageSelection::All, &mut CachedPackages::default());
+        let Err(ManifestError::DependencyResolutionError(
+            DependencyResolutionError::MissingPathDependency { dependency, path, is_dir },
+        )) = result
+        else {
+            panic!("expected the path dependency on `{dependency_dir}` to be missing");
+        };
+        assert_eq!(dependency.to_string(), "dep");
+        assert!(path.ends_with(dependency_dir));
+        assert_eq!(is_dir, dependency_dir == "empty");
+    }
+}
+
+#[test]
+fn parse_profile_allow_warnings() {
+    let package_src = r#"
+        [package]
+        name = "test"
+        type = "bin"
+
+        [profile]
+        allow-warnings = false
+    "#;
+    let Ok(Config::Package { package_config }) = Config::try_from(package_src) else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.profile.allow_warnings, Some(false));
+
+    let workspace_src = r#"
+        [workspace]
+        members = ["a"]
+
+        [profile]
+        allow-warnings = true
+    "#;
+    let Ok(Config::Workspace { profile, .. }) = Config::try_from(workspace_src) else {
+        panic!("expected a workspace config");
+    };
+    assert_eq!(profile.allow_warnings, Some(true));
+}
+
+#[test]
+fn merges_included_dependencies() {
+    let root = tempfile::tempdir().unwrap();
+    std::fs::write(
+        root.path().join("common-deps.toml"),
+        r#"
+        [dependencies]
+        shared = { path = "libs/shared" }
+        overridden = { git = "https://github.com/noir-lang/included", tag = "v1.0.0" }
+        "#,
+    )
+    .unwrap();
+
+    let package_dir = root.path().join("package");
+    std::fs::create_dir(&package_dir).unwrap();
+    let toml_path = package_dir.join("Nargo.toml");
+    std::fs::write(
+        &toml_path,
+        r#"
+        include = "../common-deps.toml"
+
+        [package]
+        name = "package"
+        type = "bin"
+
+        [dependencies]
+        overridden = { git = "https://github.com/noir-lang/local", tag = "v2.0.0" }
+        "#,
+    )
+    .unwrap();
+
+    let Config::Package { package_config } = read_toml(&toml_path).unwrap().config else {
+        panic!("expected a package config");
+    };
+    let Some(DependencyConfig::Path { path }) =
+        package_config.dependencies.get("shared").map(|dep| &dep.source)
+    else {
+        panic!("expected the included path dependency");
+    };
+    assert_eq!(Path::new(path), root.path().join("libs/shared").normalize());
+    let Some(DependencyConfig::Github { git, .. }) =
+        package_config.dependencies.get("overridden").map(|dep| &dep.source)
+    else {
+        panic!("expected the local git dependency");
+    };
+    assert_eq!(git, "https://github.com/noir-lang/local");
+
+    assert_eq!(overridden_included_dependencies(&toml_path).unwrap(), vec!["overridden"]);
+}
+
+#[test]
+fn parse_profile_proofs_dir() {
+    for key in ["proofs-dir", "proofs_dir"] {
+        let src = format!(
+            r#"
+            [package]
+            name = "test"
+            type = "bin"
+
+            [profile]
+            {key} = "artifacts/proofs"
+        "#
+        );
+        let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+            panic!("expected a package config");
+        };
+        let profile = Profile::from(package_config.profile);
+        assert_eq!(profile.proofs_dir, Some(PathBuf::from("artifacts/proofs")));
+    }
+}
+
+#[test]
+fn rejects_contract_dependencies() {
+    let root = tempfile::tempdir().unwrap();
+    let contract_dir = root.path().join("contract");
+    std::fs::create_dir_all(contract_dir.join("src")).unwrap();
+    std::fs::write(contract_dir.join("src/main.nr"), "").unwrap();
+    std::fs::write(
+        contract_dir.join("Nargo.toml"),
+        r#"
+        [package]
+        name = "my_contract"
+        type = "contract"
+        "#,
+    )
+    .unwrap();
+
+    let package_dir = root.path().join("package");
+    std::fs::create_dir_all(package_dir.join("src")).unwrap();
+    std::fs::write(package_dir.join("src/main.nr"), "").unwrap();
+    std::fs::write(
+        package_dir.join("Nargo.toml"),
+        r#"
+        [package]
+        name = "package"
+        type = "contract"
+
+        [dependencies]
+        my_contract = { path = "../contract" }
+        "#,
+    )
+    .unwrap();
+
+    let result = resolve_workspace_from_toml(
+        &package_dir.join("Nargo.toml"),
+        PackageSelection::DefaultOrAll,
+        None,
+    );
+    assert!(
+        matches!(result, Err(ManifestError::ContractDependency(name)) if name.to_string() == "my_contract")
+    );
+}
+
+#[test]
+fn parse_registry_dependency() {
+    let src = r#"
+        [package]
+        name = "test"
+        authors = []
+        type = "bin"
+
+        [dependencies]
+        my_lib = { version = "0.1.0" }
+    "#;
+
+    let Config::Package { package_config } = Config::try_from(src).unwrap() else {
+        panic!("expected a package config");
+    };
+    let Some(DependencyConfig::Registry { version }) =
+        package_config.dependencies.get("my_lib").map(|dep| &dep.source)
+    else {
+        panic!("expected a registry dependency");
+    };
+    assert_eq!(version, "0.1.0");
+}
+
+#[test]
+fn read_profile_backend() {
+    let root = tempfile::tempdir().unwrap();
+    let toml_path = root.path().join("Nargo.toml");
+    std::fs::write(
+        &toml_path,
+        r#"
+        [workspace]
+        members = ["missing_member"]
+
+        [profile]
+        backend = "my_backend"
+    "#,
+    )
+    .unwrap();
+
+    // The profile is read without resolving the workspace, so missing members are not an error.
+    let profile = read_profile_from_toml(&toml_path).unwrap();
+    assert_eq!(profile.backend, Some("my_backend".to_string()));
+}
+
+#[test]
+fn rejects_unknown_profile_features() {
+    let root = tempfile::tempdir().unwrap();
+    let toml_path = root.path().join("Nargo.toml");
+    let write_features = |features: &str| {
+        let manifest = format!("[workspace]\nmembers = []\n\n[profile]\nfeatures = {features}\n");
+        std::fs::write(&toml_path, manifest).unwrap();
+    };
+
+    write_features(r#"["minimal-std"]"#);
+    let profile = read_profile_from_toml(&toml_path).unwrap();
+    assert_eq!(profile.features, vec!["minimal-std".to_string()]);
+
+    write_features(r#"["minimal-std", "tiny-std"]"#);
+    assert!(matches!(
+        read_profile_from_toml(&toml_path),
+        Err(ManifestError::UnknownFeature { feature, .. }) if feature == "tiny-std"
+    ));
+}
+
+#[test]
+fn filters_source_files_by_include_and_exclude_globs() {
+    let root = tempfile::tempdir().unwrap();
+    let src_dir = root.path().join("src");
+    std::fs::create_dir_all(src_dir.join("scratch")).unwrap();
+    for file in ["main.nr", "module.nr", "scratch/example.nr"] {
+        std::fs::write(src_dir.join(file), "").unwrap();
+    }
+    let toml_path = root.path().join("Nargo.toml");
+    let write_globs = |globs: &str| {
+        let manifest = format!("[package]\nname = \"package\"\ntype = \"bin\"\n{globs}\n");
+        std::fs::write(&toml_path, manifest).unwrap();
+    };
+
+    write_globs(r#"exclude = ["src/scratch/**"]"#);
+    let workspace = resolve_workspace_from_toml(&toml_path, PackageSelection::All, None).unwrap();
+    assert_eq!(
+        nargo::get_all_files_in_workspace(&workspace),
+        vec![src_dir.join("main.nr"), src_dir.join("module.nr")]
+    );
+
+    write_globs("include = [\"src/*.nr\"]");
+    let workspace = resolve_workspace_from_toml(&toml_path, PackageSelection::All, None).unwrap();
+    assert_eq!(
+        nargo::get_all_files_in_workspace(&workspace),
+        vec![src_dir.join("main.nr"), src_dir.join("module.nr")]
+    );
+
+    write_globs("include = [\"src/**/*.nr\"]\nexclude = [\"src/scratch/*.nr\"]");
+    assert!(matches!(
+        resolve_workspace_from_toml(&toml_path, PackageSelection::All, None),
+        Err(ManifestError::InvalidSourceGlobs {
+            error: nargo::package::SourceGlobError::IncludedAndExcluded(path),
+            ..
+        }) if path == src_dir.join("scratch/example.nr")
+    ));
+
+    write_globs(r#"exclude = ["src/main.nr"]"#);
+    assert!(matches!(
+        resolve_workspace_from_toml(&toml_path, PackageSelection::All, None),
+        Err(ManifestError::InvalidSourceGlobs {
+            error: nargo::package::SourceGlobError::EntryNotIncluded(_),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn validate_reports_every_manifest_issue() {
+    let src = r#"
+        [package]
+        name = "foo"
+        type = "lib"
+
+        [dependencies]
+        foo = { path = "../foo" }
+        bar = { path = "" }
+        baz = { git = "", tag = "", directory = "" }
+        qux = { path = "../qux", alias = "foo" }
+    "#;
+
+    let config = Config::try_from(src).unwrap();
+
+    assert_eq!(
+        config.validate(),
+        vec![
+            ManifestIssue::EmptyPath { dependency: "bar".into(), key: "path" },
+            ManifestIssue::EmptyGitRepository { dependency: "baz".into() },
+            ManifestIssue::EmptyGitTag { dependency: "baz".into() },
+            ManifestIssue::EmptyPath { dependency: "baz".into(), key: "directory" },
+            ManifestIssue::DependencyNamedAfterPackage { dependency: "foo".into() },
+            ManifestIssue::DependencyNamedAfterPackage { dependency: "qux".into() },
+        ]
+    );
+}
+
+#[test]
+fn validate_accepts_valid_manifest() {
+    let src = r#"
+        [package]
+        name = "foo"
+        type = "lib"
+
+        [dependencies]
+        bar = { path = "../bar" }
+        baz = { git = "https://github.com/noir-lang/baz", tag = "v1.0.0" }
+        foo = { path = "../foo", alias = "other_foo" }
+    "#;
+
+    assert_eq!(Config::try_from(src).unwrap().validate(), Vec::new());
+}
@@ -0,0 +1,110 @@
+use nargo::{
+    package::{Dependency, Package},
+    workspace::Workspace,
+};
+use semver::VersionReq;
+
+use crate::{errors::PublishError, read_toml, Config, ManifestError, PackageMetadata};
+
+// Check that each selected package in the workspace is ready to be published as a library
+pub(crate) fn check_workspace_publishable(workspace: &Workspace) -> Result<(), ManifestError> {
+    for package in workspace {
+        let metadata = match read_toml(&package.root_dir.join("Nargo.toml"))?.config {
+            Config::Package { package_config } => package_config.package,
+            Config::Workspace { .. } => {
+                return Err(ManifestError::UnexpectedWorkspace(package.root_dir.join("Nargo.toml")))
+            }
+        };
+
+        let errors = publish_errors(package, &metadata);
+        if !errors.is_empty() {
+            return Err(ManifestError::NotPublishable { package: package.name.clone(), errors });
+        }
+    }
+
+    Ok(())
+}
+
+// Collect every condition which prevents the package from being published.
+// The package name has already been validated when resolving the package.
+fn publish_errors(package: &Package, metadata: &PackageMetadata) -> Vec<PublishError> {
+    let mut errors = Vec::new();
+
+    if package.is_binary() {
+        errors.push(PublishError::BinaryPackage);
+    }
+
+    match &package.compiler_required_version {
+        Some(version) => {
+            if let Err(err) = VersionReq::parse(version) {
+                errors.push(PublishError::InvalidCompilerVersion { error: err.to_string() });
+            }
+        }
+        None => errors.push(PublishError::MissingCompilerVersion),
+    }
+
+    if metadata.license.is_none() {
+        errors.push(PublishError::MissingLicense);
+    }
+
+    for (name, dependency) in &package.dependencies {
+        if let Dependency::Local { .. } = dependency {
+            errors.push(PublishError::LocalDependency { dependency: name.clone() });
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, str::FromStr};
+
+    use nargo::package::PackageType;
+    use noirc_frontend::graph::CrateName;
+
+    use super::*;
+
+    fn package(package_type: PackageType, compiler_version: Option<&str>) -> Package {
+        Package {
+            version: None,
+            compiler_required_version: compiler_version.map(String::from),
+            root_dir: PathBuf::new(),
+            package_type,
+            entry_path: PathBuf::new(),
+            name: CrateName::from_str("pkg").unwrap(),
+            dependencies: Default::default(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn publishable_library_has_no_errors() {
+        let package = package(PackageType::Library, Some(">=0.1.0"));
+        let metadata = PackageMetadata { license: Some("MIT".into()), ..Default::default() };
+
+        assert_eq!(publish_errors(&package, &metadata), Vec::new());
+    }
+
+    #[test]
+    fn reports_every_failing_condition() {
+        let dependency = package(PackageType::Library, Some(">=0.1.0"));
+        let mut package = package(PackageType::Binary, Some("not a version"));
+        package
+            .dependencies
+            .insert(CrateName::from_str("dep").unwrap(), Dependency::Local { package: dependency });
+
+        let errors = publish_errors(&package, &PackageMetadata::default());
+
+        assert!(matches!(
+            errors.as_slice(),
+            [
+                PublishError::BinaryPackage,
+                PublishError::InvalidCompilerVersion { .. },
+                PublishError::MissingLicense,
+                PublishError::LocalDependency { .. },
+            ]
+        ));
+    }
+}
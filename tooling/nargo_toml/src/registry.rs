@@ -0,0 +1,103 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{decode_manifest, ManifestError};
+
+/// Environment variable holding the path to the registry index directory.
+pub(crate) const REGISTRY_ENV: &str = "NARGO_REGISTRY";
+
+/// The location of a single version of a package within the registry.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct RegistryEntry {
+    pub(crate) git: String,
+    pub(crate) tag: String,
+    pub(crate) directory: Option<String>,
+}
+
+/// Returns the registry index directory configured through [REGISTRY_ENV].
+pub(crate) fn registry_index_dir(dependency: &str) -> Result<PathBuf, ManifestError> {
+    std::env::var_os(REGISTRY_ENV)
+        .map(PathBuf::from)
+        .ok_or_else(|| ManifestError::MissingRegistry { dependency: dependency.to_string() })
+}
+
+/// Looks up the location of version `version` of the package `name` in the registry index at `index_dir`.
+///
+/// The index holds a `<name>.toml` file for each package, containing a table for each published version:
+///
+/// ```toml
+/// ["0.1.0"]
+/// git = "https://github.com/noir-lang/example"
+/// tag = "v0.1.0"
+/// ```
+///
+/// Versions are matched exactly against the keys of the index file.
+pub(crate) fn lookup_registry_entry(
+    index_dir: &Path,
+    name: &str,
+    version: &str,
+) -> Result<RegistryEntry, ManifestError> {
+    let index_path = index_dir.join(name).with_extension("toml");
+    let missing_entry = || ManifestError::MissingRegistryEntry {
+        dependency: name.to_string(),
+        version: version.to_string(),
+        index: index_dir.to_path_buf(),
+    };
+    if !index_path.is_file() {
+        return Err(missing_entry());
+    }
+
+    let index_bytes =
+        std::fs::read(&index_path).map_err(|_| ManifestError::ReadFailed(index_path.clone()))?;
+    let index_string =
+        decode_manifest(index_bytes).ok_or(ManifestError::InvalidEncoding(index_path))?;
+    let mut versions: BTreeMap<String, RegistryEntry> = toml::from_str(&index_string)?;
+    versions.remove(version).ok_or_else(missing_entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_entries_in_registry_index() {
+        let index_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            index_dir.path().join("my_lib.toml"),
+            r#"
+            ["0.1.0"]
+            git = "https://github.com/noir-lang/my_lib"
+            tag = "v0.1.0"
+
+            ["0.2.0"]
+            git = "https://github.com/noir-lang/monorepo"
+            tag = "v0.2.0"
+            directory = "crates/my_lib"
+            "#,
+        )
+        .unwrap();
+
+        let entry = lookup_registry_entry(index_dir.path(), "my_lib", "0.2.0").unwrap();
+        assert_eq!(
+            entry,
+            RegistryEntry {
+                git: "https://github.com/noir-lang/monorepo".to_string(),
+                tag: "v0.2.0".to_string(),
+                directory: Some("crates/my_lib".to_string()),
+            }
+        );
+
+        assert!(matches!(
+            lookup_registry_entry(index_dir.path(), "my_lib", "0.3.0"),
+            Err(ManifestError::MissingRegistryEntry { .. })
+        ));
+        assert!(matches!(
+            lookup_registry_entry(index_dir.path(), "other_lib", "0.1.0"),
+            Err(ManifestError::MissingRegistryEntry { .. })
+        ));
+    }
+}
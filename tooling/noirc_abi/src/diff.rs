@@ -0,0 +1,312 @@
+use std::fmt;
+
+use crate::{Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility, Sign};
+
+/// A difference between two versions of a program's ABI.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AbiChange {
+    ParameterAdded(AbiParameter),
+    ParameterRemoved(AbiParameter),
+    TypeChanged {
+        name: String,
+        old: AbiType,
+        new: AbiType,
+        visibility: AbiVisibility,
+    },
+    VisibilityChanged {
+        name: String,
+        old: AbiVisibility,
+        new: AbiVisibility,
+    },
+    /// The public parameters common to both ABIs are passed in a different order.
+    PublicParametersReordered {
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+    ReturnTypeChanged {
+        old: Option<AbiReturnType>,
+        new: Option<AbiReturnType>,
+    },
+}
+
+impl AbiChange {
+    /// Returns whether the change affects the public inputs of the program, and so breaks existing verifiers.
+    ///
+    /// Changes which only touch private parameters alter what the prover must provide but are invisible to verifiers.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            AbiChange::ParameterAdded(parameter) | AbiChange::ParameterRemoved(parameter) => {
+                parameter.is_public()
+            }
+            AbiChange::TypeChanged { visibility, .. } => *visibility == AbiVisibility::Public,
+            AbiChange::VisibilityChanged { .. } | AbiChange::PublicParametersReordered { .. } => {
+                true
+            }
+            AbiChange::ReturnTypeChanged { old, new } => [old, new]
+                .into_iter()
+                .flatten()
+                .any(|return_type| return_type.visibility == AbiVisibility::Public),
+        }
+    }
+}
+
+impl fmt::Display for AbiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiChange::ParameterAdded(parameter) => write!(
+                f,
+                "added {} parameter `{}: {}`",
+                visibility_name(parameter.visibility),
+                parameter.name,
+                type_name(&parameter.typ)
+            ),
+            AbiChange::ParameterRemoved(parameter) => write!(
+                f,
+                "removed {} parameter `{}: {}`",
+                visibility_name(parameter.visibility),
+                parameter.name,
+                type_name(&parameter.typ)
+            ),
+            AbiChange::TypeChanged { name, old, new, .. } => write!(
+                f,
+                "changed the type of parameter `{name}` from `{}` to `{}`",
+                type_name(old),
+                type_name(new)
+            ),
+            AbiChange::VisibilityChanged { name, old, new } => write!(
+                f,
+                "changed parameter `{name}` from {} to {}",
+                visibility_name(*old),
+                visibility_name(*new)
+            ),
+            AbiChange::PublicParametersReordered { old, new } => write!(
+                f,
+                "reordered public parameters from ({}) to ({})",
+                old.join(", "),
+                new.join(", ")
+            ),
+            AbiChange::ReturnTypeChanged { old, new } => write!(
+                f,
+                "changed the return type from {} to {}",
+                return_type_name(old.as_ref()),
+                return_type_name(new.as_ref())
+            ),
+        }
+    }
+}
+
+/// Compares the ABI of a program against that of a previous version of the program, returning every change made.
+pub fn diff_abis(old: &Abi, new: &Abi) -> Vec<AbiChange> {
+    let find_parameter = |abi: &'_ Abi, name: &str| {
+        abi.parameters.iter().find(|parameter| parameter.name == name).cloned()
+    };
+
+    let mut changes = Vec::new();
+    for old_parameter in &old.parameters {
+        let Some(new_parameter) = find_parameter(new, &old_parameter.name) else {
+            changes.push(AbiChange::ParameterRemoved(old_parameter.clone()));
+            continue;
+        };
+
+        if old_parameter.typ != new_parameter.typ {
+            // A type change to a parameter which is public in either version alters the public inputs.
+            let visibility = if old_parameter.is_public() || new_parameter.is_public() {
+                AbiVisibility::Public
+            } else {
+                new_parameter.visibility
+            };
+            changes.push(AbiChange::TypeChanged {
+                name: old_parameter.name.clone(),
+                old: old_parameter.typ.clone(),
+                new: new_parameter.typ.clone(),
+                visibility,
+            });
+        }
+        if old_parameter.visibility != new_parameter.visibility {
+            changes.push(AbiChange::VisibilityChanged {
+                name: old_parameter.name.clone(),
+                old: old_parameter.visibility,
+                new: new_parameter.visibility,
+            });
+        }
+    }
+    for new_parameter in &new.parameters {
+        if find_parameter(old, &new_parameter.name).is_none() {
+            changes.push(AbiChange::ParameterAdded(new_parameter.clone()));
+        }
+    }
+
+    // Only the relative order of the public parameters which exist in both versions is compared,
+    // as additions, removals and visibility changes have already been reported.
+    let common_public_parameters = |abi: &Abi, other: &Abi| -> Vec<String> {
+        abi.parameters
+            .iter()
+            .filter(|parameter| {
+                parameter.is_public()
+                    && find_parameter(other, &parameter.name)
+                        .is_some_and(|other_parameter| other_parameter.is_public())
+            })
+            .map(|parameter| parameter.name.clone())
+            .collect()
+    };
+    let old_order = common_public_parameters(old, new);
+    let new_order = common_public_parameters(new, old);
+    if old_order != new_order {
+        changes.push(AbiChange::PublicParametersReordered { old: old_order, new: new_order });
+    }
+
+    if old.return_type != new.return_type {
+        changes.push(AbiChange::ReturnTypeChanged {
+            old: old.return_type.clone(),
+            new: new.return_type.clone(),
+        });
+    }
+
+    changes
+}
+
+fn visibility_name(visibility: AbiVisibility) -> &'static str {
+    match visibility {
+        AbiVisibility::Public => "public",
+        AbiVisibility::Private => "private",
+        AbiVisibility::DataBus => "databus",
+    }
+}
+
+fn return_type_name(return_type: Option<&AbiReturnType>) -> String {
+    match return_type {
+        Some(return_type) => {
+            format!(
+                "`{}` ({})",
+                type_name(&return_type.abi_type),
+                visibility_name(return_type.visibility)
+            )
+        }
+        None => "nothing".to_string(),
+    }
+}
+
+/// Formats the ABI type using Noir syntax, e.g. `[u8; 32]`.
+fn type_name(typ: &AbiType) -> String {
+    match typ {
+        AbiType::Field => "Field".to_string(),
+        AbiType::Boolean => "bool".to_string(),
+        AbiType::Integer { sign: Sign::Unsigned, width } => format!("u{width}"),
+        AbiType::Integer { sign: Sign::Signed, width } => format!("i{width}"),
+        AbiType::Array { length, typ } => format!("[{}; {length}]", type_name(typ)),
+        AbiType::String { length } => format!("str<{length}>"),
+        AbiType::Struct { path, .. } => path.clone(),
+        AbiType::Tuple { fields } => {
+            let fields: Vec<_> = fields.iter().map(type_name).collect();
+            format!("({})", fields.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility, Sign};
+
+    use super::{diff_abis, AbiChange};
+
+    fn parameter(name: &str, typ: AbiType, visibility: AbiVisibility) -> AbiParameter {
+        AbiParameter { name: name.to_string(), typ, visibility }
+    }
+
+    fn abi(parameters: Vec<AbiParameter>, return_type: Option<AbiType>) -> Abi {
+        Abi {
+            parameters,
+            param_witnesses: BTreeMap::new(),
+            return_type: return_type
+                .map(|abi_type| AbiReturnType { abi_type, visibility: AbiVisibility::Public }),
+            return_witnesses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn unchanged_abi_has_no_changes() {
+        let abi = abi(
+            vec![
+                parameter("x", AbiType::Field, AbiVisibility::Private),
+                parameter("y", AbiType::Boolean, AbiVisibility::Public),
+            ],
+            Some(AbiType::Field),
+        );
+        assert_eq!(diff_abis(&abi, &abi.clone()), Vec::new());
+    }
+
+    #[test]
+    fn private_changes_are_not_breaking() {
+        let u8_type = AbiType::Integer { sign: Sign::Unsigned, width: 8 };
+        let old = abi(
+            vec![
+                parameter("x", AbiType::Field, AbiVisibility::Private),
+                parameter("y", AbiType::Field, AbiVisibility::Public),
+            ],
+            None,
+        );
+        let new = abi(
+            vec![
+                parameter("x", u8_type.clone(), AbiVisibility::Private),
+                parameter("y", AbiType::Field, AbiVisibility::Public),
+                parameter("z", AbiType::Boolean, AbiVisibility::Private),
+            ],
+            None,
+        );
+
+        let changes = diff_abis(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                AbiChange::TypeChanged {
+                    name: "x".to_string(),
+                    old: AbiType::Field,
+                    new: u8_type,
+                    visibility: AbiVisibility::Private
+                },
+                AbiChange::ParameterAdded(parameter("z", AbiType::Boolean, AbiVisibility::Private)),
+            ]
+        );
+        assert!(changes.iter().all(|change| !change.is_breaking()));
+        assert_eq!(
+            changes[0].to_string(),
+            "changed the type of parameter `x` from `Field` to `u8`"
+        );
+    }
+
+    #[test]
+    fn public_changes_are_breaking() {
+        let old = abi(
+            vec![
+                parameter("a", AbiType::Field, AbiVisibility::Public),
+                parameter("b", AbiType::Field, AbiVisibility::Public),
+                parameter("c", AbiType::Field, AbiVisibility::Private),
+                parameter("d", AbiType::Field, AbiVisibility::Public),
+            ],
+            Some(AbiType::Field),
+        );
+        let new = abi(
+            vec![
+                parameter("b", AbiType::Field, AbiVisibility::Public),
+                parameter("a", AbiType::Field, AbiVisibility::Public),
+                parameter("c", AbiType::Field, AbiVisibility::Public),
+            ],
+            Some(AbiType::String { length: 3 }),
+        );
+
+        let changes = diff_abis(&old, &new);
+        let descriptions: Vec<_> = changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "changed parameter `c` from private to public",
+                "removed public parameter `d: Field`",
+                "reordered public parameters from (a, b) to (b, a)",
+                "changed the return type from `Field` (public) to `str<3>` (public)",
+            ]
+        );
+        assert!(changes.iter().all(AbiChange::is_breaking));
+    }
+}
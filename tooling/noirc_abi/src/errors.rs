@@ -1,5 +1,5 @@
 use crate::{input_parser::InputValue, AbiParameter, AbiType};
-use acvm::acir::native_types::Witness;
+use acvm::{acir::native_types::Witness, FieldElement};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -14,6 +14,20 @@ pub enum InputParserError {
     AbiTypeMismatch(AbiType),
     #[error("Expected argument `{0}`, but none was found")]
     MissingArgument(String),
+    #[error("Expected `{path}` to be a table containing `{field}`")]
+    ExpectedTable { path: String, field: String },
+    #[error(
+        "Expected a string of length {expected} for `{arg_name}`, but found one of length {found}"
+    )]
+    StringLengthMismatch { arg_name: String, expected: u64, found: usize },
+    #[error("The value {value} provided for `{name}` is too large. Values must be less than the field modulus {}", FieldElement::modulus())]
+    ValueTooLarge { name: String, value: String },
+    #[error("Expected the bytes of `{arg_name}` to each be less than 256, but found {value}")]
+    InvalidByte { arg_name: String, value: u64 },
+    #[error(
+        "Cannot use a boolean literal for `{arg_name}` as it is of type {typ:?} rather than a bool"
+    )]
+    UnexpectedBoolean { arg_name: String, typ: AbiType },
 }
 
 impl From<toml::ser::Error> for InputParserError {
@@ -40,6 +54,8 @@ pub enum AbiError {
     UnexpectedParams(Vec<String>),
     #[error("The parameter {} is expected to be a {:?} but found incompatible value {value:?}", .param.name, .param.typ)]
     TypeMismatch { param: AbiParameter, value: InputValue },
+    #[error("The parameter `{name}` is expected to be an array of shape {shape} but `{name}{path}` has {found} elements rather than {expected}")]
+    ArrayShapeMismatch { name: String, shape: String, path: String, expected: u64, found: usize },
     #[error("ABI expects the parameter `{0}`, but this was not found")]
     MissingParam(String),
     #[error(
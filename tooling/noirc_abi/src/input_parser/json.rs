@@ -1,4 +1,4 @@
-use super::{parse_str_to_field, InputValue};
+use super::{is_byte_array_element, parse_str_to_byte_array, parse_str_to_field, InputValue};
 use crate::{errors::InputParserError, Abi, AbiType, MAIN_RETURN_NAME};
 use acvm::FieldElement;
 use iter_extended::{try_btree_map, try_vecmap};
@@ -89,7 +89,9 @@ impl JsonTypes {
             (InputValue::Field(f), AbiType::Field | AbiType::Integer { .. }) => {
                 JsonTypes::String(Self::format_field_string(*f))
             }
-            (InputValue::Field(f), AbiType::Boolean) => JsonTypes::Bool(f.is_one()),
+            (InputValue::Field(f), AbiType::Boolean) if f.is_zero() || f.is_one() => {
+                JsonTypes::Bool(f.is_one())
+            }
 
             (InputValue::Vec(vector), AbiType::Array { typ, .. }) => {
                 let array =
@@ -146,7 +148,7 @@ impl InputValue {
             (
                 JsonTypes::String(string),
                 AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean,
-            ) => InputValue::Field(parse_str_to_field(&string)?),
+            ) => InputValue::Field(parse_str_to_field(&string, arg_name)?),
 
             (
                 JsonTypes::Integer(integer),
@@ -158,6 +160,18 @@ impl InputValue {
             }
 
             (JsonTypes::Bool(boolean), AbiType::Boolean) => InputValue::Field(boolean.into()),
+            (JsonTypes::Bool(_), _) => {
+                return Err(InputParserError::UnexpectedBoolean {
+                    arg_name: arg_name.to_owned(),
+                    typ: param_type.clone(),
+                })
+            }
+
+            (JsonTypes::String(string), AbiType::Array { length, typ })
+                if is_byte_array_element(typ) =>
+            {
+                parse_str_to_byte_array(&string, *length, arg_name)?
+            }
 
             (JsonTypes::Array(array), AbiType::Array { typ, .. }) => {
                 let array_elements =
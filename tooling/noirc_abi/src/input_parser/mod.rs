@@ -3,10 +3,11 @@ use num_traits::{Num, Zero};
 use std::collections::BTreeMap;
 
 use acvm::FieldElement;
+use iter_extended::try_vecmap;
 use serde::Serialize;
 
 use crate::errors::InputParserError;
-use crate::{Abi, AbiType};
+use crate::{Abi, AbiType, Sign};
 
 pub mod json;
 mod toml;
@@ -79,6 +80,76 @@ impl InputValue {
             _ => false,
         }
     }
+
+    /// Returns a wrapper which displays the value as the ABI type `abi_type`, e.g. `(5, Point { x: 1, y: -2 })`.
+    pub fn display<'a>(&'a self, abi_type: &'a AbiType) -> InputValueDisplay<'a> {
+        InputValueDisplay { value: self, abi_type }
+    }
+}
+
+/// Displays an [InputValue] using Noir syntax, following the structure of its ABI type.
+///
+/// Fields are shown in hex, integers in decimal and booleans as `true` or `false`.
+/// If the value does not match the ABI type then it is displayed in its debug representation instead.
+pub struct InputValueDisplay<'a> {
+    value: &'a InputValue,
+    abi_type: &'a AbiType,
+}
+
+impl std::fmt::Display for InputValueDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.value, self.abi_type) {
+            (InputValue::Field(field), AbiType::Field) => {
+                write!(f, "0x{}", BigUint::from_bytes_be(&field.to_be_bytes()).to_str_radix(16))
+            }
+            (InputValue::Field(field), AbiType::Integer { sign, width }) => {
+                let value = BigInt::from(BigUint::from_bytes_be(&field.to_be_bytes()));
+                let is_negative =
+                    *sign == Sign::Signed && *width > 0 && value.bit(u64::from(*width) - 1);
+                if is_negative {
+                    write!(f, "{}", value - (BigInt::from(1) << *width))
+                } else {
+                    write!(f, "{value}")
+                }
+            }
+            (InputValue::Field(field), AbiType::Boolean) => write!(f, "{}", field.is_one()),
+            (InputValue::String(string), AbiType::String { .. }) => write!(f, "{string:?}"),
+            (InputValue::Vec(elements), AbiType::Array { typ, .. }) => {
+                write!(f, "[")?;
+                for (index, element) in elements.iter().enumerate() {
+                    let separator = if index == 0 { "" } else { ", " };
+                    write!(f, "{separator}{}", element.display(typ))?;
+                }
+                write!(f, "]")
+            }
+            (InputValue::Vec(elements), AbiType::Tuple { fields })
+                if elements.len() == fields.len() =>
+            {
+                write!(f, "(")?;
+                for (index, (element, typ)) in elements.iter().zip(fields).enumerate() {
+                    let separator = if index == 0 { "" } else { ", " };
+                    write!(f, "{separator}{}", element.display(typ))?;
+                }
+                // Single element tuples are distinguished from parenthesized values by a trailing comma.
+                if elements.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            (InputValue::Struct(map), AbiType::Struct { path, fields })
+                if fields.iter().all(|(name, _)| map.contains_key(name)) =>
+            {
+                let name = path.rsplit("::").next().unwrap_or(path);
+                write!(f, "{name} {{")?;
+                for (index, (field_name, typ)) in fields.iter().enumerate() {
+                    let separator = if index == 0 { " " } else { ", " };
+                    write!(f, "{separator}{field_name}: {}", map[field_name].display(typ))?;
+                }
+                write!(f, " }}")
+            }
+            _ => write!(f, "{:?}", self.value),
+        }
+    }
 }
 
 /// The different formats that are supported when parsing
@@ -126,12 +197,12 @@ impl Format {
 mod serialization_tests {
     use std::collections::BTreeMap;
 
-    use acvm::FieldElement;
+    use acvm::{acir::native_types::Witness, FieldElement};
     use strum::IntoEnumIterator;
 
     use crate::{
-        input_parser::InputValue, Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility, Sign,
-        MAIN_RETURN_NAME,
+        errors::InputParserError, input_parser::InputValue, Abi, AbiParameter, AbiReturnType,
+        AbiType, AbiVisibility, Sign, MAIN_RETURN_NAME,
     };
 
     use super::Format;
@@ -195,9 +266,122 @@ mod serialization_tests {
             assert_eq!(input_map, reconstructed_input_map);
         }
     }
+
+    fn byte_array_abi(length: u64) -> Abi {
+        Abi {
+            parameters: vec![AbiParameter {
+                name: "message".into(),
+                typ: AbiType::Array {
+                    length,
+                    typ: Box::new(AbiType::Integer { sign: Sign::Unsigned, width: 8 }),
+                },
+                visibility: AbiVisibility::Private,
+            }],
+            return_type: None,
+            param_witnesses: BTreeMap::from([(
+                "message".into(),
+                vec![Witness(1)..Witness(length as u32 + 1)],
+            )]),
+            return_witnesses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_strings_into_byte_arrays() {
+        let abi = byte_array_abi(5);
+        let inputs =
+            [(Format::Toml, r#"message = "hello""#), (Format::Json, r#"{"message": "hello"}"#)];
+
+        for (format, input) in inputs {
+            let input_map = format.parse(input, &abi).unwrap();
+            let expected_bytes: Vec<InputValue> =
+                b"hello".iter().map(|byte| InputValue::Field((*byte as u128).into())).collect();
+            assert_eq!(input_map["message"], InputValue::Vec(expected_bytes));
+
+            let witness_map = abi.encode(&input_map, None).unwrap();
+            let witness_values: Vec<FieldElement> =
+                witness_map.into_iter().map(|(_, value)| value).collect();
+            let expected_values: Vec<FieldElement> =
+                b"hello".iter().map(|byte| FieldElement::from(*byte as u128)).collect();
+            assert_eq!(witness_values, expected_values);
+
+            let (decoded_inputs, _) = abi.decode(&abi.encode(&input_map, None).unwrap()).unwrap();
+            assert_eq!(decoded_inputs, input_map);
+        }
+    }
+
+    #[test]
+    fn boolean_literals_round_trip() {
+        let abi = Abi {
+            parameters: vec![AbiParameter {
+                name: "flag".into(),
+                typ: AbiType::Boolean,
+                visibility: AbiVisibility::Private,
+            }],
+            return_type: None,
+            param_witnesses: BTreeMap::from([("flag".into(), vec![Witness(1)..Witness(2)])]),
+            return_witnesses: Vec::new(),
+        };
+        let inputs = [
+            (Format::Toml, "flag = true", true),
+            (Format::Toml, "flag = false", false),
+            (Format::Json, r#"{"flag": true}"#, true),
+            (Format::Json, r#"{"flag": false}"#, false),
+        ];
+
+        for (format, input, flag) in inputs {
+            let input_map = format.parse(input, &abi).unwrap();
+            assert_eq!(input_map["flag"], InputValue::Field(flag.into()));
+
+            let witness_map = abi.encode(&input_map, None).unwrap();
+            assert_eq!(witness_map[&Witness(1)], FieldElement::from(flag));
+
+            let (decoded_inputs, _) = abi.decode(&witness_map).unwrap();
+            assert_eq!(
+                decoded_inputs["flag"].display(&AbiType::Boolean).to_string(),
+                flag.to_string()
+            );
+            let serialized_inputs = format.serialize(&decoded_inputs, &abi).unwrap();
+            assert_eq!(format.parse(&serialized_inputs, &abi).unwrap(), input_map);
+            assert!(serialized_inputs.contains(&flag.to_string()));
+        }
+    }
+
+    #[test]
+    fn rejects_boolean_literals_for_other_types() {
+        let abi = Abi {
+            parameters: vec![AbiParameter {
+                name: "x".into(),
+                typ: AbiType::Field,
+                visibility: AbiVisibility::Private,
+            }],
+            return_type: None,
+            param_witnesses: BTreeMap::new(),
+            return_witnesses: Vec::new(),
+        };
+
+        for (format, input) in [(Format::Toml, "x = true"), (Format::Json, r#"{"x": true}"#)] {
+            let error = format.parse(input, &abi).unwrap_err();
+            assert!(matches!(
+                error,
+                InputParserError::UnexpectedBoolean { arg_name, typ: AbiType::Field } if arg_name == "x"
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_strings_of_wrong_length_for_byte_arrays() {
+        let abi = byte_array_abi(4);
+
+        let error = Format::Toml.parse(r#"message = "hello""#, &abi).unwrap_err();
+        assert!(matches!(
+            error,
+            InputParserError::StringLengthMismatch { expected: 4, found: 5, .. }
+        ));
+    }
 }
 
-fn parse_str_to_field(value: &str) -> Result<FieldElement, InputParserError> {
+fn parse_str_to_field(value: &str, arg_name: &str) -> Result<FieldElement, InputParserError> {
     let big_num = if let Some(hex) = value.strip_prefix("0x") {
         BigUint::from_str_radix(hex, 16)
     } else {
@@ -207,15 +391,69 @@ fn parse_str_to_field(value: &str) -> Result<FieldElement, InputParserError> {
         if bigint < FieldElement::modulus() {
             Ok(field_from_big_uint(bigint))
         } else {
-            Err(InputParserError::ParseStr(format!(
-                "Input exceeds field modulus. Values must fall within [0, {})",
-                FieldElement::modulus(),
-            )))
+            Err(InputParserError::ValueTooLarge {
+                name: arg_name.to_owned(),
+                value: value.to_owned(),
+            })
         }
     })
 }
 
-fn parse_str_to_signed(value: &str, width: u32) -> Result<FieldElement, InputParserError> {
+/// Interprets `bytes` as the little-endian representation of a field element.
+fn parse_le_bytes_to_field(
+    bytes: &[u64],
+    arg_name: &str,
+) -> Result<FieldElement, InputParserError> {
+    let bytes = try_vecmap(bytes, |&byte| {
+        u8::try_from(byte).map_err(|_| InputParserError::InvalidByte {
+            arg_name: arg_name.to_owned(),
+            value: byte,
+        })
+    })?;
+    let bigint = BigUint::from_bytes_le(&bytes);
+    if bigint < FieldElement::modulus() {
+        Ok(field_from_big_uint(bigint))
+    } else {
+        Err(InputParserError::ValueTooLarge {
+            name: arg_name.to_owned(),
+            value: format!("0x{}", bigint.to_str_radix(16)),
+        })
+    }
+}
+
+/// Checks whether a string may be provided as the value of an array of type `typ`.
+///
+/// Each byte of the string is encoded as a separate element so the element type must be able to hold a byte.
+fn is_byte_array_element(typ: &AbiType) -> bool {
+    match typ {
+        AbiType::Field => true,
+        AbiType::Integer { sign: crate::Sign::Unsigned, width } => *width >= 8,
+        _ => false,
+    }
+}
+
+/// Encodes a string as an array with one field element per byte of its UTF-8 representation.
+fn parse_str_to_byte_array(
+    value: &str,
+    length: u64,
+    arg_name: &str,
+) -> Result<InputValue, InputParserError> {
+    if value.len() != length as usize {
+        return Err(InputParserError::StringLengthMismatch {
+            arg_name: arg_name.to_owned(),
+            expected: length,
+            found: value.len(),
+        });
+    }
+    let bytes = value.bytes().map(|byte| InputValue::Field(FieldElement::from(byte as u128)));
+    Ok(InputValue::Vec(bytes.collect()))
+}
+
+fn parse_str_to_signed(
+    value: &str,
+    width: u32,
+    arg_name: &str,
+) -> Result<FieldElement, InputParserError> {
     let big_num = if let Some(hex) = value.strip_prefix("0x") {
         BigInt::from_str_radix(hex, 16)
     } else {
@@ -232,10 +470,10 @@ fn parse_str_to_signed(value: &str, width: u32) -> Result<FieldElement, InputPar
         if bigint.is_zero() || (bigint.sign() == num_bigint::Sign::Plus && bigint < modulus) {
             Ok(field_from_big_int(bigint))
         } else {
-            Err(InputParserError::ParseStr(format!(
-                "Input exceeds field modulus. Values must fall within [0, {})",
-                FieldElement::modulus(),
-            )))
+            Err(InputParserError::ValueTooLarge {
+                name: arg_name.to_owned(),
+                value: value.to_owned(),
+            })
         }
     })
 }
@@ -262,6 +500,7 @@ mod test {
     use num_bigint::BigUint;
 
     use super::parse_str_to_field;
+    use crate::errors::InputParserError;
 
     fn big_uint_from_field(field: FieldElement) -> BigUint {
         BigUint::from_bytes_be(&field.to_be_bytes())
@@ -270,7 +509,7 @@ mod test {
     #[test]
     fn parse_empty_str_fails() {
         // Check that this fails appropriately rather than being treated as 0, etc.
-        assert!(parse_str_to_field("").is_err());
+        assert!(parse_str_to_field("", "foo").is_err());
     }
 
     #[test]
@@ -285,11 +524,11 @@ mod test {
 
         for field in fields {
             let hex_field = format!("0x{}", field.to_hex());
-            let field_from_hex = parse_str_to_field(&hex_field).unwrap();
+            let field_from_hex = parse_str_to_field(&hex_field, "foo").unwrap();
             assert_eq!(field_from_hex, field);
 
             let dec_field = big_uint_from_field(field).to_string();
-            let field_from_dec = parse_str_to_field(&dec_field).unwrap();
+            let field_from_dec = parse_str_to_field(&dec_field, "foo").unwrap();
             assert_eq!(field_from_dec, field);
         }
     }
@@ -297,6 +536,20 @@ mod test {
     #[test]
     fn rejects_noncanonical_fields() {
         let noncanonical_field = FieldElement::modulus().to_string();
-        assert!(parse_str_to_field(&noncanonical_field).is_err());
+        assert!(parse_str_to_field(&noncanonical_field, "foo").is_err());
+    }
+
+    #[test]
+    fn rejects_values_from_the_field_modulus_upwards() {
+        let largest_field = (FieldElement::modulus() - 1u32).to_string();
+        assert_eq!(parse_str_to_field(&largest_field, "foo").unwrap(), -FieldElement::one());
+
+        let modulus = FieldElement::modulus().to_string();
+        let err = parse_str_to_field(&modulus, "foo").unwrap_err();
+        assert!(
+            matches!(&err, InputParserError::ValueTooLarge { name, value } if name == "foo" && value == &modulus),
+            "unexpected error {err:?}"
+        );
+        assert!(err.to_string().ends_with(&format!("field modulus {modulus}")));
     }
 }
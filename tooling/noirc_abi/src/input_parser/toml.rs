@@ -1,10 +1,19 @@
-use super::{parse_str_to_field, parse_str_to_signed, InputValue};
+use super::{
+    is_byte_array_element, parse_le_bytes_to_field, parse_str_to_byte_array, parse_str_to_field,
+    parse_str_to_signed, InputValue,
+};
 use crate::{errors::InputParserError, Abi, AbiType, MAIN_RETURN_NAME};
 use acvm::FieldElement;
 use iter_extended::{try_btree_map, try_vecmap};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+/// The key of a table which provides the value of a `Field` as an array of its little-endian bytes,
+/// e.g. `x = { le_bytes = [1, 0] }`.
+///
+/// The table distinguishes this from an array of fields, which is written as a plain array.
+const LE_BYTES_KEY: &str = "le_bytes";
+
 pub(crate) fn parse_toml(
     input_string: &str,
     abi: &Abi,
@@ -15,9 +24,7 @@ pub(crate) fn parse_toml(
     // Convert arguments to field elements.
     let mut parsed_inputs = try_btree_map(abi.to_btree_map(), |(arg_name, abi_type)| {
         // Check that toml contains a value for each argument in the ABI.
-        let value = data
-            .get(&arg_name)
-            .ok_or_else(|| InputParserError::MissingArgument(arg_name.clone()))?;
+        let value = get_toml_value(&data, &arg_name)?;
 
         InputValue::try_from_toml(value.clone(), &abi_type, &arg_name)
             .map(|input_value| (arg_name, input_value))
@@ -39,6 +46,33 @@ pub(crate) fn parse_toml(
     Ok(parsed_inputs)
 }
 
+/// Returns the value provided for the argument `arg_name`.
+///
+/// Arguments with dotted names (e.g. `config.threshold`) may be provided either under the quoted name
+/// or within nested tables which mirror the name's structure.
+fn get_toml_value<'a>(
+    data: &'a BTreeMap<String, TomlTypes>,
+    arg_name: &str,
+) -> Result<&'a TomlTypes, InputParserError> {
+    if let Some(value) = data.get(arg_name) {
+        return Ok(value);
+    }
+
+    let missing_argument = || InputParserError::MissingArgument(arg_name.to_owned());
+    let mut segments = arg_name.split('.');
+    let mut path = segments.next().expect("split always returns at least one segment").to_owned();
+    let mut value = data.get(&path).ok_or_else(missing_argument)?;
+    for segment in segments {
+        let TomlTypes::Table(table) = value else {
+            return Err(InputParserError::ExpectedTable { path, field: segment.to_owned() });
+        };
+        value = table.get(segment).ok_or_else(missing_argument)?;
+        path = format!("{path}.{segment}");
+    }
+
+    Ok(value)
+}
+
 pub(crate) fn serialize_to_toml(
     input_map: &BTreeMap<String, InputValue>,
     abi: &Abi,
@@ -87,7 +121,9 @@ impl TomlTypes {
                 let f_str = format!("0x{}", f.to_hex());
                 TomlTypes::String(f_str)
             }
-            (InputValue::Field(f), AbiType::Boolean) => TomlTypes::Bool(f.is_one()),
+            (InputValue::Field(f), AbiType::Boolean) if f.is_zero() || f.is_one() => {
+                TomlTypes::Bool(f.is_one())
+            }
 
             (InputValue::Vec(vector), AbiType::Array { typ, .. }) => {
                 let array =
@@ -131,9 +167,9 @@ impl InputValue {
                 AbiType::Field
                 | AbiType::Integer { sign: crate::Sign::Unsigned, .. }
                 | AbiType::Boolean,
-            ) => InputValue::Field(parse_str_to_field(&string)?),
+            ) => InputValue::Field(parse_str_to_field(&string, arg_name)?),
             (TomlTypes::String(string), AbiType::Integer { sign: crate::Sign::Signed, width }) => {
-                InputValue::Field(parse_str_to_signed(&string, *width)?)
+                InputValue::Field(parse_str_to_signed(&string, *width, arg_name)?)
             }
             (
                 TomlTypes::Integer(integer),
@@ -145,6 +181,29 @@ impl InputValue {
             }
 
             (TomlTypes::Bool(boolean), AbiType::Boolean) => InputValue::Field(boolean.into()),
+            (TomlTypes::Bool(_), _) => {
+                return Err(InputParserError::UnexpectedBoolean {
+                    arg_name: arg_name.to_owned(),
+                    typ: param_type.clone(),
+                })
+            }
+
+            (TomlTypes::Table(table), AbiType::Field) => {
+                let bytes = match (table.get(LE_BYTES_KEY), table.len()) {
+                    (Some(TomlTypes::Array(bytes)), 1) => try_vecmap(bytes, |byte| match byte {
+                        TomlTypes::Integer(byte) => Ok(*byte),
+                        _ => Err(InputParserError::AbiTypeMismatch(param_type.clone())),
+                    })?,
+                    _ => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
+                };
+                InputValue::Field(parse_le_bytes_to_field(&bytes, arg_name)?)
+            }
+
+            (TomlTypes::String(string), AbiType::Array { length, typ })
+                if is_byte_array_element(typ) =>
+            {
+                parse_str_to_byte_array(&string, *length, arg_name)?
+            }
 
             (TomlTypes::Array(array), AbiType::Array { typ, .. }) => {
                 let array_elements =
@@ -179,3 +238,89 @@ impl InputValue {
         Ok(input_value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::FieldElement;
+
+    use super::parse_toml;
+    use crate::{
+        errors::InputParserError, input_parser::InputValue, Abi, AbiParameter, AbiType,
+        AbiVisibility,
+    };
+
+    fn abi_with_parameters(names: &[&str]) -> Abi {
+        Abi {
+            parameters: names
+                .iter()
+                .map(|name| AbiParameter {
+                    name: name.to_string(),
+                    typ: AbiType::Field,
+                    visibility: AbiVisibility::Private,
+                })
+                .collect(),
+            return_type: None,
+            param_witnesses: BTreeMap::new(),
+            return_witnesses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_dotted_parameter_names_from_nested_tables() {
+        let abi = abi_with_parameters(&["config.threshold", "config.limits.max", "x"]);
+        let src = r#"
+            x = 1
+
+            [config]
+            threshold = 2
+
+            [config.limits]
+            max = 3
+        "#;
+
+        let inputs = parse_toml(src, &abi).unwrap();
+        assert_eq!(inputs["x"], InputValue::Field(FieldElement::from(1u128)));
+        assert_eq!(inputs["config.threshold"], InputValue::Field(FieldElement::from(2u128)));
+        assert_eq!(inputs["config.limits.max"], InputValue::Field(FieldElement::from(3u128)));
+    }
+
+    #[test]
+    fn parses_fields_from_little_endian_bytes() {
+        let abi = abi_with_parameters(&["x", "y"]);
+        let src = r#"
+            x = { le_bytes = [1, 2] }
+            y = { le_bytes = [] }
+        "#;
+
+        let inputs = parse_toml(src, &abi).unwrap();
+        assert_eq!(inputs["x"], InputValue::Field(FieldElement::from(0x0201u128)));
+        assert_eq!(inputs["y"], InputValue::Field(FieldElement::zero()));
+
+        let error = parse_toml("x = { le_bytes = [256] }\ny = 0", &abi).unwrap_err();
+        assert!(matches!(error, InputParserError::InvalidByte { value: 256, .. }));
+
+        // The modulus is the smallest value which does not fit in a field.
+        let modulus_bytes: Vec<_> =
+            FieldElement::modulus().to_bytes_le().iter().map(u8::to_string).collect();
+        let src = format!("x = {{ le_bytes = [{}] }}\ny = 0", modulus_bytes.join(", "));
+        let error = parse_toml(&src, &abi).unwrap_err();
+        assert!(matches!(error, InputParserError::ValueTooLarge { name, .. } if name == "x"));
+    }
+
+    #[test]
+    fn rejects_nested_tables_which_do_not_match_the_abi() {
+        let abi = abi_with_parameters(&["config.limits.max"]);
+        let src = r#"
+            [config]
+            limits = 3
+        "#;
+
+        let error = parse_toml(src, &abi).unwrap_err();
+        assert!(matches!(
+            error,
+            InputParserError::ExpectedTable { path, field } if path == "config.limits" && field == "max"
+        ));
+    }
+}
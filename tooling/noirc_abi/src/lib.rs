@@ -21,9 +21,11 @@ use std::{collections::BTreeMap, str};
 //
 // This ABI has nothing to do with ACVM or ACIR. Although they implicitly have a relationship
 
+pub mod diff;
 pub mod errors;
 pub mod input_parser;
 mod serialization;
+pub mod visibility;
 
 /// A map from the fields in an TOML/JSON file which correspond to some ABI to their values
 pub type InputMap = BTreeMap<String, InputValue>;
@@ -216,7 +218,7 @@ impl AbiParameter {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AbiReturnType {
     pub abi_type: AbiType,
     pub visibility: AbiVisibility,
@@ -307,6 +309,17 @@ impl Abi {
                     .clone();
 
                 if !value.matches_abi(&expected_type) {
+                    if let Some((path, expected, found)) =
+                        find_array_length_mismatch(&value, &expected_type)
+                    {
+                        return Err(AbiError::ArrayShapeMismatch {
+                            shape: array_shape(&expected_type),
+                            name: param_name,
+                            path,
+                            expected,
+                            found,
+                        });
+                    }
                     let param = self
                         .parameters
                         .iter()
@@ -500,6 +513,36 @@ fn decode_value(
     Ok(value)
 }
 
+/// Finds the first array within `value` whose length differs from that of the corresponding array in `abi_type`.
+///
+/// Returns the indices leading to that array, e.g. `[1]`, along with its expected and actual lengths.
+fn find_array_length_mismatch(
+    value: &InputValue,
+    abi_type: &AbiType,
+) -> Option<(String, u64, usize)> {
+    let (InputValue::Vec(elements), AbiType::Array { length, typ }) = (value, abi_type) else {
+        return None;
+    };
+    if elements.len() as u64 != *length {
+        return Some((String::new(), *length, elements.len()));
+    }
+    elements.iter().enumerate().find_map(|(index, element)| {
+        let (path, expected, found) = find_array_length_mismatch(element, typ)?;
+        Some((format!("[{index}]{path}"), expected, found))
+    })
+}
+
+/// Returns the dimensions of a (possibly nested) array type, e.g. `[2][3]` for `[[Field; 3]; 2]`.
+fn array_shape(abi_type: &AbiType) -> String {
+    let mut shape = String::new();
+    let mut typ = abi_type;
+    while let AbiType::Array { length, typ: element_type } = typ {
+        shape.push_str(&format!("[{length}]"));
+        typ = element_type;
+    }
+    shape
+}
+
 fn decode_string_value(field_elements: &[FieldElement]) -> String {
     let string_as_slice = vecmap(field_elements, |e| {
         let mut field_as_bytes = e.to_be_bytes();
@@ -553,11 +596,14 @@ fn range_to_vec(ranges: &[Range<Witness>]) -> Vec<Witness> {
 mod test {
     use std::collections::BTreeMap;
 
-    use acvm::{acir::native_types::Witness, FieldElement};
+    use acvm::{
+        acir::native_types::{Witness, WitnessMap},
+        FieldElement,
+    };
 
     use crate::{
-        input_parser::InputValue, Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility,
-        InputMap,
+        errors::AbiError, input_parser::InputValue, Abi, AbiParameter, AbiReturnType, AbiType,
+        AbiVisibility, InputMap, Sign,
     };
 
     #[test]
@@ -609,4 +655,131 @@ mod test {
         // We also decode the return value (we can do this immediately as we know it shares a witness with an input).
         assert_eq!(return_value.unwrap(), reconstructed_inputs["thing2"]);
     }
+
+    #[test]
+    fn decodes_tuple_return_values() {
+        let point = AbiType::Struct {
+            path: "foo::Point".to_string(),
+            fields: vec![
+                ("x".to_string(), AbiType::Field),
+                ("y".to_string(), AbiType::Integer { sign: Sign::Signed, width: 8 }),
+            ],
+        };
+        let abi = Abi {
+            parameters: vec![],
+            param_witnesses: BTreeMap::new(),
+            return_type: Some(AbiReturnType {
+                abi_type: AbiType::Tuple {
+                    fields: vec![
+                        AbiType::Boolean,
+                        AbiType::Tuple { fields: vec![point, AbiType::String { length: 2 }] },
+                    ],
+                },
+                visibility: AbiVisibility::Public,
+            }),
+            return_witnesses: vec![Witness(1), Witness(2), Witness(3), Witness(4), Witness(5)],
+        };
+
+        let witness_map = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::one()),
+            (Witness(2), FieldElement::from(10_u128)),
+            (Witness(3), FieldElement::from(254_u128)),
+            (Witness(4), FieldElement::from(u128::from(b'o'))),
+            (Witness(5), FieldElement::from(u128::from(b'k'))),
+        ]));
+        let (_, return_value) = abi.decode(&witness_map).unwrap();
+        let return_value = return_value.unwrap();
+
+        assert_eq!(
+            return_value,
+            InputValue::Vec(vec![
+                InputValue::Field(FieldElement::one()),
+                InputValue::Vec(vec![
+                    InputValue::Struct(BTreeMap::from([
+                        ("x".to_string(), InputValue::Field(FieldElement::from(10_u128))),
+                        ("y".to_string(), InputValue::Field(FieldElement::from(254_u128))),
+                    ])),
+                    InputValue::String("ok".to_string()),
+                ]),
+            ])
+        );
+        assert_eq!(
+            return_value.display(&abi.return_type.unwrap().abi_type).to_string(),
+            r#"(true, (Point { x: 0xa, y: -2 }, "ok"))"#
+        );
+    }
+
+    fn nested_array_abi(typ: AbiType) -> Abi {
+        let witness_count = typ.field_count();
+        Abi {
+            parameters: vec![AbiParameter {
+                name: "matrix".to_string(),
+                typ,
+                visibility: AbiVisibility::Private,
+            }],
+            param_witnesses: BTreeMap::from([(
+                "matrix".to_string(),
+                vec![(Witness(1)..Witness(witness_count + 1))],
+            )]),
+            return_type: None,
+            return_witnesses: Vec::new(),
+        }
+    }
+
+    fn array_of(length: u64, typ: AbiType) -> AbiType {
+        AbiType::Array { length, typ: Box::new(typ) }
+    }
+
+    fn field_values(values: &[u128]) -> InputValue {
+        InputValue::Vec(values.iter().map(|&value| InputValue::Field(value.into())).collect())
+    }
+
+    #[test]
+    fn two_dimensional_arrays_roundtrip_in_row_major_order() {
+        let abi = nested_array_abi(array_of(2, array_of(3, AbiType::Field)));
+        let matrix = InputValue::Vec(vec![field_values(&[1, 2, 3]), field_values(&[4, 5, 6])]);
+        let inputs: InputMap = BTreeMap::from([("matrix".to_string(), matrix.clone())]);
+
+        let witness_map = abi.encode(&inputs, None).unwrap();
+        for index in 1..=6_u32 {
+            assert_eq!(witness_map[&Witness(index)], FieldElement::from(u128::from(index)));
+        }
+
+        let (decoded_inputs, _) = abi.decode(&witness_map).unwrap();
+        assert_eq!(decoded_inputs["matrix"], matrix);
+    }
+
+    #[test]
+    fn three_dimensional_arrays_roundtrip() {
+        let integer = AbiType::Integer { sign: Sign::Unsigned, width: 8 };
+        let abi = nested_array_abi(array_of(2, array_of(2, array_of(2, integer))));
+        let cube = InputValue::Vec(vec![
+            InputValue::Vec(vec![field_values(&[1, 2]), field_values(&[3, 4])]),
+            InputValue::Vec(vec![field_values(&[5, 6]), field_values(&[7, 8])]),
+        ]);
+        let inputs: InputMap = BTreeMap::from([("matrix".to_string(), cube.clone())]);
+
+        let witness_map = abi.encode(&inputs, None).unwrap();
+        assert_eq!(witness_map[&Witness(8)], FieldElement::from(8_u128));
+
+        let (decoded_inputs, _) = abi.decode(&witness_map).unwrap();
+        assert_eq!(decoded_inputs["matrix"], cube);
+    }
+
+    #[test]
+    fn reports_the_expected_shape_of_mismatched_nested_arrays() {
+        let abi = nested_array_abi(array_of(2, array_of(3, AbiType::Field)));
+        let ragged = InputValue::Vec(vec![field_values(&[1, 2, 3]), field_values(&[4, 5])]);
+        let inputs: InputMap = BTreeMap::from([("matrix".to_string(), ragged)]);
+
+        let error = abi.encode(&inputs, None).unwrap_err();
+        assert!(matches!(
+            &error,
+            AbiError::ArrayShapeMismatch { path, expected: 3, found: 2, .. } if path == "[1]"
+        ));
+        assert_eq!(
+            error.to_string(),
+            "The parameter `matrix` is expected to be an array of shape [2][3] but `matrix[1]` has 2 elements rather than 3"
+        );
+    }
 }
@@ -0,0 +1,118 @@
+use std::fmt;
+
+use crate::{Abi, AbiVisibility};
+
+/// A use of visibility in a program's ABI which suggests that the verifier will not check the values the
+/// program's author intended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VisibilityWarning {
+    /// A private parameter is returned as-is, so its value is revealed to the verifier as a public return value.
+    PrivateParameterReturned { name: String },
+    /// A public parameter holds no values, so it does not add anything to the public inputs.
+    EmptyPublicParameter { name: String },
+}
+
+impl VisibilityWarning {
+    /// Returns the name of the parameter which the warning is about.
+    pub fn parameter_name(&self) -> &str {
+        match self {
+            VisibilityWarning::PrivateParameterReturned { name }
+            | VisibilityWarning::EmptyPublicParameter { name } => name,
+        }
+    }
+}
+
+impl fmt::Display for VisibilityWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VisibilityWarning::PrivateParameterReturned { name } => {
+                write!(
+                    f,
+                    "private parameter `{name}` is revealed through the program's return value"
+                )
+            }
+            VisibilityWarning::EmptyPublicParameter { name } => {
+                write!(
+                    f,
+                    "public parameter `{name}` holds no values so is not checked by the verifier"
+                )
+            }
+        }
+    }
+}
+
+/// Checks the visibility of a compiled program's parameters and return value for likely mistakes.
+pub fn check_visibility(abi: &Abi) -> Vec<VisibilityWarning> {
+    let mut warnings = Vec::new();
+    for parameter in &abi.parameters {
+        let witness_ranges = abi.param_witnesses.get(&parameter.name);
+        match parameter.visibility {
+            AbiVisibility::Private => {
+                let is_returned = witness_ranges.is_some_and(|ranges| {
+                    abi.return_witnesses
+                        .iter()
+                        .any(|witness| ranges.iter().any(|range| range.contains(witness)))
+                });
+                if is_returned {
+                    warnings.push(VisibilityWarning::PrivateParameterReturned {
+                        name: parameter.name.clone(),
+                    });
+                }
+            }
+            AbiVisibility::Public if parameter.typ.field_count() == 0 => {
+                warnings
+                    .push(VisibilityWarning::EmptyPublicParameter { name: parameter.name.clone() });
+            }
+            AbiVisibility::Public | AbiVisibility::DataBus => {}
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::acir::native_types::Witness;
+
+    use crate::{Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility};
+
+    use super::{check_visibility, VisibilityWarning};
+
+    fn parameter(name: &str, typ: AbiType, visibility: AbiVisibility) -> AbiParameter {
+        AbiParameter { name: name.to_string(), typ, visibility }
+    }
+
+    #[test]
+    fn warns_about_suspicious_visibility() {
+        let abi = Abi {
+            parameters: vec![
+                parameter("x", AbiType::Field, AbiVisibility::Private),
+                parameter(
+                    "empty",
+                    AbiType::Array { length: 0, typ: Box::new(AbiType::Field) },
+                    AbiVisibility::Public,
+                ),
+                parameter("y", AbiType::Field, AbiVisibility::Private),
+            ],
+            param_witnesses: BTreeMap::from([
+                ("x".to_string(), vec![Witness(1)..Witness(2)]),
+                ("empty".to_string(), Vec::new()),
+                ("y".to_string(), vec![Witness(2)..Witness(3)]),
+            ]),
+            return_type: Some(AbiReturnType {
+                abi_type: AbiType::Field,
+                visibility: AbiVisibility::Public,
+            }),
+            return_witnesses: vec![Witness(1)],
+        };
+
+        assert_eq!(
+            check_visibility(&abi),
+            vec![
+                VisibilityWarning::PrivateParameterReturned { name: "x".to_string() },
+                VisibilityWarning::EmptyPublicParameter { name: "empty".to_string() },
+            ]
+        );
+    }
+}